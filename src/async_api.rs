@@ -0,0 +1,118 @@
+//! Async versions of the command layer and the core focusing operations, for embedding in a tokio event loop (e.g. a
+//! status bar applet) without blocking it. Enabled with the `async` feature.
+//!
+//! The commands run on `tokio::process`, so the runtime needs its IO and time drivers enabled (e.g. `#[tokio::main]`).
+
+use anyhow::{Context, Result};
+use std::process::Output;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::task;
+
+use crate::config::Config;
+use crate::external_tools;
+use crate::models::{FocusDirection, MonitorIndex};
+use crate::services::window_focuser;
+
+/// Same as the sync `call_command`: runs the command, failing if it doesn't finish within `EWS_COMMAND_TIMEOUT_MS`.
+pub async fn async_call_command(args: &[&str]) -> Result<Output> {
+    async_call_command_with_timeout(args, external_tools::get_command_timeout()).await
+}
+
+/// Runs the command, killing it and returning an error if it doesn't finish within the timeout.
+pub async fn async_call_command_with_timeout(args: &[&str], timeout: Duration) -> Result<Output> {
+    let start = Instant::now();
+    let result = run_command_with_timeout(args, timeout).await;
+    external_tools::report_timing(args, start.elapsed());
+
+    result
+}
+
+/// Same as the sync `get_command_output`: runs the command and returns its stdout, replacing any invalid UTF-8.
+pub async fn async_get_command_output(args: &[&str]) -> Result<String> {
+    let raw_stdout = async_call_command(args).await?.stdout;
+    Ok(String::from_utf8_lossy(&raw_stdout).into_owned())
+}
+
+/// Same as `focus`: focuses onto the closest window in the given direction.
+///
+/// Focusing takes several tool calls that depend on each other, so it runs on tokio's blocking thread pool.
+pub async fn focus_by_direction(direction: FocusDirection, config: Config) -> Result<()> {
+    task::spawn_blocking(move || window_focuser::focus_by_direction(direction, &config)).await?
+}
+
+/// Same as `focus_monitor`: focuses onto the window on the monitor with the given (0-based, left-to-right) index.
+pub async fn focus_by_monitor_index(index: usize, config: Config) -> Result<()> {
+    task::spawn_blocking(move || {
+        window_focuser::focus_by_monitor_index(MonitorIndex(index), &config)
+    })
+    .await?
+}
+
+async fn run_command_with_timeout(args: &[&str], timeout: Duration) -> Result<Output> {
+    // The child is killed when the timed out future is dropped, the same as the sync version kills a hung tool.
+    let output = Command::new(args[0])
+        .args(&args[1..])
+        .kill_on_drop(true)
+        .output();
+
+    tokio::time::timeout(timeout, output)
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Command timed out after {}ms: {}",
+                timeout.as_millis(),
+                args.join(" ")
+            )
+        })?
+        .with_context(|| format!("Failed to execute command: {}", args.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_async_get_command_output() {
+        let output = async_get_command_output(&["echo", "hello world"])
+            .await
+            .unwrap();
+        assert_eq!(output, "hello world\n");
+    }
+
+    #[tokio::test]
+    async fn test_async_get_command_output_missing_command() {
+        let result = async_get_command_output(&["ews-no-such-command"]).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Failed to execute command: ews-no-such-command"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_call_command_timeout() {
+        let start = Instant::now();
+        let result =
+            async_call_command_with_timeout(&["sleep", "5"], Duration::from_millis(50)).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Command timed out after 50ms: sleep 5"
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commands() {
+        let (first, second) = tokio::join!(
+            async_get_command_output(&["echo", "first"]),
+            async_get_command_output(&["echo", "second"])
+        );
+
+        assert_eq!(
+            (first.unwrap(), second.unwrap()),
+            ("first\n".to_owned(), "second\n".to_owned())
+        );
+    }
+}
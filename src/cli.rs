@@ -1,46 +1,720 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::models::{FocusDirection, MonitorIndex};
+use crate::config::{Backend, Config, FocusStrategy};
+use crate::external_tools::{self, i3, xrandr};
+use crate::models::{FocusDirection, MonitorIndex, MonitorSelector, Region, SortOrder, Window};
+use crate::services::daemon::{self, Request};
+use crate::services::report::WindowTemplate;
 use crate::services::window_focuser;
+use crate::services::{doctor, monitor_cache, report, snapshot};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[command(subcommand)]
     cmd: Commands,
+
+    /// Also consider windows that have been placed off-screen, moving them onto the first monitor when focused.
+    #[arg(long, global = true)]
+    include_offscreen: bool,
+
+    /// Operate on the given X screen (e.g. 1 for `:0.1`) instead of the one in `DISPLAY`.
+    #[arg(long, global = true)]
+    screen: Option<u32>,
+
+    /// Consider the windows on every virtual desktop, switching desktops when focusing a window on another one.
+    #[arg(long, global = true)]
+    workspace_all: bool,
+
+    /// Treat windows that aren't within any monitor (e.g. just past the edge of the workspace) as being on the
+    /// nearest one instead of failing.
+    #[arg(long, global = true)]
+    lenient_bounds: bool,
+
+    /// How windows are focused. Valid strategies are [wmctrl-activate, xdotool-activate, xdotool-focus].
+    #[arg(long, global = true)]
+    focus_strategy: Option<String>,
+
+    /// Only give the window input focus instead of also raising it above the other windows (same as
+    /// `--focus-strategy xdotool-focus`).
+    #[arg(long, global = true)]
+    no_raise: bool,
+
+    /// Never focus windows whose class contains the given substring (case-insensitively), in addition to
+    /// `EWS_EXCLUDED_CLASSES`. Can be given multiple times.
+    #[arg(long, global = true)]
+    exclude_class: Vec<String>,
+
+    /// Only consider the windows whose class contains the given substring (case-insensitively), e.g. to only
+    /// navigate between browsers.
+    #[arg(long, global = true)]
+    only_class: Option<String>,
+
+    /// Only consider the windows that were used within the given number of minutes of the most recently used one.
+    #[arg(long, global = true, value_name = "MINUTES")]
+    since: Option<u64>,
+
+    /// Still consider the windows that don't report when they were last used when filtering with `--since`.
+    #[arg(long, global = true)]
+    include_untimed: bool,
+
+    /// Show a desktop notification (through `notify-send`) with the focused window's class and title.
+    #[arg(long, global = true)]
+    notify: bool,
+
+    /// Check that the focus actually moved to the window afterwards, trying once more (and failing) if it didn't.
+    #[arg(long, global = true)]
+    verify: bool,
+
+    /// Print how long each external tool call (e.g. `wmctrl -l`) took to stderr.
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Forward the command to the running daemon instead of executing it directly.
+    #[arg(long, global = true)]
+    send: bool,
+
+    /// Re-detect the monitor layout instead of using the cached one (see `EWS_CACHE_MONITORS`).
+    #[arg(long, global = true)]
+    refresh: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum Commands {
     /// Focuses onto the closest window in the given direction; wraps around until a window is found.
     Direction {
-        /// Valid directions are [left, right].
+        /// Valid directions are [left, right, up, down].
         direction: String,
+
+        /// Maximize the window on its monitor after focusing it.
+        #[arg(long)]
+        maximize: bool,
+
+        /// Only move between the windows on the current monitor, wrapping around within it.
+        #[arg(long)]
+        current_monitor_only: bool,
     },
-    /// Focuses onto the window on the monitor with the given index.
+    /// Focuses onto the window on the monitor with the given index or name.
     Monitor {
-        /// The index is 0-based and increases from left-to-right.
-        monitor: usize,
+        /// Either the index, which is 0-based and increases from left-to-right, the xrandr output name (e.g. DP-2),
+        /// or the EDID-based identity (e.g. GSM-5B09-0001E2B4) as printed by `monitors`.
+        monitor: String,
+
+        /// Maximize the window on its monitor after focusing it.
+        #[arg(long)]
+        maximize: bool,
+
+        /// Focus the rightmost window on the monitor instead of the leftmost one.
+        #[arg(long)]
+        reverse: bool,
+
+        /// Focus the window this many places (0-based) from the left of the monitor (or from the right, with
+        /// `--reverse`) instead of the first one.
+        #[arg(long, value_name = "K")]
+        nth: Option<usize>,
+    },
+    /// Focuses onto the (leftmost) window on the monitor next to the current one in the given direction; wraps around
+    /// like `direction`, but always moves a whole monitor at a time.
+    MonitorRelative {
+        /// Valid directions are [left, right, up, down].
+        direction: String,
+    },
+    /// Focuses onto the window on the primary monitor (or the first monitor if none is primary).
+    Primary,
+    /// Focuses onto the window that has gone the longest without being used (by its `_NET_WM_USER_TIME`, falling back
+    /// to the bottom of the stacking order).
+    Oldest,
+    /// Focuses onto the leftmost window whose center is within the given horizontal slice of the workspace.
+    Region {
+        /// A range of percentages of the workspace's width, e.g. 0-25 for the leftmost quarter or 25-75 for the middle.
+        fraction: String,
+    },
+    /// Focuses onto the window on the opposite monitor: the other one on a dual-monitor setup, or the horizontally
+    /// mirrored one (e.g. first <-> last) when there are more.
+    Flip,
+    /// Focuses onto the window with the given ID, which has to be on the current workspace.
+    Focus {
+        /// Either hex with a 0x prefix (e.g. 0x05000006, as printed by `list`) or decimal (e.g. 83886086).
+        id: String,
     },
+    /// Focuses onto the largest window (by area) on the current workspace.
+    Largest,
+    /// Focuses onto the window that covers the most of the current monitor.
+    Dominant,
+    /// Raises every window of the given class on the current workspace, focusing the leftmost one.
+    RaiseGroup {
+        /// Matches either part of the window's WM_CLASS (e.g. "code" or "Code" for "code.Code"), case-insensitively.
+        class: String,
+    },
+    /// Focuses the next window (from left to right) with the same class as the focused one; wraps around at the ends.
+    NextSameClass {
+        /// Valid directions are [left, right, up, down]; right/down move to the next window and left/up to the previous one.
+        direction: String,
+    },
+    /// Focuses the next window (from left to right) on the focused window's monitor; wraps around at the ends without leaving the monitor.
+    CycleMonitor {
+        /// Valid directions are [left, right, up, down]; right/down move to the next window and left/up to the previous one.
+        direction: String,
+    },
+    /// Briefly focuses the leftmost window of the given class, then restores focus to the original window.
+    Flash {
+        /// Matches either part of the window's WM_CLASS (e.g. "code" or "Code" for "code.Code"), case-insensitively.
+        class: String,
+    },
+    /// Focuses the leftmost window of the given class, or launches the given command (through `sh -c`) if there isn't one.
+    ActivateOrLaunch {
+        /// Matches either part of the window's WM_CLASS (e.g. "code" or "Code" for "code.Code"), case-insensitively.
+        class: String,
+
+        /// The command that starts the app, e.g. "firefox --new-window".
+        launch: String,
+    },
+    /// Keeps the leftmost window of the given class above the others and focuses it; the next time, lets it back down.
+    PeekToggle {
+        /// Matches either part of the window's WM_CLASS (e.g. "code" or "Code" for "code.Code"), case-insensitively.
+        class: String,
+    },
+    /// Keeps the leftmost window of the given class focused, refocusing it whenever focus moves away, until the window is
+    /// closed or it's interrupted (e.g. with Ctrl-C).
+    Pin {
+        /// Matches either part of the window's WM_CLASS (e.g. "code" or "Code" for "code.Code"), case-insensitively.
+        class: String,
+
+        /// How often (in milliseconds) to check that the window is still focused. Overrides `EWS_PIN_INTERVAL_MS`.
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: Option<u64>,
+    },
+    /// Shows the windows in a chooser (`EWS_CHOOSER`, `rofi -dmenu` by default) and focuses the chosen one.
+    Select,
+    /// Minimizes every other window on the monitor of the focused window.
+    SoloMonitor,
+    /// Restores every other window on the monitor of the focused window, undoing `solo-monitor`.
+    UnsoloMonitor,
+    /// Focuses the window after the focused one in the given sort order, wrapping around at the end.
+    Cycle {
+        /// Valid orders are [position, class, title, mru, stacking].
+        #[arg(long, default_value = "position")]
+        sort: String,
+    },
+    /// Prints the windows on the current workspace (ID, class, and title) in the given sort order.
+    List {
+        /// Valid orders are [position, class, title, mru, stacking].
+        #[arg(long, default_value = "position")]
+        sort: String,
+
+        /// Print each window with a template instead, e.g. "{hex_id} {monitor} {class} {title}". The placeholders are
+        /// [id, hex_id, x, y, w, h, class, title, monitor]; use {{ and }} for literal braces.
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Only list the windows on the monitor with the given index.
+        #[arg(long, value_name = "INDEX")]
+        monitor: Option<usize>,
+    },
+    /// Tiles every window on the monitor of the focused window into a grid (two columns, as many rows as needed).
+    TileMonitor,
+    /// Centers the focused window on its monitor, keeping its size.
+    Center,
+    /// Saves the position and size of every window on the current workspace, for `restore` to put back later.
+    Snapshot {
+        /// The file to save to. Defaults to `snapshot.json` in the cache directory.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Moves and resizes the windows back to how they were in the snapshot, matching them by ID or else by class.
+    Restore {
+        /// The file to restore from. Defaults to `snapshot.json` in the cache directory.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Moves the focused window onto the adjacent monitor in the given direction; wraps around like `direction`.
+    MoveDirection {
+        /// Valid directions are [left, right, up, down].
+        direction: String,
+    },
+    /// Prints the focused window's ID, class, and title, even if it's one that's never focused (e.g. a dialog or a dock).
+    Info,
+    /// Prints the distinct window classes, including excluded ones, e.g. to find the ones to exclude or focus by.
+    Classes {
+        /// Also print how many windows have each class.
+        #[arg(long)]
+        counts: bool,
+    },
+    /// Prints the number of windows on each monitor.
+    Counts {
+        /// Print the counts as a JSON object keyed by monitor index instead.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Prints the position and size of the monitor that the focused window is on, as `X=.. Y=.. W=.. H=..`.
+    ActiveMonitorGeometry {
+        /// Print the geometry as a JSON object instead.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Prints each monitor (with its index, name, dimensions, and position) along with whether it's primary or has the
+    /// focused window on it, and how many windows it has.
+    Status {
+        /// Print the statuses as a JSON array instead.
+        #[arg(long)]
+        json: bool,
+
+        /// Print a single line for status bars instead, e.g. `[M0:3] [M1*:1] [M2:0]`, where the active monitor is
+        /// starred.
+        #[arg(long, conflicts_with = "json")]
+        compact: bool,
+
+        /// What to put between the monitors with `--compact`.
+        #[arg(long, default_value = " ", requires = "compact")]
+        separator: String,
+    },
+    /// Prints the monitors (with their indices, names, positions, and identities) as they're laid out in the grid.
+    Monitors {
+        /// Print the monitors of every X screen (e.g. `:0.0` and `:0.1`), one screen after the other.
+        #[arg(long)]
+        all_screens: bool,
+
+        /// Also print each monitor's DPI (from its physical size, if xrandr reports one).
+        #[arg(long)]
+        dpi: bool,
+    },
+    /// Checks whether the external tools are installed and the monitors and windows are detected correctly,
+    /// printing a PASS/WARN/FAIL report.
+    Doctor,
+    /// Runs in the background, executing commands sent with `--send` over a Unix socket to avoid the per-process startup cost.
+    Daemon,
 }
 
 pub fn run() -> Result<()> {
-    let args = Args::parse();
+    run_with_output(std::env::args_os(), &mut std::io::stdout()).inspect_err(|error| {
+        // Let clap print the help, version, or usage error itself, with its usual exit code.
+        if let Some(clap_error) = error.downcast_ref::<clap::Error>() {
+            clap_error.exit();
+        }
+    })
+}
+
+/// Same as `run`, but with the given arguments (starting with the binary's name) instead of the process's, and the
+/// reporting commands (e.g. `list` or `status`) write to the given output instead of stdout.
+///
+/// Invalid arguments, as well as `--help` and `--version`, are returned as a `clap::Error`.
+pub fn run_with_output(
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let args = Args::try_parse_from(args)?;
+    let config = build_config(&args)?;
+
+    external_tools::set_command_timeout(config.command_timeout);
+    external_tools::set_timings(config.timings);
+
+    if let Some(screen) = config.screen {
+        external_tools::set_screen(screen)?;
+    }
+
+    if args.send {
+        let global_flags = get_global_flags(&args);
+
+        // The daemon focuses with its own config, so these would silently have no effect.
+        if !global_flags.is_empty() {
+            return Err(anyhow::anyhow!(
+                "{} can't be combined with --send; pass them to the daemon instead",
+                global_flags.join(", ")
+            ));
+        }
+
+        return daemon::send(&to_request(args.cmd)?, &config);
+    }
+
+    // The doctor reports missing tools itself instead of bailing on the first one.
+    if !matches!(args.cmd, Commands::Doctor) {
+        external_tools::check_if_all_tools_installed()?;
+
+        if config.backend == Backend::I3 {
+            i3::check_if_installed()?;
+        }
+    }
+
+    if args.refresh {
+        monitor_cache::clear()?;
+    }
 
     match args.cmd {
-        Commands::Direction { direction } => {
-            window_focuser::focus_by_direction(FocusDirection::try_from(direction)?)
+        Commands::Direction { direction, .. } => {
+            window_focuser::focus_by_direction(FocusDirection::try_from(direction)?, &config)
+        }
+        Commands::Monitor { monitor, .. } => {
+            window_focuser::focus_by_monitor(&MonitorSelector::from_string(&monitor), &config)
+        }
+        Commands::MonitorRelative { direction } => {
+            window_focuser::focus_relative_monitor(FocusDirection::try_from(direction)?, &config)
+        }
+        Commands::Primary => window_focuser::focus_primary(&config),
+        Commands::Focus { id } => window_focuser::focus_by_id(&id.parse()?, &config),
+        Commands::Largest => window_focuser::focus_largest(&config),
+        Commands::Dominant => window_focuser::focus_dominant(&config),
+        Commands::Oldest => window_focuser::focus_oldest(&config),
+        Commands::Region { fraction } => {
+            window_focuser::focus_region(&Region::try_from(fraction)?, &config)
+        }
+        Commands::Flip => window_focuser::flip(&config),
+        Commands::RaiseGroup { class } => window_focuser::raise_group(&class, &config),
+        Commands::Flash { class } => window_focuser::flash(&class, &config),
+        Commands::ActivateOrLaunch { class, launch } => {
+            window_focuser::activate_or_launch(&class, &launch, &config)
         }
-        Commands::Monitor { monitor } => {
-            window_focuser::focus_by_monitor_index(MonitorIndex(monitor))
+        Commands::PeekToggle { class } => window_focuser::peek_toggle(&class, &config),
+        Commands::Pin { class, .. } => window_focuser::pin(&class, &config),
+        Commands::NextSameClass { direction } => {
+            window_focuser::next_same_class(FocusDirection::try_from(direction)?, &config)
+        }
+        Commands::CycleMonitor { direction } => {
+            window_focuser::cycle_monitor(FocusDirection::try_from(direction)?, &config)
+        }
+        Commands::Select => window_focuser::select(&config),
+        Commands::SoloMonitor => window_focuser::solo_monitor(&config),
+        Commands::UnsoloMonitor => window_focuser::unsolo_monitor(&config),
+        Commands::TileMonitor => window_focuser::tile_monitor(&config),
+        Commands::Center => window_focuser::center(&config),
+        Commands::Snapshot { file } => {
+            window_focuser::save_snapshot(&file.unwrap_or_else(snapshot::get_default_path), &config)
+        }
+        Commands::Restore { file } => window_focuser::restore_snapshot(
+            &file.unwrap_or_else(snapshot::get_default_path),
+            &config,
+        ),
+        Commands::MoveDirection { direction } => {
+            window_focuser::move_by_direction(FocusDirection::try_from(direction)?, &config)
+        }
+        Commands::Cycle { sort } => window_focuser::cycle(&SortOrder::try_from(sort)?, &config),
+        Commands::List {
+            sort,
+            format,
+            monitor,
+        } => {
+            let sort_order = SortOrder::try_from(sort)?;
+            let monitor = monitor.map(MonitorIndex);
+            let template = format.as_deref().map(WindowTemplate::parse).transpose()?;
+
+            let windows =
+                window_focuser::list_windows_with_monitors(&sort_order, monitor.as_ref(), &config)?;
+
+            write_windows(out, windows, template.as_ref())
+        }
+        Commands::Info => {
+            let (id, class, title) = window_focuser::describe_focused_window()?;
+
+            writeln!(
+                out,
+                "{} {} {}",
+                id.to_hex_string(),
+                class.as_deref().unwrap_or("N/A"),
+                title.unwrap_or_default()
+            )?;
+            Ok(())
+        }
+        Commands::Classes { counts } => {
+            let classes = window_focuser::list_window_classes(&config)?;
+
+            writeln!(out, "{}", report::format_classes(&classes, counts))?;
+            Ok(())
+        }
+        Commands::Counts { json } => {
+            let counts = window_focuser::count_windows_per_monitor(&config)?;
+
+            if json {
+                writeln!(out, "{}", report::format_counts_json(&counts))?;
+            } else {
+                writeln!(out, "{}", report::format_counts(&counts))?;
+            }
+
+            Ok(())
+        }
+        Commands::ActiveMonitorGeometry { json } => {
+            let bounds = window_focuser::get_active_monitor_bounds(&config)?;
+
+            if json {
+                writeln!(out, "{}", report::format_geometry_json(bounds))?;
+            } else {
+                writeln!(out, "{}", report::format_geometry(bounds))?;
+            }
+
+            Ok(())
+        }
+        Commands::Status {
+            json,
+            compact,
+            separator,
+        } => {
+            let statuses = window_focuser::get_monitor_statuses(&config)?;
+
+            if compact {
+                writeln!(
+                    out,
+                    "{}",
+                    report::format_status_compact(&statuses, &separator)
+                )?;
+            } else if json {
+                writeln!(out, "{}", report::format_status_json(&statuses))?;
+            } else {
+                writeln!(out, "{}", report::format_status(&statuses))?;
+            }
+
+            Ok(())
+        }
+        Commands::Monitors { all_screens, dpi } => {
+            let identities = xrandr::get_monitor_identities().unwrap_or_else(|error| {
+                log::warn!("Failed to read the monitor identities: {error:#}");
+                Default::default()
+            });
+
+            let workspace = if all_screens {
+                xrandr::parse_all_screens_workspace()?
+            } else {
+                xrandr::parse_workspace()?
+            };
+
+            let monitor_grid = workspace.monitor_grid.with_identities(&identities);
+
+            writeln!(out, "{}", report::format_monitors(&monitor_grid, dpi))?;
+            Ok(())
         }
+        Commands::Doctor => {
+            let checks = doctor::run(&config);
+            writeln!(out, "{}", doctor::format_report(&checks))?;
+
+            if doctor::has_failures(&checks) {
+                return Err(anyhow::anyhow!("Some checks failed"));
+            }
+
+            Ok(())
+        }
+        Commands::Daemon => daemon::run(&config),
+    }
+}
+
+/// Writes the listed windows, one per line, either in the default format or with the given template.
+///
+/// Nothing is written when there are no windows, so that scripts don't see an empty line as a window.
+fn write_windows(
+    out: &mut impl Write,
+    windows: Vec<(Window, Option<MonitorIndex>)>,
+    template: Option<&WindowTemplate>,
+) -> Result<()> {
+    if windows.is_empty() {
+        return Ok(());
+    }
+
+    let output = match template {
+        Some(template) => report::format_windows_with_template(&windows, template),
+        None => report::format_windows(
+            &windows
+                .into_iter()
+                .map(|(window, _)| window)
+                .collect::<Vec<Window>>(),
+        ),
+    };
+
+    writeln!(out, "{output}")?;
+    Ok(())
+}
+
+/// The global flags (other than `--send`) that were given, e.g. `["--verify", "--since"]`.
+fn get_global_flags(args: &Args) -> Vec<&'static str> {
+    let flags = [
+        ("--include-offscreen", args.include_offscreen),
+        ("--screen", args.screen.is_some()),
+        ("--workspace-all", args.workspace_all),
+        ("--lenient-bounds", args.lenient_bounds),
+        ("--focus-strategy", args.focus_strategy.is_some()),
+        ("--no-raise", args.no_raise),
+        ("--exclude-class", !args.exclude_class.is_empty()),
+        ("--only-class", args.only_class.is_some()),
+        ("--since", args.since.is_some()),
+        ("--include-untimed", args.include_untimed),
+        ("--notify", args.notify),
+        ("--verify", args.verify),
+        ("--timings", args.timings),
+        ("--refresh", args.refresh),
+    ];
+
+    flags
+        .into_iter()
+        .filter(|(_, is_given)| *is_given)
+        .map(|(flag, _)| flag)
+        .collect()
+}
+
+/// Converts the command into the request that the daemon executes for it.
+fn to_request(cmd: Commands) -> Result<Request> {
+    match cmd {
+        Commands::Direction {
+            direction,
+            maximize,
+            current_monitor_only,
+        } => Ok(Request::Direction {
+            direction: FocusDirection::try_from(direction)?,
+            maximize,
+            current_monitor_only,
+        }),
+        Commands::Monitor {
+            monitor,
+            maximize,
+            reverse,
+            nth,
+        } => Ok(Request::Monitor {
+            monitor: MonitorSelector::from_string(&monitor),
+            maximize,
+            reverse,
+            nth,
+        }),
+        Commands::MonitorRelative { direction } => Ok(Request::MonitorRelative(
+            FocusDirection::try_from(direction)?,
+        )),
+        Commands::Primary => Ok(Request::Primary),
+        Commands::Focus { id } => Ok(Request::Focus(id.parse()?)),
+        Commands::Largest => Ok(Request::Largest),
+        Commands::Dominant => Ok(Request::Dominant),
+        Commands::Oldest => Ok(Request::Oldest),
+        Commands::Region { fraction } => Ok(Request::Region(Region::try_from(fraction)?)),
+        Commands::Flip => Ok(Request::Flip),
+        Commands::RaiseGroup { class } => Ok(Request::RaiseGroup(class)),
+        Commands::Flash { class } => Ok(Request::Flash(class)),
+        Commands::PeekToggle { class } => Ok(Request::PeekToggle(class)),
+        Commands::NextSameClass { direction } => {
+            Ok(Request::NextSameClass(FocusDirection::try_from(direction)?))
+        }
+        Commands::CycleMonitor { direction } => {
+            Ok(Request::CycleMonitor(FocusDirection::try_from(direction)?))
+        }
+        Commands::SoloMonitor => Ok(Request::SoloMonitor),
+        Commands::UnsoloMonitor => Ok(Request::UnsoloMonitor),
+        Commands::TileMonitor => Ok(Request::TileMonitor),
+        Commands::Center => Ok(Request::Center),
+        Commands::MoveDirection { direction } => {
+            Ok(Request::MoveDirection(FocusDirection::try_from(direction)?))
+        }
+        Commands::Cycle { sort } => Ok(Request::Cycle(SortOrder::try_from(sort)?)),
+        // The daemon splits requests on whitespace, which would break up the launch command.
+        Commands::ActivateOrLaunch { .. } => Err(anyhow::anyhow!(
+            "activate-or-launch can't be sent to the daemon"
+        )),
+        Commands::Select
+        | Commands::Pin { .. }
+        | Commands::Snapshot { .. }
+        | Commands::Restore { .. }
+        | Commands::List { .. }
+        | Commands::Info
+        | Commands::Classes { .. }
+        | Commands::Counts { .. }
+        | Commands::ActiveMonitorGeometry { .. }
+        | Commands::Status { .. }
+        | Commands::Monitors { .. }
+        | Commands::Doctor
+        | Commands::Daemon => Err(anyhow::anyhow!(
+            "Only focusing commands can be sent to the daemon"
+        )),
+    }
+}
+
+/// Builds the config from the environment, with any flags given on the command line taking precedence.
+fn build_config(args: &Args) -> Result<Config> {
+    let mut config = Config::from_env();
+
+    config
+        .excluded_classes
+        .extend(args.exclude_class.iter().cloned());
+
+    if let Some(only_class) = &args.only_class {
+        config.only_class = Some(only_class.clone());
+    }
+
+    if let Some(minutes) = args.since {
+        config.since = Some(Duration::from_secs(minutes * 60));
+    }
+
+    if args.include_untimed {
+        config.include_untimed = true;
+    }
+
+    if let Some(screen) = args.screen {
+        config.screen = Some(screen);
+    }
+
+    if args.include_offscreen {
+        config.include_offscreen = true;
+    }
+
+    if args.workspace_all {
+        config.workspace_all = true;
+    }
+
+    if args.lenient_bounds {
+        config.lenient_bounds = true;
+    }
+
+    if args.notify {
+        config.notify = true;
+    }
+
+    if args.verify {
+        config.verify_focus = true;
+    }
+
+    if args.timings {
+        config.timings = true;
+    }
+
+    if let Some(strategy) = &args.focus_strategy {
+        config.focus_strategy = FocusStrategy::try_from_string(strategy)?;
+    }
+
+    if args.no_raise {
+        config.focus_strategy = FocusStrategy::XdotoolFocus;
+    }
+
+    if let Commands::Direction { maximize: true, .. } | Commands::Monitor { maximize: true, .. } =
+        args.cmd
+    {
+        config.maximize = true;
+    }
+
+    if let Commands::Direction {
+        current_monitor_only: true,
+        ..
+    } = args.cmd
+    {
+        config.current_monitor_only = true;
+    }
+
+    if let Commands::Monitor { reverse: true, .. } = args.cmd {
+        config.pick_last_window = true;
+    }
+
+    if let Commands::Monitor { nth: Some(nth), .. } = args.cmd {
+        config.nth_window = Some(nth);
+    }
+
+    if let Commands::Pin {
+        interval: Some(interval),
+        ..
+    } = args.cmd
+    {
+        config.pin_interval = Duration::from_millis(interval);
     }
+
+    Ok(config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::MonitorIndex;
 
     #[test]
     fn test_args_parsing_direction_left() {
@@ -60,7 +734,7 @@ mod tests {
 
     #[test]
     fn test_args_parsing_invalid_direction() {
-        let direction = "up";
+        let direction = "diagonal";
         let result = FocusDirection::try_from(direction);
         assert!(result.is_err());
     }
@@ -72,6 +746,192 @@ mod tests {
         assert_eq!(monitor_index.0, 3);
     }
 
+    #[test]
+    fn test_to_request() {
+        let request = to_request(Commands::Direction {
+            direction: "up".to_owned(),
+            maximize: false,
+            current_monitor_only: true,
+        })
+        .unwrap();
+
+        assert_eq!(
+            request,
+            Request::Direction {
+                direction: FocusDirection::Up,
+                maximize: false,
+                current_monitor_only: true
+            }
+        );
+        assert_eq!(
+            to_request(Commands::Monitor {
+                monitor: "2".to_owned(),
+                maximize: true,
+                reverse: true,
+                nth: Some(1)
+            })
+            .unwrap(),
+            Request::Monitor {
+                monitor: MonitorSelector::Index(MonitorIndex(2)),
+                maximize: true,
+                reverse: true,
+                nth: Some(1)
+            }
+        );
+        assert_eq!(
+            to_request(Commands::Cycle {
+                sort: "class".to_owned()
+            })
+            .unwrap(),
+            Request::Cycle(SortOrder::Class)
+        );
+        assert!(to_request(Commands::Cycle {
+            sort: "size".to_owned()
+        })
+        .is_err());
+        assert!(to_request(Commands::List {
+            sort: "position".to_owned(),
+            format: None,
+            monitor: None
+        })
+        .is_err());
+        assert!(to_request(Commands::ActivateOrLaunch {
+            class: "firefox".to_owned(),
+            launch: "firefox --new-window".to_owned()
+        })
+        .is_err());
+        assert!(to_request(Commands::Pin {
+            class: "firefox".to_owned(),
+            interval: None
+        })
+        .is_err());
+        assert!(to_request(Commands::Daemon).is_err());
+    }
+
+    mod run_with_output {
+        use super::*;
+
+        #[test]
+        fn test_doctor() {
+            let mut out = Vec::new();
+
+            // Whether the checks pass depends on the machine, but the report is written either way.
+            let _ = run_with_output(["easy-window-switcher-rs", "doctor"], &mut out);
+
+            assert!(String::from_utf8(out).unwrap().contains("wmctrl"));
+        }
+    }
+
+    mod get_global_flags {
+        use super::*;
+
+        fn get_flags(args: &[&str]) -> Vec<&'static str> {
+            let args =
+                Args::try_parse_from(["easy-window-switcher-rs"].iter().chain(args)).unwrap();
+
+            get_global_flags(&args)
+        }
+
+        #[test]
+        fn test_no_flags() {
+            assert!(get_flags(&["--send", "direction", "left"]).is_empty());
+        }
+
+        #[test]
+        fn test_given_flags() {
+            assert_eq!(
+                get_flags(&["--send", "direction", "left", "--verify", "--since", "5"]),
+                vec!["--since", "--verify"]
+            );
+            assert_eq!(
+                get_flags(&["primary", "--exclude-class", "slack", "--screen", "1"]),
+                vec!["--screen", "--exclude-class"]
+            );
+        }
+
+        #[test]
+        fn test_command_flags_ignored() {
+            assert!(get_flags(&["--send", "monitor", "1", "--maximize", "--reverse"]).is_empty());
+        }
+    }
+
+    mod write_windows {
+        use super::*;
+        use crate::models::WindowId;
+
+        fn create_windows() -> Vec<(Window, Option<MonitorIndex>)> {
+            vec![
+                (
+                    Window::new(
+                        WindowId(0x05000006),
+                        0,
+                        0,
+                        800,
+                        600,
+                        "code.Code".to_string(),
+                        "main.rs - Visual Studio Code".to_string(),
+                    ),
+                    Some(MonitorIndex(0)),
+                ),
+                (
+                    Window::new(
+                        WindowId(0x03a00003),
+                        1920,
+                        0,
+                        800,
+                        600,
+                        "firefox.Firefox".to_string(),
+                        "Mozilla Firefox".to_string(),
+                    ),
+                    None,
+                ),
+            ]
+        }
+
+        #[test]
+        fn test_default_format() {
+            let mut out = Vec::new();
+            write_windows(&mut out, create_windows(), None).unwrap();
+
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                "0x05000006 code.Code main.rs - Visual Studio Code\n\
+                 0x03a00003 firefox.Firefox Mozilla Firefox\n"
+            );
+        }
+
+        #[test]
+        fn test_template() {
+            let template = WindowTemplate::parse("{monitor}: {title}").unwrap();
+
+            let mut out = Vec::new();
+            write_windows(&mut out, create_windows(), Some(&template)).unwrap();
+
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                "0: main.rs - Visual Studio Code\n-: Mozilla Firefox\n"
+            );
+        }
+
+        #[test]
+        fn test_no_windows() {
+            let mut out = Vec::new();
+            write_windows(&mut out, Vec::new(), None).unwrap();
+
+            assert!(out.is_empty());
+        }
+
+        #[test]
+        fn test_no_windows_with_template() {
+            let template = WindowTemplate::parse("{title}").unwrap();
+
+            let mut out = Vec::new();
+            write_windows(&mut out, Vec::new(), Some(&template)).unwrap();
+
+            assert!(out.is_empty());
+        }
+    }
+
     // Note: Testing the actual run() function and command execution would require
     // mocking the external tools and window management system, which is beyond
     // the scope of unit tests. Integration tests would be more appropriate for
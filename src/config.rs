@@ -0,0 +1,835 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::external_tools::DEFAULT_COMMAND_TIMEOUT;
+use crate::models::FocusDirection;
+
+/// The window types that are excluded by default, since they're never windows that anyone wants to focus.
+pub const DEFAULT_EXCLUDED_WINDOW_TYPES: [&str; 3] = ["DOCK", "DESKTOP", "SPLASH"];
+
+/// How often `pin` checks whether the pinned window is still focused, by default.
+pub const DEFAULT_PIN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The chooser that `select` uses by default.
+pub const DEFAULT_CHOOSER: [&str; 2] = ["rofi", "-dmenu"];
+
+/// Where the windows and monitors come from, and how windows are focused.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Backend {
+    /// The X tools (`wmctrl`, `xdotool`, and `xrandr`), which work under any EWMH-compliant window manager.
+    #[default]
+    X11,
+    /// i3's IPC (through `i3-msg`), which only considers the windows on i3's visible workspaces.
+    I3,
+}
+
+impl Backend {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        match value {
+            "x11" => Ok(Backend::X11),
+            "i3" => Ok(Backend::I3),
+            _ => Err(anyhow::anyhow!(
+                "Invalid backend: {} (expected one of x11, i3)",
+                value
+            )),
+        }
+    }
+}
+
+/// Where directional navigation goes when the monitor next to the current one is empty, instead of carrying on in the
+/// same direction to the monitor after it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmptyMonitorPreference {
+    /// Prefer the closest populated monitor in the empty monitor's row, which only changes Up/Down.
+    Row,
+    /// Prefer the closest populated monitor in the empty monitor's column, which only changes Left/Right.
+    Column,
+}
+
+impl EmptyMonitorPreference {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        match value {
+            "row" => Ok(EmptyMonitorPreference::Row),
+            "column" => Ok(EmptyMonitorPreference::Column),
+            _ => Err(anyhow::anyhow!(
+                "Invalid empty monitor preference: {} (expected one of row, column)",
+                value
+            )),
+        }
+    }
+
+    /// Whether the preference picks a different monitor than carrying on in the given direction would, i.e. whether
+    /// its row/column runs across the direction.
+    pub fn applies_to(&self, direction: &FocusDirection) -> bool {
+        match self {
+            EmptyMonitorPreference::Row => !direction.is_horizontal(),
+            EmptyMonitorPreference::Column => direction.is_horizontal(),
+        }
+    }
+}
+
+/// How a window is focused.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FocusStrategy {
+    /// Raises the window above the others and activates it (`wmctrl -a`).
+    #[default]
+    WmctrlActivate,
+    /// Same as `WmctrlActivate`, but through `xdotool windowactivate`, which works under some window managers
+    /// with incomplete EWMH support where `wmctrl -a` doesn't.
+    XdotoolActivate,
+    /// Only gives the window input focus, leaving the stacking order alone (`xdotool windowfocus`).
+    XdotoolFocus,
+}
+
+impl FocusStrategy {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        match value {
+            "wmctrl-activate" => Ok(FocusStrategy::WmctrlActivate),
+            "xdotool-activate" => Ok(FocusStrategy::XdotoolActivate),
+            "xdotool-focus" => Ok(FocusStrategy::XdotoolFocus),
+            _ => Err(anyhow::anyhow!(
+                "Invalid focus strategy: {} (expected one of wmctrl-activate, xdotool-activate, xdotool-focus)",
+                value
+            )),
+        }
+    }
+}
+
+/// User-configurable settings, read from the environment (which includes any `.env` file loaded at startup).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The `_NET_WM_WINDOW_TYPE`s of windows that should never be focused, without the `_NET_WM_WINDOW_TYPE_` prefix
+    /// (e.g. `DOCK` for `_NET_WM_WINDOW_TYPE_DOCK`).
+    ///
+    /// Env: `EWS_EXCLUDED_WINDOW_TYPES` (comma-separated, e.g. `DOCK,DESKTOP,SPLASH,DIALOG`).
+    pub excluded_window_types: Vec<String>,
+
+    /// Substrings of the classes (e.g. `slack` for `slack.Slack`) of windows that should never be focused,
+    /// matched case-insensitively.
+    ///
+    /// Env: `EWS_EXCLUDED_CLASSES` (comma-separated). Flag: `--exclude-class` (repeatable), which adds to them.
+    pub excluded_classes: Vec<String>,
+
+    /// A substring of the class of the only windows that can be focused (matched case-insensitively), e.g. to only
+    /// navigate between browsers.
+    ///
+    /// Flag: `--only-class`.
+    pub only_class: Option<String>,
+
+    /// Only consider the windows that were used (going by their `_NET_WM_USER_TIME`) within this long of the most
+    /// recently used window.
+    ///
+    /// Flag: `--since` (in minutes).
+    pub since: Option<Duration>,
+
+    /// Whether windows without a `_NET_WM_USER_TIME` are still considered when filtering with `since`.
+    ///
+    /// Flag: `--include-untimed`.
+    pub include_untimed: bool,
+
+    /// How long an external tool (e.g. `xrandr`) gets to run before it's killed and considered hung.
+    ///
+    /// Env: `EWS_COMMAND_TIMEOUT_MS` (in milliseconds).
+    pub command_timeout: Duration,
+
+    /// How often `pin` checks whether the pinned window is still focused.
+    ///
+    /// Env: `EWS_PIN_INTERVAL_MS` (in milliseconds). Flag: `pin --interval`.
+    pub pin_interval: Duration,
+
+    /// Whether to print how long each external tool call (e.g. `wmctrl -l`) took to stderr.
+    ///
+    /// Env: `EWS_TIMINGS` (`true`/`false`). Flag: `--timings`.
+    pub timings: bool,
+
+    /// Whether window geometry includes the window's frame (as reported through `_NET_FRAME_EXTENTS` by xprop) instead
+    /// of only its client area, which makes monitor assignment more accurate for windows with unusual decorations.
+    ///
+    /// Env: `EWS_FRAME_INCLUSIVE_GEOMETRY` (`true`/`false`).
+    pub frame_inclusive_geometry: bool,
+
+    /// Whether windows placed off-screen (i.e. with negative offsets) can be focused. They're moved onto the first
+    /// monitor before being focused.
+    ///
+    /// Env: `EWS_INCLUDE_OFFSCREEN` (`true`/`false`). Flag: `--include-offscreen`.
+    pub include_offscreen: bool,
+
+    /// Whether windows that aren't within any monitor (e.g. below the last monitor when it's shorter than the others,
+    /// or just past the edge of the workspace) are treated as being on the nearest monitor instead of failing the
+    /// command.
+    ///
+    /// Flag: `--lenient-bounds`.
+    pub lenient_bounds: bool,
+
+    /// The X screen (e.g. 1 for `:0.1`) to operate on instead of the one in `DISPLAY`, for setups with a separate
+    /// X screen per GPU.
+    ///
+    /// Env: `EWS_SCREEN`. Flag: `--screen`.
+    pub screen: Option<u32>,
+
+    /// Whether windows on every virtual desktop (not just the current workspace) can be focused. The window's desktop
+    /// is switched to before it's focused.
+    ///
+    /// Env: `EWS_WORKSPACE_ALL` (`true`/`false`). Flag: `--workspace-all`.
+    pub workspace_all: bool,
+
+    /// Where the windows and monitors come from. With the i3 backend, windows are always focused through i3.
+    ///
+    /// Env: `EWS_BACKEND` (`x11` or `i3`).
+    pub backend: Backend,
+
+    /// How windows are focused: either raised and activated (through `wmctrl` or `xdotool`), or only given input focus.
+    ///
+    /// Env: `EWS_FOCUS_STRATEGY` (`wmctrl-activate`, `xdotool-activate`, or `xdotool-focus`), or `EWS_NO_RAISE`
+    /// (`true`/`false`) as a shorthand for `xdotool-focus`. Flags: `--focus-strategy`, `--no-raise`.
+    pub focus_strategy: FocusStrategy,
+
+    /// Whether Left/Right wrap around from the last column of monitors to the first (and vice versa) instead of
+    /// stopping at the edge.
+    ///
+    /// Env: `EWS_WRAP_HORIZONTAL` (`true`/`false`).
+    pub wrap_horizontal: bool,
+
+    /// Whether Up/Down wrap around from the bottom monitor of a column to the top one (and vice versa) instead of
+    /// stopping at the edge.
+    ///
+    /// Env: `EWS_WRAP_VERTICAL` (`true`/`false`).
+    pub wrap_vertical: bool,
+
+    /// Whether Left and Right are swapped for `direction` and `move-direction` (e.g. for a right-to-left preference).
+    ///
+    /// Env: `EWS_INVERT_DIRECTION` (`true`/`false`).
+    pub invert_direction: bool,
+
+    /// Where Left/Right/Up/Down go when the monitor next to the current one has no windows. Without a preference, they
+    /// carry on in the same direction to the next monitor that has any.
+    ///
+    /// Env: `EWS_EMPTY_MONITOR_PREFERENCE` (`row` or `column`).
+    pub empty_monitor_preference: Option<EmptyMonitorPreference>,
+
+    /// Whether Left/Right move straight to the adjacent monitor's window when the focused window is fullscreen,
+    /// instead of to the (hidden) windows behind it on the same monitor. Requires `xprop`.
+    ///
+    /// Env: `EWS_SKIP_FULLSCREEN` (`true`/`false`).
+    pub skip_fullscreen: bool,
+
+    /// Whether moving by direction breaks ties between equally close windows (e.g. ones stacked at the same x offset)
+    /// by picking the one nearest the mouse pointer. Off by default, since it costs an extra `xdotool` call.
+    ///
+    /// Env: `EWS_POINTER_TIEBREAK` (`true`/`false`).
+    pub pointer_tiebreak: bool,
+
+    /// Whether `move-direction` keeps a window's position relative to the size of its monitor (e.g. halfway across
+    /// it) when moving it onto a differently sized monitor, instead of moving it to the monitor's origin.
+    ///
+    /// Env: `EWS_MOVE_RELATIVE` (`true`/`false`).
+    pub move_relative: bool,
+
+    /// The Unix socket that the daemon listens on. Defaults to one in `$XDG_RUNTIME_DIR` (or the temp dir).
+    ///
+    /// Env: `EWS_SOCKET_PATH`.
+    pub socket_path: Option<PathBuf>,
+
+    /// Whether the monitor layout is cached between runs (in `$XDG_CACHE_HOME`), only re-detecting it when the
+    /// active monitors change.
+    ///
+    /// Env: `EWS_CACHE_MONITORS` (`true`/`false`). Flag: `--refresh` forces re-detection.
+    pub cache_monitors: bool,
+
+    /// Whether the monitors stacked in a column are merged into a single logical monitor for navigation, so that
+    /// Left/Right skip over the whole stack and Up/Down never move between its monitors.
+    ///
+    /// Env: `EWS_MERGE_STACKED_MONITORS` (`true`/`false`).
+    pub merge_stacked_monitors: bool,
+
+    /// The scale factors of monitors (keyed by their output names or identities) that are scaled differently in the
+    /// coordinate space that windows are positioned in than xrandr reports, e.g. on a mixed-DPI setup.
+    ///
+    /// Env: `EWS_MONITOR_SCALES` (comma-separated `name=scale` pairs, e.g. `DP-2=2,HDMI-A-0=1.5`).
+    pub monitor_scales: HashMap<String, f64>,
+
+    /// The left-to-right order of the columns of monitors (by output name or identity), overriding the order that's
+    /// detected from their positions. Columns that aren't listed keep their detected order after the listed ones.
+    ///
+    /// Env: `EWS_MONITOR_ORDER` (comma-separated, e.g. `HDMI-A-0,DP-2`).
+    pub monitor_order: Vec<String>,
+
+    /// The command (and its arguments) of the chooser that `select` shows the windows in, which reads the options
+    /// from stdin and prints the chosen one to stdout.
+    ///
+    /// Env: `EWS_CHOOSER` (whitespace-separated, e.g. `dmenu -l 10`).
+    pub chooser: Vec<String>,
+
+    /// A shell command that's run after a window is focused, with the window's hex ID, title, and class as `$1`, `$2`,
+    /// and `$3` (e.g. `notify-send "$2"`). Failures are only logged.
+    ///
+    /// Env: `EWS_POST_FOCUS_CMD`.
+    pub post_focus_command: Option<String>,
+
+    /// Whether a desktop notification with the focused window's class and title is shown (through `notify-send`)
+    /// after a window is focused.
+    ///
+    /// Flag: `--notify`.
+    pub notify: bool,
+
+    /// Whether the focus is checked to have actually moved to the window after focusing it, refocusing it once if it
+    /// didn't and failing if it still didn't. Costs an extra round-trip, so it's off by default.
+    ///
+    /// Flag: `--verify`.
+    pub verify_focus: bool,
+
+    /// Whether moving left/right only cycles between the windows on the current monitor, wrapping around within it
+    /// instead of moving onto the next monitor (and up/down do nothing).
+    ///
+    /// Flag: `--current-monitor-only` (on `direction`).
+    pub current_monitor_only: bool,
+
+    /// Whether the rightmost window on a monitor is focused when focusing a monitor, instead of the leftmost one.
+    ///
+    /// Flag: `--reverse` (on `monitor`).
+    pub pick_last_window: bool,
+
+    /// Which window on a monitor is focused when focusing a monitor (0-based, counting from the left, or from the
+    /// right along with `pick_last_window`), instead of the first one. Errors if the monitor doesn't have that many.
+    ///
+    /// Flag: `--nth <K>` (on `monitor`).
+    pub nth_window: Option<usize>,
+
+    /// Whether the focused window is maximized on its monitor after being focused.
+    ///
+    /// Flag: `--maximize` (on `direction` and `monitor`).
+    pub maximize: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            excluded_window_types: DEFAULT_EXCLUDED_WINDOW_TYPES
+                .iter()
+                .map(|window_type| window_type.to_string())
+                .collect(),
+            excluded_classes: Vec::new(),
+            only_class: None,
+            since: None,
+            include_untimed: false,
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+            pin_interval: DEFAULT_PIN_INTERVAL,
+            timings: false,
+            frame_inclusive_geometry: false,
+            include_offscreen: false,
+            lenient_bounds: false,
+            screen: None,
+            workspace_all: false,
+            backend: Backend::X11,
+            focus_strategy: FocusStrategy::WmctrlActivate,
+            wrap_horizontal: true,
+            wrap_vertical: true,
+            invert_direction: false,
+            empty_monitor_preference: None,
+            skip_fullscreen: false,
+            pointer_tiebreak: false,
+            move_relative: false,
+            socket_path: None,
+            cache_monitors: false,
+            merge_stacked_monitors: false,
+            monitor_scales: HashMap::new(),
+            monitor_order: Vec::new(),
+            chooser: DEFAULT_CHOOSER.iter().map(|arg| arg.to_string()).collect(),
+            post_focus_command: None,
+            notify: false,
+            verify_focus: false,
+            current_monitor_only: false,
+            pick_last_window: false,
+            nth_window: None,
+            maximize: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| env::var(key).ok())
+    }
+
+    /// Builds the config from the given variable lookup, falling back to the defaults for anything that isn't set.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = Config::default();
+
+        if let Some(value) = lookup("EWS_EXCLUDED_WINDOW_TYPES") {
+            config.excluded_window_types = parse_list(&value);
+        }
+
+        if let Some(value) = lookup("EWS_EXCLUDED_CLASSES") {
+            config.excluded_classes = parse_list(&value);
+        }
+
+        if let Some(value) = lookup("EWS_COMMAND_TIMEOUT_MS") {
+            match value.trim().parse::<u64>() {
+                Ok(timeout) => config.command_timeout = Duration::from_millis(timeout),
+                Err(_) => log::warn!("Ignoring invalid EWS_COMMAND_TIMEOUT_MS: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_PIN_INTERVAL_MS") {
+            match value.trim().parse::<u64>() {
+                Ok(interval) if interval > 0 => {
+                    config.pin_interval = Duration::from_millis(interval)
+                }
+                _ => log::warn!("Ignoring invalid EWS_PIN_INTERVAL_MS: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_TIMINGS") {
+            config.timings = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_FRAME_INCLUSIVE_GEOMETRY") {
+            config.frame_inclusive_geometry = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_INCLUDE_OFFSCREEN") {
+            config.include_offscreen = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_SCREEN") {
+            match value.trim().parse::<u32>() {
+                Ok(screen) => config.screen = Some(screen),
+                Err(_) => log::warn!("Ignoring invalid EWS_SCREEN: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_WORKSPACE_ALL") {
+            config.workspace_all = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_BACKEND") {
+            match Backend::try_from_string(value.trim()) {
+                Ok(backend) => config.backend = backend,
+                Err(_) => log::warn!("Ignoring invalid EWS_BACKEND: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_FOCUS_STRATEGY") {
+            match FocusStrategy::try_from_string(value.trim()) {
+                Ok(strategy) => config.focus_strategy = strategy,
+                Err(_) => log::warn!("Ignoring invalid EWS_FOCUS_STRATEGY: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_NO_RAISE") {
+            if parse_bool(&value) {
+                config.focus_strategy = FocusStrategy::XdotoolFocus;
+            }
+        }
+
+        if let Some(value) = lookup("EWS_WRAP_HORIZONTAL") {
+            config.wrap_horizontal = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_WRAP_VERTICAL") {
+            config.wrap_vertical = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_INVERT_DIRECTION") {
+            config.invert_direction = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_EMPTY_MONITOR_PREFERENCE") {
+            match EmptyMonitorPreference::try_from_string(value.trim()) {
+                Ok(preference) => config.empty_monitor_preference = Some(preference),
+                Err(_) => log::warn!("Ignoring invalid EWS_EMPTY_MONITOR_PREFERENCE: {value}"),
+            }
+        }
+
+        if let Some(value) = lookup("EWS_SKIP_FULLSCREEN") {
+            config.skip_fullscreen = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_POINTER_TIEBREAK") {
+            config.pointer_tiebreak = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_MOVE_RELATIVE") {
+            config.move_relative = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_SOCKET_PATH") {
+            config.socket_path = Some(PathBuf::from(value.trim()));
+        }
+
+        if let Some(value) = lookup("EWS_CACHE_MONITORS") {
+            config.cache_monitors = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_MERGE_STACKED_MONITORS") {
+            config.merge_stacked_monitors = parse_bool(&value);
+        }
+
+        if let Some(value) = lookup("EWS_MONITOR_SCALES") {
+            config.monitor_scales = parse_monitor_scales(&value);
+        }
+
+        if let Some(value) = lookup("EWS_MONITOR_ORDER") {
+            config.monitor_order = parse_list(&value);
+        }
+
+        if let Some(value) = lookup("EWS_CHOOSER") {
+            if value.trim().is_empty() {
+                log::warn!("Ignoring empty EWS_CHOOSER");
+            } else {
+                config.chooser = value.split_whitespace().map(|arg| arg.to_owned()).collect();
+            }
+        }
+
+        if let Some(value) = lookup("EWS_POST_FOCUS_CMD") {
+            if !value.trim().is_empty() {
+                config.post_focus_command = Some(value);
+            }
+        }
+
+        config
+    }
+}
+
+fn parse_bool(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_owned())
+        .collect()
+}
+
+/// Parses `name=scale` pairs (e.g. `DP-2=2,HDMI-A-0=1.5`), skipping (and warning about) any invalid ones.
+fn parse_monitor_scales(value: &str) -> HashMap<String, f64> {
+    parse_list(value)
+        .into_iter()
+        .filter_map(|pair| {
+            let scale = pair
+                .split_once('=')
+                .and_then(|(name, scale)| Some((name.trim(), scale.trim().parse::<f64>().ok()?)))
+                .filter(|(name, scale)| !name.is_empty() && *scale > 0.0);
+
+            if scale.is_none() {
+                log::warn!("Ignoring invalid monitor scale in EWS_MONITOR_SCALES: {pair}");
+            }
+
+            scale.map(|(name, scale)| (name.to_owned(), scale))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn create_lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        move |key| vars.get(key).cloned()
+    }
+
+    mod from_lookup {
+        use super::*;
+
+        #[test]
+        fn test_defaults() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert_eq!(config, Config::default());
+            assert_eq!(
+                config.excluded_window_types,
+                vec!["DOCK", "DESKTOP", "SPLASH"]
+            );
+        }
+
+        #[test]
+        fn test_excluded_window_types() {
+            let config = Config::from_lookup(create_lookup(&[(
+                "EWS_EXCLUDED_WINDOW_TYPES",
+                "DOCK, DIALOG,,_NET_WM_WINDOW_TYPE_SPLASH",
+            )]));
+
+            assert_eq!(
+                config.excluded_window_types,
+                vec!["DOCK", "DIALOG", "_NET_WM_WINDOW_TYPE_SPLASH"]
+            );
+        }
+
+        #[test]
+        fn test_excluded_classes() {
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_EXCLUDED_CLASSES", "slack, zoom")]));
+            assert_eq!(config.excluded_classes, vec!["slack", "zoom"]);
+        }
+
+        #[test]
+        fn test_command_timeout() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_COMMAND_TIMEOUT_MS", "500")]));
+            assert_eq!(config.command_timeout, Duration::from_millis(500));
+        }
+
+        #[test]
+        fn test_invalid_command_timeout() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_COMMAND_TIMEOUT_MS", "soon")]));
+            assert_eq!(config.command_timeout, DEFAULT_COMMAND_TIMEOUT);
+        }
+
+        #[test]
+        fn test_pin_interval() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_PIN_INTERVAL_MS", "250")]));
+            assert_eq!(config.pin_interval, Duration::from_millis(250));
+
+            for value in ["0", "often"] {
+                let config = Config::from_lookup(create_lookup(&[("EWS_PIN_INTERVAL_MS", value)]));
+                assert_eq!(config.pin_interval, DEFAULT_PIN_INTERVAL);
+            }
+        }
+
+        #[test]
+        fn test_include_offscreen() {
+            for value in ["1", "true", "TRUE", " yes ", "on"] {
+                let config =
+                    Config::from_lookup(create_lookup(&[("EWS_INCLUDE_OFFSCREEN", value)]));
+                assert!(config.include_offscreen);
+            }
+
+            for value in ["0", "false", "", "nope"] {
+                let config =
+                    Config::from_lookup(create_lookup(&[("EWS_INCLUDE_OFFSCREEN", value)]));
+                assert!(!config.include_offscreen);
+            }
+        }
+
+        #[test]
+        fn test_screen() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_SCREEN", "1")]));
+            assert_eq!(config.screen, Some(1));
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_SCREEN", "second")]));
+            assert_eq!(config.screen, None);
+        }
+
+        #[test]
+        fn test_workspace_all() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_WORKSPACE_ALL", "true")]));
+            assert!(config.workspace_all);
+        }
+
+        #[test]
+        fn test_no_raise() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_NO_RAISE", "true")]));
+            assert_eq!(config.focus_strategy, FocusStrategy::XdotoolFocus);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_NO_RAISE", "false")]));
+            assert_eq!(config.focus_strategy, FocusStrategy::WmctrlActivate);
+        }
+
+        #[test]
+        fn test_backend() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_BACKEND", "i3")]));
+            assert_eq!(config.backend, Backend::I3);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_BACKEND", "sway")]));
+            assert_eq!(config.backend, Backend::X11);
+        }
+
+        #[test]
+        fn test_focus_strategy() {
+            for (value, strategy) in [
+                ("wmctrl-activate", FocusStrategy::WmctrlActivate),
+                ("xdotool-activate", FocusStrategy::XdotoolActivate),
+                (" xdotool-focus ", FocusStrategy::XdotoolFocus),
+            ] {
+                let config = Config::from_lookup(create_lookup(&[("EWS_FOCUS_STRATEGY", value)]));
+                assert_eq!(config.focus_strategy, strategy);
+            }
+        }
+
+        #[test]
+        fn test_invalid_focus_strategy() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_FOCUS_STRATEGY", "magic")]));
+            assert_eq!(config.focus_strategy, FocusStrategy::WmctrlActivate);
+        }
+
+        #[test]
+        fn test_socket_path() {
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_SOCKET_PATH", "/tmp/ews.sock")]));
+            assert_eq!(config.socket_path, Some(PathBuf::from("/tmp/ews.sock")));
+        }
+
+        #[test]
+        fn test_cache_monitors() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_CACHE_MONITORS", "1")]));
+            assert!(config.cache_monitors);
+        }
+
+        #[test]
+        fn test_merge_stacked_monitors() {
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_MERGE_STACKED_MONITORS", "true")]));
+            assert!(config.merge_stacked_monitors);
+        }
+
+        #[test]
+        fn test_chooser() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert_eq!(config.chooser, vec!["rofi", "-dmenu"]);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_CHOOSER", " dmenu  -l 10 ")]));
+            assert_eq!(config.chooser, vec!["dmenu", "-l", "10"]);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_CHOOSER", " ")]));
+            assert_eq!(config.chooser, vec!["rofi", "-dmenu"]);
+        }
+
+        #[test]
+        fn test_monitor_scales() {
+            let config = Config::from_lookup(create_lookup(&[(
+                "EWS_MONITOR_SCALES",
+                "DP-2=2, HDMI-A-0 = 1.5,eDP-1,DP-1=0,DP-3=abc,=2",
+            )]));
+
+            assert_eq!(
+                config.monitor_scales,
+                HashMap::from([("DP-2".to_owned(), 2.0), ("HDMI-A-0".to_owned(), 1.5)])
+            );
+        }
+
+        #[test]
+        fn test_monitor_order() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(config.monitor_order.is_empty());
+
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_MONITOR_ORDER", "HDMI-A-0, DP-2,")]));
+            assert_eq!(config.monitor_order, vec!["HDMI-A-0", "DP-2"]);
+        }
+
+        #[test]
+        fn test_timings() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.timings);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_TIMINGS", "true")]));
+            assert!(config.timings);
+        }
+
+        #[test]
+        fn test_frame_inclusive_geometry() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.frame_inclusive_geometry);
+
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_FRAME_INCLUSIVE_GEOMETRY", "true")]));
+            assert!(config.frame_inclusive_geometry);
+        }
+
+        #[test]
+        fn test_wrap() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(config.wrap_horizontal);
+            assert!(config.wrap_vertical);
+
+            let config = Config::from_lookup(create_lookup(&[
+                ("EWS_WRAP_HORIZONTAL", "false"),
+                ("EWS_WRAP_VERTICAL", "true"),
+            ]));
+            assert!(!config.wrap_horizontal);
+            assert!(config.wrap_vertical);
+        }
+
+        #[test]
+        fn test_invert_direction() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.invert_direction);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_INVERT_DIRECTION", "true")]));
+            assert!(config.invert_direction);
+        }
+
+        #[test]
+        fn test_empty_monitor_preference() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert_eq!(config.empty_monitor_preference, None);
+
+            let config =
+                Config::from_lookup(create_lookup(&[("EWS_EMPTY_MONITOR_PREFERENCE", "column")]));
+            assert_eq!(
+                config.empty_monitor_preference,
+                Some(EmptyMonitorPreference::Column)
+            );
+
+            let config = Config::from_lookup(create_lookup(&[(
+                "EWS_EMPTY_MONITOR_PREFERENCE",
+                "diagonal",
+            )]));
+            assert_eq!(config.empty_monitor_preference, None);
+        }
+
+        #[test]
+        fn test_skip_fullscreen() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.skip_fullscreen);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_SKIP_FULLSCREEN", "true")]));
+            assert!(config.skip_fullscreen);
+        }
+
+        #[test]
+        fn test_pointer_tiebreak() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.pointer_tiebreak);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_POINTER_TIEBREAK", "true")]));
+            assert!(config.pointer_tiebreak);
+        }
+
+        #[test]
+        fn test_move_relative() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert!(!config.move_relative);
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_MOVE_RELATIVE", "true")]));
+            assert!(config.move_relative);
+        }
+
+        #[test]
+        fn test_post_focus_command() {
+            let config = Config::from_lookup(create_lookup(&[]));
+            assert_eq!(config.post_focus_command, None);
+
+            let config = Config::from_lookup(create_lookup(&[(
+                "EWS_POST_FOCUS_CMD",
+                "notify-send \"$2\"",
+            )]));
+            assert_eq!(
+                config.post_focus_command,
+                Some("notify-send \"$2\"".to_owned())
+            );
+
+            let config = Config::from_lookup(create_lookup(&[("EWS_POST_FOCUS_CMD", " ")]));
+            assert_eq!(config.post_focus_command, None);
+        }
+
+        #[test]
+        fn test_empty_excluded_window_types() {
+            let config = Config::from_lookup(create_lookup(&[("EWS_EXCLUDED_WINDOW_TYPES", "")]));
+            assert!(config.excluded_window_types.is_empty());
+        }
+    }
+}
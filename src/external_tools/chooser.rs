@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+
+use super::utils::call_command_with_input;
+
+/// Shows the lines in the chooser (e.g. `rofi -dmenu`), one per line on its stdin, and returns the chosen line.
+///
+/// Returns `None` if the user cancelled the chooser (i.e. nothing was chosen).
+pub fn choose(command: &[String], lines: &[String]) -> Result<Option<String>> {
+    let args = command.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    if args.is_empty() {
+        return Err(anyhow::anyhow!("No chooser command is configured"));
+    }
+
+    let output = call_command_with_input(&args, &lines.join("\n"))?;
+    let stdout = String::from_utf8(output.stdout).context("Invalid UTF-8 output")?;
+
+    // Choosers like rofi and dmenu exit with a non-zero status when cancelled, so only the output matters.
+    Ok(stdout
+        .lines()
+        .next()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_command(command: &[&str]) -> Vec<String> {
+        command.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn create_lines() -> Vec<String> {
+        vec!["first".to_owned(), "second".to_owned()]
+    }
+
+    #[test]
+    fn test_chosen_line() {
+        let result = choose(&create_command(&["tail", "-n", "1"]), &create_lines()).unwrap();
+        assert_eq!(result, Some("second".to_owned()));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let result = choose(&create_command(&["false"]), &create_lines()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_no_command() {
+        assert!(choose(&[], &create_lines()).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::Result;
+
+use super::utils::call_command;
+use crate::models::Window;
+
+/// Runs the post-focus hook (e.g. `notify-send "$2"`) through `sh -c`, with the focused window's hex ID, title, and
+/// class as `$1`, `$2`, and `$3`.
+///
+/// The window's details are passed as arguments instead of being substituted into the command so that titles with
+/// quotes or other shell syntax in them can't break (or inject into) the command.
+pub fn run_post_focus_hook(command: &str, window: &Window) -> Result<()> {
+    let args = build_post_focus_hook_args(command, window);
+    let output = call_command(&args.iter().map(String::as_str).collect::<Vec<&str>>())?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Post-focus hook exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+fn build_post_focus_hook_args(command: &str, window: &Window) -> Vec<String> {
+    vec![
+        "sh".to_owned(),
+        "-c".to_owned(),
+        command.to_owned(),
+        // Becomes `$0`, i.e. the name that the shell reports errors under.
+        "ews-post-focus".to_owned(),
+        window.id.to_hex_string(),
+        window.title.clone(),
+        window.window_class.clone(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WindowId;
+
+    fn create_window() -> Window {
+        Window::new(
+            WindowId(0x05000006),
+            0,
+            24,
+            1920,
+            1056,
+            "gnome-terminal-server.Gnome-terminal".to_string(),
+            "devin@Desktop: \"~\"; exit".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_build_post_focus_hook_args() {
+        let args = build_post_focus_hook_args("notify-send \"$2\"", &create_window());
+
+        assert_eq!(
+            args,
+            vec![
+                "sh",
+                "-c",
+                "notify-send \"$2\"",
+                "ews-post-focus",
+                "0x05000006",
+                "devin@Desktop: \"~\"; exit",
+                "gnome-terminal-server.Gnome-terminal",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_post_focus_hook() {
+        let command = "[ \"$1\" = 0x05000006 ] && [ \"$2\" = 'devin@Desktop: \"~\"; exit' ] && [ \"$3\" = gnome-terminal-server.Gnome-terminal ]";
+        run_post_focus_hook(command, &create_window()).unwrap();
+    }
+
+    #[test]
+    fn test_run_failing_post_focus_hook() {
+        assert!(run_post_focus_hook("exit 1", &create_window()).is_err());
+    }
+}
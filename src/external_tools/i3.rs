@@ -0,0 +1,421 @@
+use std::collections::HashSet;
+use std::process::Output;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::utils::{call_command, get_command_output, is_tool_installed};
+use super::xrandr::{build_workspace, ParsedMonitorConfig};
+use crate::models::{Monitor, Window, WindowId, Workspace};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WindowProperties {
+    class: Option<String>,
+    instance: Option<String>,
+}
+
+/// A container in the tree from `i3-msg -t get_tree`. Only the fields that are needed are parsed.
+#[derive(Debug, Deserialize)]
+struct Node {
+    #[serde(rename = "type")]
+    node_type: String,
+    name: Option<String>,
+    /// The X11 ID of the window, if the container holds one.
+    window: Option<usize>,
+    /// The absolute position of the container (including its decoration) within the output space.
+    rect: Rect,
+    /// The position of the window within the container, i.e. below its decoration.
+    #[serde(default)]
+    window_rect: Rect,
+    #[serde(default)]
+    deco_rect: Rect,
+    window_properties: Option<WindowProperties>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    floating_nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct I3Workspace {
+    name: String,
+    visible: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct I3Output {
+    name: String,
+    active: bool,
+    #[serde(default)]
+    primary: bool,
+    rect: Rect,
+}
+
+pub fn check_if_installed() -> Result<()> {
+    if !is_tool_installed("i3-msg") {
+        return Err(anyhow::anyhow!(
+            "i3-msg is not installed; it comes with i3, which the i3 backend requires"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lists the windows on the workspaces that are currently visible (i.e. one per output).
+pub fn get_windows() -> Result<Vec<Window>> {
+    let workspaces = get_command_output(&["i3-msg", "-t", "get_workspaces"])?;
+    let tree = get_command_output(&["i3-msg", "-t", "get_tree"])?;
+
+    parse_tree(&tree, &parse_visible_workspaces(&workspaces)?)
+}
+
+/// Builds the workspace from the active outputs that i3 knows about.
+pub fn parse_workspace() -> Result<Workspace> {
+    let outputs = get_command_output(&["i3-msg", "-t", "get_outputs"])?;
+
+    build_workspace(parse_outputs(&outputs)?)
+}
+
+/// Focuses the window through i3 itself, which also switches to the window's workspace if needed.
+pub fn focus_window_by_id(window_id: &WindowId) -> Result<()> {
+    focus_window_by_id_with(window_id, call_command)
+}
+
+fn focus_window_by_id_with(
+    window_id: &WindowId,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call(&["i3-msg", &format!("[id={window_id}]"), "focus"])?;
+    Ok(())
+}
+
+fn parse_visible_workspaces(workspaces: &str) -> Result<HashSet<String>> {
+    let workspaces: Vec<I3Workspace> =
+        serde_json::from_str(workspaces).context("Invalid i3 workspaces")?;
+
+    Ok(workspaces
+        .into_iter()
+        .filter(|workspace| workspace.visible)
+        .map(|workspace| workspace.name)
+        .collect())
+}
+
+/// Collects the windows (tiled and floating) on the given workspaces from the tree, in tree order.
+fn parse_tree(tree: &str, visible_workspaces: &HashSet<String>) -> Result<Vec<Window>> {
+    let root: Node = serde_json::from_str(tree).context("Invalid i3 tree")?;
+    let mut windows = Vec::new();
+
+    collect_windows(&root, false, visible_workspaces, &mut windows);
+
+    for (stacking_index, window) in windows.iter_mut().enumerate() {
+        window.stacking_index = stacking_index;
+    }
+
+    Ok(windows)
+}
+
+fn collect_windows(
+    node: &Node,
+    is_visible: bool,
+    visible_workspaces: &HashSet<String>,
+    windows: &mut Vec<Window>,
+) {
+    let is_visible = if node.node_type == "workspace" {
+        node.name
+            .as_ref()
+            .is_some_and(|name| visible_workspaces.contains(name))
+    } else {
+        is_visible
+    };
+
+    if let (Some(id), true) = (node.window, is_visible) {
+        windows.push(to_window(node, id));
+    }
+
+    for child in node.nodes.iter().chain(&node.floating_nodes) {
+        collect_windows(child, is_visible, visible_workspaces, windows);
+    }
+}
+
+/// Converts the container into a window, positioned (like with `wmctrl`) at the top-left corner below its decoration.
+fn to_window(node: &Node, id: usize) -> Window {
+    let properties = node.window_properties.as_ref();
+
+    // Matches the `instance.Class` format of `wmctrl -x` (e.g. "code.Code").
+    let window_class = match properties.map(|properties| (&properties.instance, &properties.class))
+    {
+        Some((Some(instance), Some(class))) => format!("{instance}.{class}"),
+        Some((None, Some(class))) => class.to_owned(),
+        _ => "N/A".to_owned(),
+    };
+
+    let mut window = Window::new(
+        WindowId(id),
+        node.rect.x + node.window_rect.x,
+        node.rect.y + node.window_rect.y,
+        node.window_rect.width,
+        node.window_rect.height,
+        window_class,
+        node.name.clone().unwrap_or_default(),
+    );
+
+    window.frame_top = Some(node.deco_rect.height);
+    window
+}
+
+fn parse_outputs(outputs: &str) -> Result<Vec<ParsedMonitorConfig>> {
+    let outputs: Vec<I3Output> = serde_json::from_str(outputs).context("Invalid i3 outputs")?;
+
+    Ok(outputs
+        .into_iter()
+        .filter(|output| output.active)
+        .map(|output| {
+            let monitor = Monitor {
+                name: Some(output.name),
+                primary: output.primary,
+                ..Monitor::new(output.rect.width, output.rect.height)
+            };
+
+            (monitor, output.rect.x, output.rect.y)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external_tools::xrandr::build_monitors_grid;
+    use crate::models::MonitorGrid;
+
+    // Trimmed down from `i3-msg -t get_tree` with two outputs side by side, workspace 1 (visible) holding two tiled
+    // windows, workspace 2 (hidden) holding one, and workspace 3 (visible) holding a floating window.
+    const TREE: &str = r#"{
+        "type": "root", "name": "root", "window": null,
+        "rect": {"x": 0, "y": 0, "width": 3840, "height": 1080},
+        "nodes": [
+            {
+                "type": "output", "name": "__i3", "window": null,
+                "rect": {"x": 0, "y": 0, "width": 3840, "height": 1080},
+                "nodes": [{
+                    "type": "con", "name": "content", "window": null,
+                    "rect": {"x": 0, "y": 0, "width": 3840, "height": 1080},
+                    "nodes": [{
+                        "type": "workspace", "name": "__i3_scratch", "window": null,
+                        "rect": {"x": 0, "y": 0, "width": 3840, "height": 1080},
+                        "floating_nodes": [{
+                            "type": "floating_con", "name": null, "window": null,
+                            "rect": {"x": 100, "y": 100, "width": 400, "height": 300},
+                            "nodes": [{
+                                "type": "con", "name": "Scratchpad", "window": 77594600,
+                                "rect": {"x": 100, "y": 100, "width": 400, "height": 300},
+                                "window_rect": {"x": 0, "y": 0, "width": 400, "height": 300},
+                                "window_properties": {"class": "Alacritty", "instance": "Alacritty"}
+                            }]
+                        }]
+                    }]
+                }]
+            },
+            {
+                "type": "output", "name": "DP-1", "window": null,
+                "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                "nodes": [{
+                    "type": "con", "name": "content", "window": null,
+                    "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                    "nodes": [
+                        {
+                            "type": "workspace", "name": "1", "window": null,
+                            "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                            "nodes": [
+                                {
+                                    "type": "con", "name": "main.rs - Visual Studio Code", "window": 77594630,
+                                    "rect": {"x": 0, "y": 0, "width": 960, "height": 1080},
+                                    "window_rect": {"x": 2, "y": 22, "width": 956, "height": 1056},
+                                    "deco_rect": {"x": 0, "y": 0, "width": 960, "height": 22},
+                                    "window_properties": {"class": "Code", "instance": "code"}
+                                },
+                                {
+                                    "type": "con", "name": "zsh", "window": 77594631,
+                                    "rect": {"x": 960, "y": 0, "width": 960, "height": 1080},
+                                    "window_rect": {"x": 2, "y": 22, "width": 956, "height": 1056},
+                                    "deco_rect": {"x": 0, "y": 0, "width": 960, "height": 22},
+                                    "window_properties": {"class": "Alacritty", "instance": "Alacritty"}
+                                }
+                            ]
+                        },
+                        {
+                            "type": "workspace", "name": "2", "window": null,
+                            "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                            "nodes": [{
+                                "type": "con", "name": "Slack", "window": 77594632,
+                                "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                                "window_rect": {"x": 0, "y": 0, "width": 1920, "height": 1080},
+                                "window_properties": {"class": "Slack", "instance": "slack"}
+                            }]
+                        }
+                    ]
+                }]
+            },
+            {
+                "type": "output", "name": "HDMI-1", "window": null,
+                "rect": {"x": 1920, "y": 0, "width": 1920, "height": 1080},
+                "nodes": [{
+                    "type": "con", "name": "content", "window": null,
+                    "rect": {"x": 1920, "y": 0, "width": 1920, "height": 1080},
+                    "nodes": [{
+                        "type": "workspace", "name": "3", "window": null,
+                        "rect": {"x": 1920, "y": 0, "width": 1920, "height": 1080},
+                        "floating_nodes": [{
+                            "type": "floating_con", "name": null, "window": null,
+                            "rect": {"x": 2420, "y": 200, "width": 800, "height": 622},
+                            "nodes": [{
+                                "type": "con", "name": "Calculator", "window": 77594633,
+                                "rect": {"x": 2420, "y": 200, "width": 800, "height": 622},
+                                "window_rect": {"x": 1, "y": 21, "width": 798, "height": 600},
+                                "deco_rect": {"x": 0, "y": 0, "width": 800, "height": 21}
+                            }]
+                        }]
+                    }]
+                }]
+            }
+        ]
+    }"#;
+
+    const WORKSPACES: &str = r#"[
+        {"num": 1, "name": "1", "visible": true, "focused": true, "output": "DP-1"},
+        {"num": 2, "name": "2", "visible": false, "focused": false, "output": "DP-1"},
+        {"num": 3, "name": "3", "visible": true, "focused": false, "output": "HDMI-1"}
+    ]"#;
+
+    const OUTPUTS: &str = r#"[
+        {"name": "xroot-0", "active": false, "primary": false, "rect": {"x": 0, "y": 0, "width": 3840, "height": 1080}},
+        {"name": "HDMI-1", "active": true, "primary": false, "rect": {"x": 1920, "y": 0, "width": 1920, "height": 1080}},
+        {"name": "DP-1", "active": true, "primary": true, "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080}},
+        {"name": "DP-2", "active": false, "primary": false, "rect": {"x": 0, "y": 0, "width": 0, "height": 0}}
+    ]"#;
+
+    mod parse_tree {
+        use super::*;
+
+        fn get_windows() -> Vec<Window> {
+            let visible_workspaces = parse_visible_workspaces(WORKSPACES).unwrap();
+            parse_tree(TREE, &visible_workspaces).unwrap()
+        }
+
+        #[test]
+        fn test_visible_windows() {
+            let ids = get_windows()
+                .iter()
+                .map(|window| window.id.0)
+                .collect::<Vec<usize>>();
+
+            assert_eq!(ids, vec![77594630, 77594631, 77594633]);
+        }
+
+        #[test]
+        fn test_tiled_window_geometry() {
+            let window = &get_windows()[1];
+
+            assert_eq!(
+                (
+                    window.x_offset,
+                    window.y_offset,
+                    window.width,
+                    window.height
+                ),
+                (962, 22, 956, 1056)
+            );
+            assert_eq!(window.frame_top, Some(22));
+            assert_eq!(window.window_class, "Alacritty.Alacritty");
+            assert_eq!(window.title, "zsh");
+            assert_eq!(window.stacking_index, 1);
+        }
+
+        #[test]
+        fn test_floating_window_geometry() {
+            let window = &get_windows()[2];
+
+            assert_eq!(
+                (
+                    window.x_offset,
+                    window.y_offset,
+                    window.width,
+                    window.height
+                ),
+                (2421, 221, 798, 600)
+            );
+            assert_eq!(window.frame_top, Some(21));
+
+            // Windows without any properties get the same placeholder class as with `wmctrl`.
+            assert_eq!(window.window_class, "N/A");
+        }
+
+        #[test]
+        fn test_windows_on_monitors() {
+            let workspace = Workspace::new(MonitorGrid(
+                build_monitors_grid(parse_outputs(OUTPUTS).unwrap()).unwrap(),
+            ));
+
+            let monitors = get_windows()
+                .iter()
+                .map(|window| {
+                    workspace
+                        .monitor_grid
+                        .determine_which_monitor_window_is_on(window)
+                        .unwrap()
+                        .0
+                })
+                .collect::<Vec<usize>>();
+
+            assert_eq!(monitors, vec![0, 0, 1]);
+        }
+
+        #[test]
+        fn test_invalid_tree() {
+            assert!(parse_tree("not json", &HashSet::new()).is_err());
+        }
+    }
+
+    mod parse_outputs {
+        use super::*;
+
+        #[test]
+        fn test_active_outputs() {
+            let grid = build_monitors_grid(parse_outputs(OUTPUTS).unwrap()).unwrap();
+
+            assert_eq!(
+                grid,
+                vec![
+                    vec![Monitor {
+                        name: Some("DP-1".to_owned()),
+                        ..Monitor::new_primary(1920, 1080)
+                    }],
+                    vec![Monitor {
+                        name: Some("HDMI-1".to_owned()),
+                        ..Monitor::new(1920, 1080)
+                    }],
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_focus_window_by_id_args() {
+        let mut calls = Vec::new();
+
+        focus_window_by_id_with(&WindowId(77594630), |args| {
+            calls.push(args.join(" "));
+            Err(anyhow::anyhow!("Not actually run"))
+        })
+        .unwrap_err();
+
+        assert_eq!(calls, vec!["i3-msg [id=77594630] focus"]);
+    }
+}
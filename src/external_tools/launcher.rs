@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Launches the command (e.g. `firefox --new-window`) through `sh -c` without waiting for it to finish, so that the app
+/// keeps running on its own.
+///
+/// Only a failure to start the shell is an error; the command itself failing (e.g. not being found) is up to the shell
+/// to report on stderr, which (along with the app's own stderr) is passed through to ours.
+pub fn launch(command: &str) -> Result<()> {
+    let mut child = Command::new("sh")
+        .args(build_launch_args(command))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to launch: {command}"))?;
+
+    // Reap the app whenever it exits so that a long-running process (i.e. the daemon) doesn't collect zombies.
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+fn build_launch_args(command: &str) -> Vec<&str> {
+    // The last argument becomes `$0`, i.e. the name that the shell reports errors under.
+    vec!["-c", command, "ews-launch"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_launch_args() {
+        assert_eq!(
+            build_launch_args("firefox --new-window"),
+            vec!["-c", "firefox --new-window", "ews-launch"]
+        );
+    }
+
+    #[test]
+    fn test_launch() {
+        launch("true").unwrap();
+    }
+}
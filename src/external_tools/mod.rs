@@ -1,12 +1,31 @@
+pub mod chooser;
+pub mod hook;
+pub mod i3;
+pub mod launcher;
+pub mod notify;
 mod utils;
 pub mod wmctrl;
 pub mod xdotool;
+pub mod xprop;
 pub mod xrandr;
 
-pub fn check_if_all_tools_installed() {
-    wmctrl::check_if_installed();
-    xdotool::check_if_installed();
-    xrandr::check_if_installed();
+use anyhow::Result;
+
+pub use utils::{
+    get_tool_version, set_command_timeout, set_screen, set_timings, DEFAULT_COMMAND_TIMEOUT,
+};
+
+#[cfg(feature = "async")]
+pub(crate) use utils::{get_command_timeout, report_timing};
+
+/// Returns an error naming the first required tool that isn't installed. xprop is optional, so it only warns.
+pub fn check_if_all_tools_installed() -> Result<()> {
+    wmctrl::check_if_installed()?;
+    xdotool::check_if_installed()?;
+    xrandr::check_if_installed()?;
+    xprop::check_if_installed();
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -15,10 +34,7 @@ mod tests {
 
     #[test]
     fn test_check_if_all_tools_installed() {
-        // This test just ensures the function runs without panicking
         // In a real system with the tools installed, it should complete successfully
-        // On systems without the tools, it would exit(1), but we can't easily test that
-        // in unit tests without mocking
-        check_if_all_tools_installed();
+        check_if_all_tools_installed().unwrap();
     }
 }
@@ -0,0 +1,57 @@
+use anyhow::Result;
+
+use super::utils::call_command;
+
+/// Pops up a desktop notification through `notify-send`.
+///
+/// notify-send is optional, so callers are expected to only log a failure (e.g. when it isn't installed).
+pub fn send(summary: &str, body: &str) -> Result<()> {
+    let args = build_args(summary, body);
+    let output = call_command(&args)?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "notify-send exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+fn build_args<'a>(summary: &'a str, body: &'a str) -> Vec<&'a str> {
+    // The `--` keeps summaries/bodies that start with a dash (e.g. some window titles) from being parsed as options.
+    vec![
+        "notify-send",
+        "--app-name=easy-window-switcher",
+        "--",
+        summary,
+        body,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_args() {
+        assert_eq!(
+            build_args("code.Code", "main.rs - Visual Studio Code"),
+            vec![
+                "notify-send",
+                "--app-name=easy-window-switcher",
+                "--",
+                "code.Code",
+                "main.rs - Visual Studio Code"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_args_with_leading_dash() {
+        let args = build_args("Alacritty.Alacritty", "-zsh");
+        assert_eq!(args[2..], ["--", "Alacritty.Alacritty", "-zsh"]);
+    }
+}
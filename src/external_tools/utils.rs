@@ -1,27 +1,224 @@
-use core::str;
-use std::process::{Command, Output};
+use anyhow::{Context, Result};
+use std::env;
+use std::io::{Read, Write};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The default amount of time that an external tool gets to run before it's considered hung.
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often to check whether a running command has finished.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+static COMMAND_TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT.as_millis() as u64);
+
+static TIMINGS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_command_timeout(timeout: Duration) {
+    COMMAND_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+pub(crate) fn get_command_timeout() -> Duration {
+    Duration::from_millis(COMMAND_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Whether to print how long each external tool call took to stderr, for tracking down where the latency comes from.
+pub fn set_timings(enabled: bool) {
+    TIMINGS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Runs the function, returning its result along with how long it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+/// Runs the command through the function, printing how long it took to stderr if timings are enabled.
+fn with_timing<T>(args: &[&str], f: impl FnOnce() -> T) -> T {
+    let (result, elapsed) = timed(f);
+    report_timing(args, elapsed);
+
+    result
+}
+
+/// Prints how long the command took to stderr if timings are enabled.
+pub(crate) fn report_timing(args: &[&str], elapsed: Duration) {
+    if TIMINGS_ENABLED.load(Ordering::Relaxed) {
+        eprintln!("{}", format_timing(args, elapsed));
+    }
+}
+
+fn format_timing(args: &[&str], elapsed: Duration) -> String {
+    format!(
+        "[timing] {:>8.2}ms  {}",
+        elapsed.as_secs_f64() * 1000.0,
+        args.join(" ")
+    )
+}
+
+/// Points the external tools at the given X screen (e.g. screen 1 of `:0` is `:0.1`) by updating `DISPLAY`, which
+/// every tool that's run afterwards inherits.
+pub fn set_screen(screen: u32) -> Result<()> {
+    let display = env::var("DISPLAY").context("DISPLAY isn't set")?;
+    env::set_var("DISPLAY", display_for_screen(&display, screen)?);
+
+    Ok(())
+}
+
+/// Replaces (or adds) the screen number of the given display name, which is of the form `[host]:display[.screen]`.
+fn display_for_screen(display: &str, screen: u32) -> Result<String> {
+    let invalid_display = || anyhow::anyhow!("Invalid DISPLAY: {display}");
+
+    let (host, display_and_screen) = display.rsplit_once(':').ok_or_else(invalid_display)?;
+    let display_number = display_and_screen
+        .split_once('.')
+        .map_or(display_and_screen, |(display_number, _)| display_number);
+
+    if display_number.is_empty() || !display_number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid_display());
+    }
+
+    Ok(format!("{host}:{display_number}.{screen}"))
+}
 
 pub fn is_tool_installed(tool: &str) -> bool {
+    is_tool_installed_with_version_flag(tool, "--version")
+}
+
+/// Same as `is_tool_installed`, but for tools that don't accept the conventional `--version` flag.
+pub fn is_tool_installed_with_version_flag(tool: &str, version_flag: &str) -> bool {
     Command::new(tool)
-        .arg("--version")
+        .arg(version_flag)
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
 
-pub fn call_command(args: &[&str]) -> Output {
-    Command::new(args[0])
+/// Returns the first line that the tool prints for its version flag (e.g. "xdotool version 3.20160805.1"),
+/// or `None` if it isn't installed.
+pub fn get_tool_version(tool: &str, version_flag: &str) -> Option<String> {
+    let output = Command::new(tool).arg(version_flag).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    // Some tools (e.g. older xprop versions) print their version to stderr.
+    [output.stdout, output.stderr]
+        .iter()
+        .filter_map(|stream| {
+            String::from_utf8_lossy(stream)
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(|line| line.to_owned())
+        })
+        .next()
+        .or_else(|| Some(String::new()))
+}
+
+pub fn call_command(args: &[&str]) -> Result<Output> {
+    call_command_with_timeout(args, get_command_timeout())
+}
+
+/// Runs the command, killing it and returning an error if it doesn't finish within the timeout.
+///
+/// This is what keeps a hung tool (e.g. `xrandr` during a display hotplug) from freezing the switcher.
+pub fn call_command_with_timeout(args: &[&str], timeout: Duration) -> Result<Output> {
+    with_timing(args, || run_command_with_timeout(args, timeout))
+}
+
+fn run_command_with_timeout(args: &[&str], timeout: Duration) -> Result<Output> {
+    let mut child = Command::new(args[0])
         .args(&args[1..])
-        .output()
-        .expect("Failed to execute command")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute command: {}", args.join(" ")))?;
+
+    // Drain the output on separate threads so that a chatty command can't fill up its pipe and block forever.
+    let stdout_reader = spawn_pipe_reader(child.stdout.take());
+    let stderr_reader = spawn_pipe_reader(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if Instant::now() >= deadline {
+            // Best effort; the command might have finished in the meantime.
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(anyhow::anyhow!(
+                "Command timed out after {}ms: {}",
+                timeout.as_millis(),
+                args.join(" ")
+            ));
+        }
+
+        thread::sleep(COMMAND_POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
 }
 
-pub fn get_command_output(args: &[&str]) -> String {
-    let raw_stdout = call_command(args).stdout;
+/// Runs the command with the given input on its stdin, waiting for however long it takes to finish.
+///
+/// Unlike `call_command`, there's no timeout, since this is meant for interactive commands (e.g. `rofi -dmenu`).
+pub fn call_command_with_input(args: &[&str], input: &str) -> Result<Output> {
+    let mut child = Command::new(args[0])
+        .args(&args[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute command: {}", args.join(" ")))?;
+
+    // Write the input on a separate thread so that a command that doesn't read all of it can't block forever.
+    let stdin = child.stdin.take();
+    let input = input.to_owned();
 
-    str::from_utf8(&raw_stdout)
-        .expect("Invalid UTF-8 output")
-        .to_owned()
+    let stdin_writer = thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+
+    let output = child.wait_with_output()?;
+    let _ = stdin_writer.join();
+
+    Ok(output)
+}
+
+/// Runs the command and returns its stdout. Any invalid UTF-8 (e.g. a window title in a legacy encoding) is replaced
+/// with replacement characters instead of failing the whole command.
+pub fn get_command_output(args: &[&str]) -> Result<String> {
+    let raw_stdout = call_command(args)?.stdout;
+
+    Ok(String::from_utf8_lossy(&raw_stdout).into_owned())
+}
+
+fn spawn_pipe_reader(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+
+        buffer
+    })
 }
 
 #[cfg(test)]
@@ -40,10 +237,65 @@ mod tests {
         assert!(!is_tool_installed("definitely_not_a_real_tool_12345"));
     }
 
+    #[test]
+    fn test_is_tool_installed_with_version_flag() {
+        assert!(is_tool_installed_with_version_flag("ls", "--version"));
+        assert!(!is_tool_installed_with_version_flag(
+            "definitely_not_a_real_tool_12345",
+            "-version"
+        ));
+    }
+
+    #[test]
+    fn test_display_for_screen() {
+        assert_eq!(display_for_screen(":0", 1).unwrap(), ":0.1");
+        assert_eq!(display_for_screen(":0.0", 1).unwrap(), ":0.1");
+        assert_eq!(display_for_screen(":1.1", 0).unwrap(), ":1.0");
+        assert_eq!(
+            display_for_screen("localhost:10.0", 2).unwrap(),
+            "localhost:10.2"
+        );
+    }
+
+    #[test]
+    fn test_display_for_screen_invalid() {
+        assert!(display_for_screen("", 1).is_err());
+        assert!(display_for_screen("localhost", 1).is_err());
+        assert!(display_for_screen(":", 1).is_err());
+        assert!(display_for_screen(":x.0", 1).is_err());
+    }
+
+    #[test]
+    fn test_get_tool_version() {
+        assert!(get_tool_version("ls", "--version")
+            .unwrap()
+            .starts_with("ls"));
+        assert_eq!(
+            get_tool_version("definitely_not_a_real_tool_12345", "--version"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_timed() {
+        let (output, elapsed) = timed(|| call_command(&["sleep", "0.05"]));
+
+        assert!(output.unwrap().status.success());
+        assert!(elapsed >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_format_timing() {
+        assert_eq!(
+            format_timing(&["wmctrl", "-l", "-G", "-x"], Duration::from_micros(12_345)),
+            "[timing]    12.35ms  wmctrl -l -G -x"
+        );
+    }
+
     #[test]
     fn test_call_command_basic() {
         // Test with a simple command that should work on all systems
-        let output = call_command(&["echo", "test"]);
+        let output = call_command(&["echo", "test"]).unwrap();
         assert!(output.status.success());
 
         let stdout = String::from_utf8(output.stdout).unwrap();
@@ -53,21 +305,73 @@ mod tests {
     #[test]
     fn test_get_command_output() {
         // Test with echo command
-        let output = get_command_output(&["echo", "hello world"]);
+        let output = get_command_output(&["echo", "hello world"]).unwrap();
         assert_eq!(output.trim(), "hello world");
     }
 
     #[test]
     fn test_get_command_output_multiline() {
         // Test with printf for more controlled output
-        let output = get_command_output(&["printf", "line1\nline2"]);
+        let output = get_command_output(&["printf", "line1\nline2"]).unwrap();
         assert_eq!(output, "line1\nline2");
     }
 
     #[test]
-    #[should_panic(expected = "Failed to execute command")]
+    fn test_get_command_output_invalid_utf8() {
+        // `\351` is "é" in Latin-1, which isn't valid UTF-8 on its own.
+        let output = get_command_output(&["printf", "caf\\351 au lait"]).unwrap();
+        assert_eq!(output, "caf\u{FFFD} au lait");
+    }
+
+    #[test]
     fn test_call_command_invalid_command() {
-        // This should panic since the command doesn't exist
-        call_command(&["definitely_not_a_real_command_12345"]);
+        // This should error since the command doesn't exist
+        let result = call_command(&["definitely_not_a_real_command_12345"]);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to execute command"));
+    }
+
+    #[test]
+    fn test_call_command_with_timeout_expires() {
+        let start = Instant::now();
+        let result = call_command_with_timeout(&["sleep", "5"], Duration::from_millis(200));
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+    }
+
+    #[test]
+    fn test_call_command_with_timeout_completes() {
+        let output = call_command_with_timeout(&["echo", "test"], Duration::from_secs(2)).unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "test");
+    }
+
+    #[test]
+    fn test_call_command_with_input() {
+        let output = call_command_with_input(&["sort"], "b\na\n").unwrap();
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_call_command_with_unread_input() {
+        let output = call_command_with_input(&["true"], &"a".repeat(1000000)).unwrap();
+        assert!(output.status.success());
+    }
+
+    #[test]
+    fn test_call_command_with_large_output() {
+        // More output than fits in a pipe's buffer shouldn't block the command from finishing.
+        let output = call_command_with_timeout(
+            &["head", "-c", "1000000", "/dev/zero"],
+            Duration::from_secs(2),
+        )
+        .unwrap();
+
+        assert_eq!(output.stdout.len(), 1000000);
     }
 }
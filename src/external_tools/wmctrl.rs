@@ -1,40 +1,389 @@
+use std::process::Output;
+
+use anyhow::Result;
+
 use super::utils::{call_command, get_command_output, is_tool_installed};
+use super::xprop::{self, FrameExtents};
+use crate::config::Config;
 use crate::models::{Window, WindowId};
 
-pub fn check_if_installed() {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StateAction {
+    Add,
+    Remove,
+    Toggle,
+}
+
+impl StateAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StateAction::Add => "add",
+            StateAction::Remove => "remove",
+            StateAction::Toggle => "toggle",
+        }
+    }
+}
+
+/// The window states that can be changed with `set_window_state`, named after their `_NET_WM_STATE_*` properties.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowState {
+    MaximizedVert,
+    MaximizedHorz,
+    Fullscreen,
+    Hidden,
+    Sticky,
+    Above,
+}
+
+impl WindowState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindowState::MaximizedVert => "maximized_vert",
+            WindowState::MaximizedHorz => "maximized_horz",
+            WindowState::Fullscreen => "fullscreen",
+            WindowState::Hidden => "hidden",
+            WindowState::Sticky => "sticky",
+            WindowState::Above => "above",
+        }
+    }
+}
+
+pub fn check_if_installed() -> Result<()> {
     if !is_tool_installed("wmctrl") {
-        eprintln!("Error: wmctrl is not installed; please install it first through your e.g. package manager");
-        std::process::exit(1);
+        return Err(anyhow::anyhow!(
+            "wmctrl is not installed; please install it first through your e.g. package manager"
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn get_windows_config(config: &Config) -> Result<Vec<Window>> {
+    let windows_config = get_raw_windows_config()?;
+    let can_detect_window_types = xprop::is_installed();
+    let windows = parse_windows_config(&windows_config, config, can_detect_window_types);
+
+    if !can_detect_window_types {
+        return Ok(windows);
     }
+
+    // Both the window type and the frame extents come from a single xprop call per window.
+    Ok(windows
+        .into_iter()
+        .filter_map(|mut window| {
+            let properties = xprop::get_window_properties(&window.id).unwrap_or_default();
+
+            // Panels, docks, and the like are properly identified by their window type.
+            if properties.window_type.is_some_and(|window_type| {
+                is_excluded_window_type(&window_type, &config.excluded_window_types)
+            }) {
+                return None;
+            }
+
+            // Where possible, use each window's actual decoration rather than assuming the constant one.
+            if window.has_geometry() {
+                match properties.frame_extents {
+                    Some(extents) if config.frame_inclusive_geometry => {
+                        include_frame_extents(&mut window, &extents)
+                    }
+                    extents => window.frame_top = extents.map(|extents| extents.top),
+                }
+            }
+
+            Some(window)
+        })
+        .collect())
+}
+
+/// Grows the window's geometry (which `wmctrl` reports for its client area only) to include its frame.
+///
+/// Since the offsets then already account for the decoration, the window's decoration height becomes zero.
+fn include_frame_extents(window: &mut Window, extents: &FrameExtents) {
+    window.x_offset -= extents.left;
+    window.y_offset -= extents.top;
+    window.width += extents.left + extents.right;
+    window.height += extents.top + extents.bottom;
+    window.frame_top = Some(0);
 }
 
-pub fn get_windows_config() -> Vec<Window> {
-    let windows_config = get_command_output(&["wmctrl", "-l", "-G", "-x"]);
-    parse_windows_config(&windows_config)
+/// The unparsed output of `wmctrl`, with one line per window.
+///
+/// Falls back to listing the windows without their geometry for `wmctrl` builds that don't support `-G`, in which
+/// case only the commands that don't need window positions (e.g. `raise-group` or `select`) can work.
+pub fn get_raw_windows_config() -> Result<String> {
+    get_raw_windows_config_with(call_command)
 }
 
-pub fn focus_window_by_id(window_id: &WindowId) {
-    call_command(&["wmctrl", "-i", "-a", &window_id.to_string()]);
+fn get_raw_windows_config_with(call: impl Fn(&[&str]) -> Result<Output>) -> Result<String> {
+    let output = call(&["wmctrl", "-l", "-G", "-x"])?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+
+    log::warn!(
+        "wmctrl -G failed ({}); listing the windows without their geometry",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    let output = call(&["wmctrl", "-l", "-x"])?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
-fn parse_windows_config(windows_config: &str) -> Vec<Window> {
+/// Raises and activates the window.
+pub fn focus_window_by_id(window_id: &WindowId) -> Result<()> {
+    focus_window_by_id_with(window_id, call_command)
+}
+
+fn focus_window_by_id_with(
+    window_id: &WindowId,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call(&["wmctrl", "-i", "-a", &window_id.to_string()])?;
+    Ok(())
+}
+
+/// Finds the index of the currently active virtual desktop, if the window manager reports one.
+pub fn get_current_desktop() -> Result<Option<usize>> {
+    let desktops_config = get_command_output(&["wmctrl", "-d"])?;
+    Ok(parse_current_desktop(&desktops_config))
+}
+
+/// Makes the virtual desktop with the given index the active one.
+pub fn switch_to_desktop(index: usize) -> Result<()> {
+    switch_to_desktop_with(index, call_command)
+}
+
+/// Moves the view to the given position of a (single, large) desktop that uses viewports, e.g. under Compiz.
+pub fn switch_to_viewport(x: i32, y: i32) -> Result<()> {
+    switch_to_viewport_with(x, y, call_command)
+}
+
+fn switch_to_desktop_with(
+    index: usize,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call_checked_command(&["wmctrl", "-s", &index.to_string()], call)
+}
+
+fn switch_to_viewport_with(
+    x: i32,
+    y: i32,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call_checked_command(&["wmctrl", "-o", &format!("{x},{y}")], call)
+}
+
+/// Calls the command, treating a non-zero exit status as an error.
+fn call_checked_command(args: &[&str], call: impl FnOnce(&[&str]) -> Result<Output>) -> Result<()> {
+    let output = call(args)?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Command failed with {}: {}: {}",
+            output.status,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Moves the window so that its top-left corner is at the given position, keeping its current size.
+pub fn move_window_by_id(window_id: &WindowId, x: i32, y: i32) -> Result<()> {
+    let args = get_move_window_args(window_id, x, y);
+    call_command(&args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>())?;
+    Ok(())
+}
+
+/// Moves and resizes the window so that it occupies the given bounds.
+pub fn move_resize_window_by_id(
+    window_id: &WindowId,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> Result<()> {
+    let args = get_move_resize_window_args(window_id, x, y, width, height);
+    call_command(&args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>())?;
+    Ok(())
+}
+
+/// Raises the window to the top of the stack. This is the same call as focusing, since wmctrl
+/// activating a window both raises and focuses it; it only differs in intent.
+pub fn raise_window_by_id(window_id: &WindowId) -> Result<()> {
+    call_command(&["wmctrl", "-i", "-a", &window_id.to_string()])?;
+    Ok(())
+}
+
+/// Minimizes (i.e. hides) the window.
+pub fn minimize_window_by_id(window_id: &WindowId) -> Result<()> {
+    set_window_state(window_id, StateAction::Add, WindowState::Hidden)
+}
+
+/// Restores a window that was minimized.
+pub fn unminimize_window_by_id(window_id: &WindowId) -> Result<()> {
+    set_window_state(window_id, StateAction::Remove, WindowState::Hidden)
+}
+
+/// Adds, removes, or toggles one of the window's `_NET_WM_STATE` properties.
+pub fn set_window_state(
+    window_id: &WindowId,
+    action: StateAction,
+    state: WindowState,
+) -> Result<()> {
+    set_window_state_with(window_id, action, state, call_command)
+}
+
+fn set_window_state_with(
+    window_id: &WindowId,
+    action: StateAction,
+    state: WindowState,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call_checked_command(
+        &[
+            "wmctrl",
+            "-i",
+            "-r",
+            &window_id.to_string(),
+            "-b",
+            &format!("{},{}", action.as_str(), state.as_str()),
+        ],
+        call,
+    )
+}
+
+fn get_move_window_args(window_id: &WindowId, x: i32, y: i32) -> Vec<String> {
+    // A width/height of -1 means to leave them alone.
+    get_move_resize_window_args(window_id, x, y, -1, -1)
+}
+
+fn get_move_resize_window_args(
+    window_id: &WindowId,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> Vec<String> {
+    // The geometry is of the form `gravity,x,y,width,height`, where a gravity of 0 means the window's default.
+    vec![
+        "wmctrl".to_owned(),
+        "-i".to_owned(),
+        "-r".to_owned(),
+        window_id.to_string(),
+        "-e".to_owned(),
+        format!("0,{x},{y},{width},{height}"),
+    ]
+}
+
+/// Sample output (the current desktop is marked with a `*`):
+///
+/// 0  * DG: 5760x1080  VP: 0,0  WA: 0,24 5760x1056  Workspace 1
+/// 1  - DG: 5760x1080  VP: N/A  WA: 0,24 5760x1056  Workspace 2
+fn parse_current_desktop(desktops_config: &str) -> Option<usize> {
+    desktops_config.lines().find_map(|line| {
+        let mut columns = line.split_whitespace();
+        let index = columns.next()?;
+
+        (columns.next()? == "*")
+            .then(|| index.parse::<usize>().ok())
+            .flatten()
+    })
+}
+
+/// Determines whether a window's raw `_NET_WM_WINDOW_TYPE` value (which can be a list of types, in order of preference)
+/// contains any of the excluded types. Excluded types can be given with or without the `_NET_WM_WINDOW_TYPE_` prefix.
+fn is_excluded_window_type(window_type: &str, excluded_window_types: &[String]) -> bool {
+    let normalize = |window_type: &str| {
+        window_type
+            .trim()
+            .trim_start_matches("_NET_WM_WINDOW_TYPE_")
+            .to_uppercase()
+    };
+
+    window_type.split(',').map(normalize).any(|window_type| {
+        excluded_window_types
+            .iter()
+            .any(|excluded_type| normalize(excluded_type) == window_type)
+    })
+}
+
+/// Whether the window's class matches any of the excluded classes.
+pub fn is_excluded_class(window: &Window, excluded_classes: &[String]) -> bool {
+    excluded_classes
+        .iter()
+        .any(|excluded_class| window.class_contains(excluded_class))
+}
+
+/// Parses the output of `wmctrl`, dropping any windows that should never be focused.
+///
+/// Windows with negative offsets are placed off-screen, so they're only kept when `include_offscreen` is set.
+///
+/// Windows sitting exactly at the top of the workspace (i.e. a y-offset of 0) are usually panels, but can also be
+/// legitimate (e.g. maximized) windows. When window types can be detected, panels get excluded by their type instead,
+/// so those windows are kept; otherwise, fall back to excluding them by position.
+fn parse_windows_config(
+    windows_config: &str,
+    config: &Config,
+    can_detect_window_types: bool,
+) -> Vec<Window> {
+    let parsed = parse_windows_config_lenient(windows_config, config, can_detect_window_types);
+
+    for (_, error) in &parsed.errors {
+        log::warn!("Skipping window: {error}");
+    }
+
+    parsed.windows
+}
+
+/// The windows parsed from the output of `wmctrl`, along with the lines that couldn't be parsed.
+#[derive(Debug, Default)]
+pub struct ParsedWindows {
+    pub windows: Vec<Window>,
+    /// The raw lines that couldn't be parsed, each with the reason why.
+    pub errors: Vec<(String, String)>,
+}
+
+/// Same as `parse_windows_config`, but collects the lines that couldn't be parsed instead of only logging them
+/// (e.g. so that `doctor` can report them).
+pub fn parse_windows_config_lenient(
+    windows_config: &str,
+    config: &Config,
+    can_detect_window_types: bool,
+) -> ParsedWindows {
     let split_windows_config: Vec<&str> = windows_config.split("\n").collect();
-    let mut windows = Vec::new();
+    let mut parsed = ParsedWindows::default();
 
-    for window_config in split_windows_config {
+    for (stacking_index, window_config) in split_windows_config.into_iter().enumerate() {
         if !window_config.is_empty() {
-            let window = Window::from_raw_config(window_config).unwrap();
+            let mut window = match Window::from_raw_config_lenient(window_config) {
+                Ok(window) => window,
+                Err(error) => {
+                    parsed
+                        .errors
+                        .push((window_config.to_owned(), format!("{error:#}")));
+                    continue;
+                }
+            };
+
+            window.stacking_index = stacking_index;
 
             if window.window_class != "N/A"
                 && window.window_class != "nemo-desktop.Nemo-desktop"
-                && window.y_offset > 0
+                && !is_excluded_class(&window, &config.excluded_classes)
+                && (!window.has_geometry()
+                    || window.y_offset > 0
+                    || (window.y_offset == 0 && can_detect_window_types)
+                    || (window.y_offset < 0 && config.include_offscreen))
             {
-                windows.push(window);
+                parsed.windows.push(window);
             }
         }
     }
 
-    windows
+    parsed
 }
 
 #[cfg(test)]
@@ -45,11 +394,567 @@ mod tests {
 
     #[test]
     fn test_get_windows_config() {
-        let windows = get_windows_config();
+        let windows = get_windows_config(&Config::default()).unwrap();
 
         assert!(!windows.is_empty());
     }
 
+    #[test]
+    fn test_parse_windows_config_stacking_index() {
+        let windows_config = [
+            "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal",
+            "0x01e00003 -1 0    0    1920 24   N/A                                   devin-Desktop Top Panel",
+            "0x04400003  0 1920 24   1920 1056 code.Code                              devin-Desktop Visual Studio Code",
+        ]
+        .join("\n");
+
+        let windows = parse_windows_config(&windows_config, &Config::default(), false);
+        let stacking_indices: Vec<usize> =
+            windows.iter().map(|window| window.stacking_index).collect();
+
+        // The stacking index is the line index, even when earlier lines are filtered out.
+        assert_eq!(stacking_indices, vec![0, 2]);
+    }
+
+    mod include_frame_extents {
+        use super::*;
+        use crate::models::{Monitor, MonitorGrid, MonitorIndex};
+
+        fn create_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "code.Code".to_string(),
+                "main.rs".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_adjusted_geometry() {
+            let mut window = create_window(1930, 61);
+
+            include_frame_extents(
+                &mut window,
+                &FrameExtents {
+                    left: 10,
+                    right: 10,
+                    top: 37,
+                    bottom: 5,
+                },
+            );
+
+            assert_eq!(
+                (
+                    window.x_offset,
+                    window.y_offset,
+                    window.width,
+                    window.height
+                ),
+                (1920, 24, 820, 642)
+            );
+            assert_eq!(window.decoration_height(), 0);
+        }
+
+        #[test]
+        fn test_monitor_assignment() {
+            // Two stacked 1920x1080 monitors, with a window whose 37px title bar starts just above the bottom monitor.
+            let monitor_grid = MonitorGrid(vec![vec![
+                Monitor::new(1920, 1080),
+                Monitor::new(1920, 1080),
+            ]]);
+
+            let extents = FrameExtents {
+                left: 0,
+                right: 0,
+                top: 37,
+                bottom: 0,
+            };
+
+            // Going by the assumed 24px decoration, the window would be on the bottom monitor...
+            let mut window = create_window(0, 1110);
+            assert_eq!(
+                monitor_grid
+                    .determine_which_monitor_window_is_on(&window)
+                    .unwrap(),
+                MonitorIndex(1)
+            );
+
+            // ...but its frame actually starts on the top monitor.
+            include_frame_extents(&mut window, &extents);
+            assert_eq!(window.y_offset, 1073);
+            assert_eq!(
+                monitor_grid
+                    .determine_which_monitor_window_is_on(&window)
+                    .unwrap(),
+                MonitorIndex(0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_windows_config_skips_unparsable_lines() {
+        let windows_config = [
+            "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal",
+            "garbage",
+            "0x04400003  0 1920 24   1920 1056 code.Code                              devin-Desktop Visual Studio Code",
+        ]
+        .join("\n");
+
+        let ids: Vec<WindowId> = parse_windows_config(&windows_config, &Config::default(), false)
+            .into_iter()
+            .map(|window| window.id)
+            .collect();
+
+        assert_eq!(ids, vec![WindowId(0x05000006), WindowId(0x04400003)]);
+    }
+
+    #[test]
+    fn test_parse_windows_config_lenient() {
+        let windows_config = [
+            "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal",
+            "garbage",
+            "",
+            "0x04400003  0 1920 code.Code devin-Desktop Visual Studio Code",
+            "0x04400004  0 3840 24   1920 1056 code.Code                              devin-Desktop Visual Studio Code",
+            "0xnothex  0 1920 24   1920 1056 code.Code                              devin-Desktop Visual Studio Code",
+        ]
+        .join("\n");
+
+        let parsed = parse_windows_config_lenient(&windows_config, &Config::default(), false);
+
+        let ids: Vec<WindowId> = parsed
+            .windows
+            .iter()
+            .map(|window| window.id.clone())
+            .collect();
+        assert_eq!(ids, vec![WindowId(0x05000006), WindowId(0x04400004)]);
+
+        let lines: Vec<&str> = parsed
+            .errors
+            .iter()
+            .map(|(line, _)| line.as_str())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "garbage",
+                "0x04400003  0 1920 code.Code devin-Desktop Visual Studio Code",
+                "0xnothex  0 1920 24   1920 1056 code.Code                              devin-Desktop Visual Studio Code",
+            ]
+        );
+
+        assert!(parsed.errors.iter().all(|(_, error)| !error.is_empty()));
+        assert_eq!(
+            parsed.errors[1].1,
+            "Invalid window config: 0x04400003  0 1920 code.Code devin-Desktop Visual Studio Code"
+        );
+    }
+
+    #[test]
+    fn test_parse_windows_config_lenient_all_good() {
+        let windows_config =
+            "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal\n";
+
+        let parsed = parse_windows_config_lenient(windows_config, &Config::default(), false);
+
+        assert_eq!(parsed.windows.len(), 1);
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_windows_config_excluded_classes() {
+        let windows_config = [
+            "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal",
+            "0x03a00003  0 0    24   1920 1056 slack.Slack                           devin-Desktop Slack",
+            "0x04400003  0 3840 24   1920 1056 code.Code                             devin-Desktop Visual Studio Code",
+        ]
+        .join("\n");
+
+        let config = Config {
+            excluded_classes: vec!["SLACK".to_owned(), "zoom".to_owned()],
+            ..Config::default()
+        };
+
+        let ids: Vec<WindowId> = parse_windows_config(&windows_config, &config, false)
+            .into_iter()
+            .map(|window| window.id)
+            .collect();
+
+        assert_eq!(ids, vec![WindowId(0x05000006), WindowId(0x04400003)]);
+    }
+
+    #[test]
+    fn test_parse_windows_config_include_offscreen() {
+        let windows_config = [
+            // Still excluded cause of `N/A` window class.
+            "0x0340000b  0 -159 -1156 59   1056 N/A                   devin-5900x unity-launcher",
+            // Parked just above the workspace.
+            "0x04a00007  0 1920 -900  3440 1416 code.Code             devin-5900x parked - Visual Studio Code",
+            "0x04a00006  0 1920 564  3440 1416 code.Code             devin-5900x wmctrl.rs - easy-window-switcher-rs - Visual Studio Code"
+        ].join("\n");
+
+        assert_eq!(
+            parse_windows_config(&windows_config, &Config::default(), false).len(),
+            1
+        );
+
+        let config = Config {
+            include_offscreen: true,
+            ..Config::default()
+        };
+
+        let windows = parse_windows_config(&windows_config, &config, false);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].id, WindowId(0x04a00007));
+        assert_eq!(windows[0].y_offset, -900);
+    }
+
+    #[test]
+    fn test_parse_windows_config_top_aligned_with_window_types() {
+        let windows_config = [
+            // Maximized on the top row of monitors, so it sits flush at the top of the workspace.
+            "0x04a00006  0 1920 0  3440 1416 gnome-terminal-server.Gnome-terminal  devin-5900x Terminal",
+            "0x04a00007  0 0 564  1920 1016 code.Code             devin-5900x wmctrl.rs - easy-window-switcher-rs - Visual Studio Code"
+        ].join("\n");
+
+        let windows = parse_windows_config(&windows_config, &Config::default(), true);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].id, WindowId(0x04a00006));
+        assert_eq!(windows[0].y_offset, 0);
+
+        // Without window types to go off of, the position heuristic still applies.
+        let windows = parse_windows_config(&windows_config, &Config::default(), false);
+
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].id, WindowId(0x04a00007));
+    }
+
+    mod parse_current_desktop {
+        use super::*;
+
+        #[test]
+        fn test_current_desktop() {
+            let desktops_config = [
+                "0  - DG: 5760x1080  VP: N/A  WA: 0,24 5760x1056  Workspace 1",
+                "1  * DG: 5760x1080  VP: 0,0  WA: 0,24 5760x1056  Workspace 2",
+            ]
+            .join("\n");
+
+            assert_eq!(parse_current_desktop(&desktops_config), Some(1));
+        }
+
+        #[test]
+        fn test_no_current_desktop() {
+            assert_eq!(parse_current_desktop(""), None);
+            assert_eq!(
+                parse_current_desktop(
+                    "0  - DG: 5760x1080  VP: N/A  WA: 0,24 5760x1056  Workspace 1"
+                ),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_windows_config_without_geometry() {
+        let windows_config = [
+            "0x05000006  0 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal",
+            "0x01e00003 -1 N/A                                   devin-Desktop Top Panel",
+            "0x04400003  1 code.Code                              devin-Desktop Visual Studio Code",
+        ]
+        .join("\n");
+
+        let parsed = parse_windows_config_lenient(&windows_config, &Config::default(), false);
+
+        assert!(parsed.errors.is_empty());
+        assert_eq!(
+            parsed
+                .windows
+                .iter()
+                .map(|window| (window.id.clone(), window.window_class.as_str()))
+                .collect::<Vec<(WindowId, &str)>>(),
+            vec![
+                (WindowId(0x05000006), "gnome-terminal-server.Gnome-terminal"),
+                (WindowId(0x04400003), "code.Code"),
+            ]
+        );
+        assert!(parsed.windows.iter().all(|window| !window.has_geometry()));
+    }
+
+    mod get_raw_windows_config_with {
+        use std::cell::RefCell;
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        use super::*;
+
+        fn get_config(geometry_code: i32) -> (String, Vec<String>) {
+            let calls = RefCell::new(Vec::new());
+
+            let config = get_raw_windows_config_with(|args| {
+                let command = args.join(" ");
+                calls.borrow_mut().push(command.clone());
+
+                let (code, stdout) = if command.contains("-G") {
+                    (
+                        geometry_code,
+                        "0x05000006  0 1920 24   1920 1056 code.Code  host Code",
+                    )
+                } else {
+                    (0, "0x05000006  0 code.Code  host Code")
+                };
+
+                Ok(Output {
+                    // The raw wait status holds the exit code in its second byte.
+                    status: ExitStatus::from_raw(code << 8),
+                    stdout: stdout.as_bytes().to_vec(),
+                    stderr: Vec::new(),
+                })
+            })
+            .unwrap();
+
+            (config, calls.into_inner())
+        }
+
+        #[test]
+        fn test_with_geometry() {
+            let (config, calls) = get_config(0);
+
+            assert_eq!(
+                config,
+                "0x05000006  0 1920 24   1920 1056 code.Code  host Code"
+            );
+            assert_eq!(calls, vec!["wmctrl -l -G -x"]);
+        }
+
+        #[test]
+        fn test_falls_back_without_geometry() {
+            let (config, calls) = get_config(1);
+
+            assert_eq!(config, "0x05000006  0 code.Code  host Code");
+            assert_eq!(calls, vec!["wmctrl -l -G -x", "wmctrl -l -x"]);
+        }
+    }
+
+    mod switch_to_desktop_and_viewport {
+        use std::cell::RefCell;
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        use super::*;
+
+        fn create_output(code: i32, stderr: &str) -> Output {
+            Output {
+                // The raw wait status holds the exit code in its second byte.
+                status: ExitStatus::from_raw(code << 8),
+                stdout: Vec::new(),
+                stderr: stderr.as_bytes().to_vec(),
+            }
+        }
+
+        fn create_recorder<'a>(
+            calls: &'a RefCell<Vec<Vec<String>>>,
+            code: i32,
+        ) -> impl FnOnce(&[&str]) -> Result<Output> + 'a {
+            move |args| {
+                calls
+                    .borrow_mut()
+                    .push(args.iter().map(|arg| arg.to_string()).collect());
+
+                Ok(create_output(code, "Cannot switch desktop"))
+            }
+        }
+
+        #[test]
+        fn test_switch_to_desktop_args() {
+            let calls = RefCell::new(Vec::new());
+            switch_to_desktop_with(2, create_recorder(&calls, 0)).unwrap();
+
+            assert_eq!(calls.into_inner(), vec![vec!["wmctrl", "-s", "2"]]);
+        }
+
+        #[test]
+        fn test_switch_to_viewport_args() {
+            let calls = RefCell::new(Vec::new());
+            switch_to_viewport_with(1920, 0, create_recorder(&calls, 0)).unwrap();
+
+            assert_eq!(calls.into_inner(), vec![vec!["wmctrl", "-o", "1920,0"]]);
+        }
+
+        #[test]
+        fn test_failed_exit_status() {
+            let calls = RefCell::new(Vec::new());
+            let error = switch_to_desktop_with(5, create_recorder(&calls, 1)).unwrap_err();
+
+            assert!(error.to_string().contains("wmctrl -s 5"));
+            assert!(error.to_string().contains("Cannot switch desktop"));
+        }
+
+        #[test]
+        fn test_failed_call() {
+            let result =
+                switch_to_viewport_with(0, 0, |_| Err(anyhow::anyhow!("Command timed out")));
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_focus_window_by_id_args() {
+        let mut calls = Vec::new();
+
+        focus_window_by_id_with(&WindowId(77594630), |args| {
+            calls.push(args.join(" "));
+            Err(anyhow::anyhow!("Not actually run"))
+        })
+        .unwrap_err();
+
+        assert_eq!(calls, vec!["wmctrl -i -a 77594630"]);
+    }
+
+    #[test]
+    fn test_get_move_window_args() {
+        assert_eq!(
+            get_move_window_args(&WindowId(77594630), -10, 24),
+            vec!["wmctrl", "-i", "-r", "77594630", "-e", "0,-10,24,-1,-1"]
+        );
+    }
+
+    #[test]
+    fn test_get_move_resize_window_args() {
+        assert_eq!(
+            get_move_resize_window_args(&WindowId(77594630), 1920, 0, 3440, 1440),
+            vec!["wmctrl", "-i", "-r", "77594630", "-e", "0,1920,0,3440,1440"]
+        );
+    }
+
+    mod set_window_state {
+        use std::cell::RefCell;
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        use super::*;
+
+        fn get_args(action: StateAction, state: WindowState) -> Vec<String> {
+            let calls = RefCell::new(Vec::new());
+
+            set_window_state_with(&WindowId(77594630), action, state, |args| {
+                calls.borrow_mut().push(
+                    args.iter()
+                        .map(|arg| arg.to_string())
+                        .collect::<Vec<String>>(),
+                );
+
+                Ok(Output {
+                    status: ExitStatus::from_raw(0),
+                    stdout: Vec::new(),
+                    stderr: Vec::new(),
+                })
+            })
+            .unwrap();
+
+            let mut calls = calls.into_inner();
+            assert_eq!(calls.len(), 1);
+
+            calls.remove(0)
+        }
+
+        #[test]
+        fn test_every_action_and_state() {
+            let actions = [
+                (StateAction::Add, "add"),
+                (StateAction::Remove, "remove"),
+                (StateAction::Toggle, "toggle"),
+            ];
+
+            let states = [
+                (WindowState::MaximizedVert, "maximized_vert"),
+                (WindowState::MaximizedHorz, "maximized_horz"),
+                (WindowState::Fullscreen, "fullscreen"),
+                (WindowState::Hidden, "hidden"),
+                (WindowState::Sticky, "sticky"),
+                (WindowState::Above, "above"),
+            ];
+
+            for (action, action_str) in actions {
+                for (state, state_str) in states {
+                    assert_eq!(
+                        get_args(action, state),
+                        vec![
+                            "wmctrl".to_owned(),
+                            "-i".to_owned(),
+                            "-r".to_owned(),
+                            "77594630".to_owned(),
+                            "-b".to_owned(),
+                            format!("{action_str},{state_str}"),
+                        ]
+                    );
+                }
+            }
+        }
+    }
+
+    mod is_excluded_window_type {
+        use super::*;
+
+        fn get_result(window_type: &str) -> bool {
+            is_excluded_window_type(window_type, &Config::default().excluded_window_types)
+        }
+
+        #[test]
+        fn test_excluded_by_default() {
+            assert!(get_result("_NET_WM_WINDOW_TYPE_DOCK"));
+            assert!(get_result("_NET_WM_WINDOW_TYPE_DESKTOP"));
+            assert!(get_result("_NET_WM_WINDOW_TYPE_SPLASH"));
+        }
+
+        #[test]
+        fn test_included_by_default() {
+            assert!(!get_result("_NET_WM_WINDOW_TYPE_NORMAL"));
+            assert!(!get_result("_NET_WM_WINDOW_TYPE_DIALOG"));
+        }
+
+        #[test]
+        fn test_list_of_types() {
+            assert!(get_result(
+                "_KDE_NET_WM_WINDOW_TYPE_OVERRIDE, _NET_WM_WINDOW_TYPE_DOCK"
+            ));
+            assert!(!get_result(
+                "_KDE_NET_WM_WINDOW_TYPE_OVERRIDE, _NET_WM_WINDOW_TYPE_NORMAL"
+            ));
+        }
+
+        #[test]
+        fn test_configured_types() {
+            let excluded_window_types = vec![
+                "dialog".to_string(),
+                "_NET_WM_WINDOW_TYPE_UTILITY".to_string(),
+            ];
+
+            assert!(is_excluded_window_type(
+                "_NET_WM_WINDOW_TYPE_DIALOG",
+                &excluded_window_types
+            ));
+            assert!(is_excluded_window_type(
+                "_NET_WM_WINDOW_TYPE_UTILITY",
+                &excluded_window_types
+            ));
+            assert!(!is_excluded_window_type(
+                "_NET_WM_WINDOW_TYPE_DOCK",
+                &excluded_window_types
+            ));
+        }
+
+        #[test]
+        fn test_nothing_excluded() {
+            assert!(!is_excluded_window_type("_NET_WM_WINDOW_TYPE_DOCK", &[]));
+        }
+    }
+
     #[test]
     fn test_parse_windows_config() {
         let windows_config = [
@@ -64,7 +969,7 @@ mod tests {
             "0x04a00006  0 1920 564  3440 1416 code.Code             devin-5900x wmctrl.rs - easy-window-switcher-rs - Visual Studio Code"
         ].join("\n");
 
-        let windows = parse_windows_config(&windows_config);
+        let windows = parse_windows_config(&windows_config, &Config::default(), false);
 
         assert_eq!(windows.len(), 1);
 
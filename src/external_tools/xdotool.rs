@@ -1,19 +1,70 @@
-use super::utils::{get_command_output, is_tool_installed};
+use std::process::Output;
+
+use anyhow::Result;
+
+use super::utils::{call_command, get_command_output, is_tool_installed};
 use crate::models::WindowId;
 
-pub fn check_if_installed() {
+pub fn check_if_installed() -> Result<()> {
     if !is_tool_installed("xdotool") {
-        eprintln!("Error: xdotool is not installed; please install it first through your e.g. package manager");
-        std::process::exit(1);
+        return Err(anyhow::anyhow!(
+            "xdotool is not installed; please install it first through your e.g. package manager"
+        ));
     }
+
+    Ok(())
 }
 
-pub fn get_current_focused_window_id() -> WindowId {
-    let output = get_command_output(&["xdotool", "getwindowfocus"])
+pub fn get_current_focused_window_id() -> Result<WindowId> {
+    let output = get_command_output(&["xdotool", "getwindowfocus"])?
         .trim()
         .to_owned();
 
-    WindowId(output.parse::<usize>().unwrap())
+    output.parse()
+}
+
+/// Gets the mouse pointer's position on the screen as `(x, y)`.
+pub fn get_mouse_location() -> Result<(i32, i32)> {
+    parse_mouse_location(&get_command_output(&["xdotool", "getmouselocation"])?)
+}
+
+/// Parses the output of `xdotool getmouselocation`, e.g. `x:2400 y:540 screen:0 window:77594630`.
+fn parse_mouse_location(output: &str) -> Result<(i32, i32)> {
+    let get_coordinate = |name: &str| {
+        output
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|value| value.parse::<i32>().ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid mouse location: {}", output.trim()))
+    };
+
+    Ok((get_coordinate("x")?, get_coordinate("y")?))
+}
+
+/// Raises and activates the window, like `wmctrl -a`, but through the window manager's `_NET_ACTIVE_WINDOW` support.
+pub fn activate_window_by_id(window_id: &WindowId) -> Result<()> {
+    activate_window_by_id_with(window_id, call_command)
+}
+
+fn activate_window_by_id_with(
+    window_id: &WindowId,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call(&["xdotool", "windowactivate", &window_id.to_string()])?;
+    Ok(())
+}
+
+/// Gives the window input focus without raising it above the other windows.
+pub fn focus_window_by_id(window_id: &WindowId) -> Result<()> {
+    focus_window_by_id_with(window_id, call_command)
+}
+
+fn focus_window_by_id_with(
+    window_id: &WindowId,
+    call: impl FnOnce(&[&str]) -> Result<Output>,
+) -> Result<()> {
+    call(&["xdotool", "windowfocus", &window_id.to_string()])?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -22,8 +73,66 @@ mod tests {
 
     #[test]
     fn test_get_current_focused_window_id() {
-        let id = get_current_focused_window_id();
+        let id = get_current_focused_window_id().unwrap();
 
         assert!(id.0 > 0);
     }
+
+    mod parse_mouse_location {
+        use super::*;
+
+        #[test]
+        fn test_valid_output() {
+            assert_eq!(
+                parse_mouse_location("x:2400 y:540 screen:0 window:77594630\n").unwrap(),
+                (2400, 540)
+            );
+        }
+
+        #[test]
+        fn test_negative_coordinates() {
+            assert_eq!(
+                parse_mouse_location("x:-1200 y:-10 screen:0 window:77594630").unwrap(),
+                (-1200, -10)
+            );
+        }
+
+        #[test]
+        fn test_invalid_output() {
+            assert_eq!(
+                parse_mouse_location("screen:0 window:77594630")
+                    .unwrap_err()
+                    .to_string(),
+                "Invalid mouse location: screen:0 window:77594630"
+            );
+            assert!(parse_mouse_location("x:abc y:540").is_err());
+            assert!(parse_mouse_location("").is_err());
+        }
+    }
+
+    #[test]
+    fn test_focus_window_by_id_args() {
+        let mut calls = Vec::new();
+
+        focus_window_by_id_with(&WindowId(77594630), |args| {
+            calls.push(args.join(" "));
+            Err(anyhow::anyhow!("Not actually run"))
+        })
+        .unwrap_err();
+
+        assert_eq!(calls, vec!["xdotool windowfocus 77594630"]);
+    }
+
+    #[test]
+    fn test_activate_window_by_id_args() {
+        let mut calls = Vec::new();
+
+        activate_window_by_id_with(&WindowId(77594630), |args| {
+            calls.push(args.join(" "));
+            Err(anyhow::anyhow!("Not actually run"))
+        })
+        .unwrap_err();
+
+        assert_eq!(calls, vec!["xdotool windowactivate 77594630"]);
+    }
 }
@@ -0,0 +1,590 @@
+use anyhow::Result;
+
+use super::utils::{call_command, get_command_output, is_tool_installed_with_version_flag};
+use crate::models::WindowId;
+
+const GTK_FRAME_EXTENTS: &str = "_GTK_FRAME_EXTENTS";
+const NET_CLIENT_LIST_STACKING: &str = "_NET_CLIENT_LIST_STACKING";
+const NET_FRAME_EXTENTS: &str = "_NET_FRAME_EXTENTS";
+const NET_WM_STATE: &str = "_NET_WM_STATE";
+const NET_WM_STATE_FULLSCREEN: &str = "_NET_WM_STATE_FULLSCREEN";
+const NET_WM_NAME: &str = "_NET_WM_NAME";
+const NET_WM_USER_TIME: &str = "_NET_WM_USER_TIME";
+const NET_WM_WINDOW_TYPE: &str = "_NET_WM_WINDOW_TYPE";
+const WM_CLASS: &str = "WM_CLASS";
+const WM_NAME: &str = "WM_NAME";
+
+/// The extents (in pixels) of the frame around a window, in the same order that X reports them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameExtents {
+    pub left: i32,
+    pub right: i32,
+    pub top: i32,
+    pub bottom: i32,
+}
+
+/// The properties of a window that listing the windows relies on.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindowProperties {
+    /// The raw `_NET_WM_WINDOW_TYPE` (e.g. "_NET_WM_WINDOW_TYPE_NORMAL").
+    pub window_type: Option<String>,
+    pub frame_extents: Option<FrameExtents>,
+}
+
+/// Unlike the other tools, xprop is optional: features that rely on it degrade gracefully when it's missing.
+pub fn check_if_installed() {
+    if !is_installed() {
+        log::warn!("xprop is not installed; features that read window properties will be disabled");
+    }
+}
+
+pub fn is_installed() -> bool {
+    // xprop doesn't understand `--version`, only `-version`.
+    is_tool_installed_with_version_flag("xprop", "-version")
+}
+
+/// Reads the raw value of the given property of a window.
+///
+/// Returns `None` if the window doesn't have the property and an error if xprop itself failed (e.g. the window doesn't exist).
+pub fn get_property(window_id: &WindowId, property: &str) -> Result<Option<String>> {
+    let output = call_command(&["xprop", "-id", &window_id.to_hex_string(), property])?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to read property {property} of window {}: {}",
+            window_id.to_hex_string(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(parse_property(
+        &String::from_utf8_lossy(&output.stdout),
+        property,
+    ))
+}
+
+/// Reads the raw value of the given property of the root window.
+pub fn get_root_property(property: &str) -> Result<Option<String>> {
+    let output = get_command_output(&["xprop", "-root", property])?;
+
+    Ok(parse_property(&output, property))
+}
+
+/// Queries the IDs of the windows managed by the window manager in their stacking order (bottom-most first), as
+/// reported by `_NET_CLIENT_LIST_STACKING` on the root window.
+pub fn get_client_list_stacking() -> Result<Vec<WindowId>> {
+    Ok(get_root_property(NET_CLIENT_LIST_STACKING)?
+        .map(|value| parse_window_ids(&value))
+        .unwrap_or_default())
+}
+
+/// Sample value (of `_NET_CLIENT_LIST_STACKING(WINDOW): window id # 0x3a00003, 0x5000006`): `0x3a00003, 0x5000006`
+///
+/// IDs that can't be parsed are skipped.
+fn parse_window_ids(value: &str) -> Vec<WindowId> {
+    value
+        .trim_start_matches("window id #")
+        .split(',')
+        .filter_map(|id| WindowId::from_hex_str(id.trim()).ok())
+        .collect()
+}
+
+/// Queries the class of any window (not just the ones that wmctrl lists) from its `WM_CLASS`, in the same
+/// `instance.Class` form that wmctrl uses (e.g. "code.Code").
+pub fn get_window_class(window_id: &WindowId) -> Result<Option<String>> {
+    Ok(get_property(window_id, WM_CLASS)?.and_then(|value| parse_window_class(&value)))
+}
+
+/// Sample value (of `WM_CLASS(STRING) = "code", "Code"`): `"code", "Code"`
+fn parse_window_class(value: &str) -> Option<String> {
+    let parts = parse_strings(value);
+
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
+/// Queries the title of any window (not just the ones that wmctrl lists) from its `_NET_WM_NAME`, falling back to the
+/// legacy `WM_NAME` for windows that don't set it.
+pub fn get_window_title(window_id: &WindowId) -> Result<Option<String>> {
+    let value = match get_property(window_id, NET_WM_NAME)? {
+        Some(value) => Some(value),
+        None => get_property(window_id, WM_NAME)?,
+    };
+
+    Ok(value.and_then(|value| parse_strings(&value).into_iter().next()))
+}
+
+/// Parses a (comma-separated) list of the double-quoted strings that xprop prints for string properties, e.g.
+/// `"code", "Code"`. xprop escapes double quotes and backslashes within the strings with a backslash.
+fn parse_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = value.chars();
+
+    while chars.by_ref().any(|c| c == '"') {
+        let mut string = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => string.extend(chars.next()),
+                _ => string.push(c),
+            }
+        }
+
+        strings.push(string);
+    }
+
+    strings
+}
+
+/// Checks whether the given window is fullscreen, i.e. whether its `_NET_WM_STATE` includes `_NET_WM_STATE_FULLSCREEN`.
+pub fn is_fullscreen(window_id: &WindowId) -> Result<bool> {
+    Ok(get_property(window_id, NET_WM_STATE)?.is_some_and(|value| parse_is_fullscreen(&value)))
+}
+
+/// Sample value (of `_NET_WM_STATE(ATOM) = _NET_WM_STATE_FULLSCREEN, _NET_WM_STATE_FOCUSED`):
+/// `_NET_WM_STATE_FULLSCREEN, _NET_WM_STATE_FOCUSED`
+fn parse_is_fullscreen(value: &str) -> bool {
+    value
+        .split(',')
+        .any(|state| state.trim() == NET_WM_STATE_FULLSCREEN)
+}
+
+/// Queries the `_NET_WM_USER_TIME` of the given window, i.e. the X server time of the last user activity in it.
+pub fn get_user_time(window_id: &WindowId) -> Result<Option<u64>> {
+    Ok(get_property(window_id, NET_WM_USER_TIME)?.and_then(|value| parse_user_time(&value)))
+}
+
+/// Sample value (of `_NET_WM_USER_TIME(CARDINAL) = 218431690`): `218431690`
+fn parse_user_time(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok()
+}
+
+/// Queries the window's type and frame extents (if the window manager or the window itself reports them) together,
+/// since it's done for every listed window and each xprop call is another process.
+pub fn get_window_properties(window_id: &WindowId) -> Result<WindowProperties> {
+    let output = get_command_output(&[
+        "xprop",
+        "-id",
+        &window_id.to_hex_string(),
+        NET_WM_WINDOW_TYPE,
+        GTK_FRAME_EXTENTS,
+        NET_FRAME_EXTENTS,
+    ])?;
+
+    Ok(parse_window_properties(&output))
+}
+
+/// Sample output:
+///
+/// _NET_WM_WINDOW_TYPE(ATOM) = _NET_WM_WINDOW_TYPE_NORMAL
+/// _GTK_FRAME_EXTENTS:  not found.
+/// _NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0
+fn parse_window_properties(output: &str) -> WindowProperties {
+    WindowProperties {
+        window_type: parse_property(output, NET_WM_WINDOW_TYPE),
+        frame_extents: parse_frame_extents(output),
+    }
+}
+
+/// Sample output:
+///
+/// _GTK_FRAME_EXTENTS:  not found.
+/// _NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0
+///
+/// `_GTK_FRAME_EXTENTS` takes precedence since client-side decorated windows report it instead of a server-side frame.
+fn parse_frame_extents(output: &str) -> Option<FrameExtents> {
+    parse_frame_extents_property(output, GTK_FRAME_EXTENTS)
+        .or_else(|| parse_frame_extents_property(output, NET_FRAME_EXTENTS))
+}
+
+fn parse_frame_extents_property(output: &str, property: &str) -> Option<FrameExtents> {
+    let values = parse_property(output, property)?
+        .split(',')
+        .map(|value| value.trim().parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()
+        .ok()?;
+
+    match values[..] {
+        [left, right, top, bottom] => Some(FrameExtents {
+            left,
+            right,
+            top,
+            bottom,
+        }),
+        _ => None,
+    }
+}
+
+/// Finds the value of the given property in xprop's output, which is of the form `PROP(TYPE) = value` (or
+/// `PROP(TYPE): value` for some types, like lists of windows).
+///
+/// Sample output:
+///
+/// _NET_WM_DESKTOP(CARDINAL) = 0
+/// WM_CLASS(STRING) = "code", "Code"
+/// _NET_WM_WINDOW_TYPE(ATOM) = _NET_WM_WINDOW_TYPE_NORMAL
+/// _NET_CLIENT_LIST_STACKING(WINDOW): window id # 0x3a00003, 0x5000006
+/// _NET_WM_STATE:  not found.
+fn parse_property(output: &str, property: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (name, rest) = line.split_once('(')?;
+        let (_property_type, value) = rest.split_once(") = ").or_else(|| rest.split_once("): "))?;
+
+        (name == property).then(|| value.trim().to_owned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod parse_property {
+        use super::*;
+
+        #[test]
+        fn test_cardinal_property() {
+            let output = "_NET_WM_DESKTOP(CARDINAL) = 0";
+            assert_eq!(
+                parse_property(output, "_NET_WM_DESKTOP"),
+                Some("0".to_string())
+            );
+        }
+
+        #[test]
+        fn test_cardinal_list_property() {
+            let output = "_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0";
+            assert_eq!(
+                parse_property(output, "_NET_FRAME_EXTENTS"),
+                Some("0, 0, 37, 0".to_string())
+            );
+        }
+
+        #[test]
+        fn test_string_property() {
+            let output = "WM_CLASS(STRING) = \"code\", \"Code\"";
+            assert_eq!(
+                parse_property(output, "WM_CLASS"),
+                Some("\"code\", \"Code\"".to_string())
+            );
+        }
+
+        #[test]
+        fn test_utf8_string_property_with_equals_sign() {
+            let output = "_NET_WM_NAME(UTF8_STRING) = \"a = b - Visual Studio Code\"";
+            assert_eq!(
+                parse_property(output, "_NET_WM_NAME"),
+                Some("\"a = b - Visual Studio Code\"".to_string())
+            );
+        }
+
+        #[test]
+        fn test_atom_property() {
+            let output = "_NET_WM_WINDOW_TYPE(ATOM) = _NET_WM_WINDOW_TYPE_NORMAL";
+            assert_eq!(
+                parse_property(output, "_NET_WM_WINDOW_TYPE"),
+                Some("_NET_WM_WINDOW_TYPE_NORMAL".to_string())
+            );
+        }
+
+        #[test]
+        fn test_no_such_property() {
+            assert_eq!(
+                parse_property("_NET_WM_STATE:  not found.", "_NET_WM_STATE"),
+                None
+            );
+
+            assert_eq!(
+                parse_property("FOO:  no such atom on any window.", "FOO"),
+                None
+            );
+        }
+
+        #[test]
+        fn test_other_property() {
+            let output = "_NET_WM_DESKTOP(CARDINAL) = 0";
+            assert_eq!(parse_property(output, "_NET_WM"), None);
+        }
+
+        #[test]
+        fn test_finds_property_among_many() {
+            let output = [
+                "_GTK_FRAME_EXTENTS:  not found.",
+                "_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                parse_property(&output, "_NET_FRAME_EXTENTS"),
+                Some("0, 0, 37, 0".to_string())
+            );
+            assert_eq!(parse_property(&output, "_GTK_FRAME_EXTENTS"), None);
+        }
+    }
+
+    mod parse_window_ids {
+        use super::*;
+
+        #[test]
+        fn test_client_list_stacking() {
+            let output =
+                "_NET_CLIENT_LIST_STACKING(WINDOW): window id # 0x3a00003, 0x5000006, 0x340000b";
+            let value = parse_property(output, NET_CLIENT_LIST_STACKING).unwrap();
+
+            assert_eq!(
+                parse_window_ids(&value),
+                vec![
+                    WindowId(0x3a00003),
+                    WindowId(0x5000006),
+                    WindowId(0x340000b)
+                ]
+            );
+        }
+
+        #[test]
+        fn test_equals_form() {
+            let output = "_NET_CLIENT_LIST_STACKING(WINDOW) = 0x3a00003, 0x5000006";
+            let value = parse_property(output, NET_CLIENT_LIST_STACKING).unwrap();
+
+            assert_eq!(
+                parse_window_ids(&value),
+                vec![WindowId(0x3a00003), WindowId(0x5000006)]
+            );
+        }
+
+        #[test]
+        fn test_skips_invalid_ids() {
+            assert_eq!(
+                parse_window_ids("0x3a00003, nope, 0x5000006"),
+                vec![WindowId(0x3a00003), WindowId(0x5000006)]
+            );
+        }
+
+        #[test]
+        fn test_empty() {
+            assert_eq!(parse_window_ids(""), vec![]);
+            assert_eq!(
+                parse_property(
+                    "_NET_CLIENT_LIST_STACKING:  not found.",
+                    NET_CLIENT_LIST_STACKING
+                ),
+                None
+            );
+        }
+    }
+
+    mod parse_window_class {
+        use super::*;
+
+        #[test]
+        fn test_instance_and_class() {
+            let value =
+                parse_property("WM_CLASS(STRING) = \"instance\", \"Class\"", WM_CLASS).unwrap();
+            assert_eq!(
+                parse_window_class(&value),
+                Some("instance.Class".to_owned())
+            );
+        }
+
+        #[test]
+        fn test_gnome_terminal() {
+            assert_eq!(
+                parse_window_class("\"gnome-terminal-server\", \"Gnome-terminal\""),
+                Some("gnome-terminal-server.Gnome-terminal".to_owned())
+            );
+        }
+
+        #[test]
+        fn test_empty() {
+            assert_eq!(parse_window_class(""), None);
+        }
+    }
+
+    mod parse_strings {
+        use super::*;
+
+        #[test]
+        fn test_title() {
+            let value = parse_property(
+                "_NET_WM_NAME(UTF8_STRING) = \"a = b, c - Visual Studio Code\"",
+                NET_WM_NAME,
+            )
+            .unwrap();
+
+            assert_eq!(parse_strings(&value), vec!["a = b, c - Visual Studio Code"]);
+        }
+
+        #[test]
+        fn test_escapes() {
+            assert_eq!(
+                parse_strings("\"say \\\"hi\\\" \\\\ bye\""),
+                vec!["say \"hi\" \\ bye"]
+            );
+        }
+
+        #[test]
+        fn test_empty_strings() {
+            assert_eq!(parse_strings("\"\", \"\""), vec!["", ""]);
+            assert_eq!(parse_strings(""), Vec::<String>::new());
+        }
+    }
+
+    mod parse_is_fullscreen {
+        use super::*;
+
+        #[test]
+        fn test_fullscreen() {
+            assert!(parse_is_fullscreen("_NET_WM_STATE_FULLSCREEN"));
+            assert!(parse_is_fullscreen(
+                "_NET_WM_STATE_FOCUSED, _NET_WM_STATE_FULLSCREEN"
+            ));
+        }
+
+        #[test]
+        fn test_not_fullscreen() {
+            assert!(!parse_is_fullscreen(""));
+            assert!(!parse_is_fullscreen(
+                "_NET_WM_STATE_MAXIMIZED_VERT, _NET_WM_STATE_MAXIMIZED_HORZ"
+            ));
+            assert!(!parse_is_fullscreen("_NET_WM_STATE_FULLSCREEN_ISH"));
+        }
+
+        #[test]
+        fn test_from_xprop_output() {
+            let output = "_NET_WM_STATE(ATOM) = _NET_WM_STATE_FULLSCREEN, _NET_WM_STATE_FOCUSED";
+            let value = parse_property(output, NET_WM_STATE).unwrap();
+
+            assert!(parse_is_fullscreen(&value));
+        }
+    }
+
+    mod parse_user_time {
+        use super::*;
+
+        #[test]
+        fn test_user_time() {
+            let output = "_NET_WM_USER_TIME(CARDINAL) = 218431690";
+            let value = parse_property(output, NET_WM_USER_TIME).unwrap();
+
+            assert_eq!(parse_user_time(&value), Some(218431690));
+        }
+
+        #[test]
+        fn test_invalid_user_time() {
+            assert_eq!(parse_user_time("not a number"), None);
+        }
+    }
+
+    mod parse_window_properties {
+        use super::*;
+
+        #[test]
+        fn test_all_properties() {
+            let output = [
+                "_NET_WM_WINDOW_TYPE(ATOM) = _NET_WM_WINDOW_TYPE_NORMAL",
+                "_GTK_FRAME_EXTENTS:  not found.",
+                "_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                parse_window_properties(&output),
+                WindowProperties {
+                    window_type: Some("_NET_WM_WINDOW_TYPE_NORMAL".to_string()),
+                    frame_extents: Some(FrameExtents {
+                        left: 0,
+                        right: 0,
+                        top: 37,
+                        bottom: 0
+                    })
+                }
+            );
+        }
+
+        #[test]
+        fn test_missing_properties() {
+            let output = [
+                "_NET_WM_WINDOW_TYPE:  not found.",
+                "_GTK_FRAME_EXTENTS:  not found.",
+                "_NET_FRAME_EXTENTS:  not found.",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                parse_window_properties(&output),
+                WindowProperties::default()
+            );
+            assert_eq!(parse_window_properties(""), WindowProperties::default());
+        }
+    }
+
+    mod parse_frame_extents {
+        use super::*;
+
+        #[test]
+        fn test_net_frame_extents() {
+            let output = [
+                "_GTK_FRAME_EXTENTS:  not found.",
+                "_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37, 0",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                parse_frame_extents(&output),
+                Some(FrameExtents {
+                    left: 0,
+                    right: 0,
+                    top: 37,
+                    bottom: 0
+                })
+            );
+        }
+
+        #[test]
+        fn test_gtk_frame_extents_take_precedence() {
+            let output = [
+                "_GTK_FRAME_EXTENTS(CARDINAL) = 26, 26, 23, 29",
+                "_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 0, 0",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                parse_frame_extents(&output),
+                Some(FrameExtents {
+                    left: 26,
+                    right: 26,
+                    top: 23,
+                    bottom: 29
+                })
+            );
+        }
+
+        #[test]
+        fn test_no_frame_extents() {
+            let output = [
+                "_GTK_FRAME_EXTENTS:  not found.",
+                "_NET_FRAME_EXTENTS:  not found.",
+            ]
+            .join("\n");
+
+            assert_eq!(parse_frame_extents(&output), None);
+        }
+
+        #[test]
+        fn test_empty_output() {
+            assert_eq!(parse_frame_extents(""), None);
+        }
+
+        #[test]
+        fn test_malformed_values() {
+            assert_eq!(
+                parse_frame_extents("_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, 37"),
+                None
+            );
+
+            assert_eq!(
+                parse_frame_extents("_NET_FRAME_EXTENTS(CARDINAL) = 0, 0, abc, 0"),
+                None
+            );
+        }
+    }
+}
@@ -1,25 +1,129 @@
 use anyhow::Result;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::models::{Monitor, MonitorGrid, Workspace};
 
 use super::utils::{get_command_output, is_tool_installed};
 
-type MonitorConfig = String;
-type ParsedMonitorConfig = (String, i32, i32); // (dimensions, x_offset, y_offset)
+pub type MonitorConfig = String;
+pub(super) type ParsedMonitorConfig = (Monitor, i32, i32); // (monitor, x_offset, y_offset)
 
-pub fn check_if_installed() {
+/// The properties of a connected output, as listed by `xrandr --prop`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutputProps {
+    /// The EDID as a single hex string, if the output has one.
+    pub edid: Option<String>,
+    /// Every other property's current value by name (e.g. `scaling mode` => `None`).
+    /// The supported values and ranges listed under some properties are left out.
+    pub properties: HashMap<String, String>,
+}
+
+impl OutputProps {
+    /// The stable identity derived from the EDID (e.g. `GSM-5B09-0001E2B4`), if it has a valid one.
+    pub fn identity(&self) -> Option<String> {
+        self.edid.as_deref().and_then(edid_identity)
+    }
+}
+
+pub fn check_if_installed() -> Result<()> {
     if !is_tool_installed("xrandr") {
-        eprintln!("Error: xrandr is not installed; please install it first through your e.g. package manager");
-        std::process::exit(1);
+        return Err(anyhow::anyhow!(
+            "xrandr is not installed; please install it first through your e.g. package manager"
+        ));
     }
+
+    Ok(())
 }
 
+/// Parses the workspace from the compact list of active monitors, falling back to the full `xrandr` output if that fails.
 pub fn parse_workspace() -> Result<Workspace> {
-    let raw_monitors = get_raw_monitors_config();
-    let parsed_monitors_grid = parse_raw_monitors_config(&raw_monitors)?;
+    parse_active_monitors().or_else(|error| {
+        log::debug!("Falling back to the full xrandr output: {error:#}");
+        parse_workspace_from(&get_raw_monitors_config()?)
+    })
+}
+
+/// Parses the workspace from `xrandr --listactivemonitors`, which is much faster than the full `xrandr` output.
+pub fn parse_active_monitors() -> Result<Workspace> {
+    let output = get_command_output(&["xrandr", "--listactivemonitors"])?;
+
+    build_workspace(parse_active_monitor_configs(&output)?)
+}
+
+/// Builds the workspace from the given raw monitors config (see `get_raw_monitors_config`).
+pub fn parse_workspace_from(raw_monitors: &[MonitorConfig]) -> Result<Workspace> {
+    build_workspace(parse_monitor_configs(raw_monitors)?)
+}
+
+/// Builds the workspace from the parsed monitors, with its origin at their smallest offsets.
+pub(super) fn build_workspace(monitor_configs: Vec<ParsedMonitorConfig>) -> Result<Workspace> {
+    let origin = find_origin(&monitor_configs);
+    let parsed_monitors_grid = build_monitors_grid(monitor_configs)?;
+
+    Ok(Workspace::new(MonitorGrid::new(parsed_monitors_grid)?).with_origin(origin))
+}
+
+/// Parses the workspaces of every X screen on the display (e.g. `:0.0` and `:0.1` on a setup with a separate screen
+/// per GPU) into a single workspace, with each screen's monitors placed to the right of the previous screen's.
+///
+/// Plain `xrandr` only reports the default screen, so the screens are enumerated by asking for each one in turn until
+/// xrandr rejects the screen number.
+pub fn parse_all_screens_workspace() -> Result<Workspace> {
+    let mut screen_outputs = Vec::new();
+
+    for screen in 0.. {
+        let screen_number = screen.to_string();
+
+        let output = match get_command_output(&[
+            "xrandr",
+            "--screen",
+            &screen_number,
+            "--listactivemonitors",
+        ]) {
+            Ok(output) if !output.trim().is_empty() => output,
+            // Screen 0 always exists, so failing to read it is an actual error.
+            Ok(_) | Err(_) if screen > 0 => break,
+            Ok(_) => return Err(anyhow::anyhow!("xrandr didn't report any monitors")),
+            Err(error) => return Err(error),
+        };
+
+        screen_outputs.push(output);
+    }
+
+    build_all_screens_workspace(&screen_outputs)
+}
+
+/// Merges each screen's `xrandr --listactivemonitors` output into a single workspace, with its origin at the smallest
+/// offsets across all of the screens' monitors (like `build_workspace`).
+fn build_all_screens_workspace(screen_outputs: &[String]) -> Result<Workspace> {
+    let screens = screen_outputs
+        .iter()
+        .map(|output| parse_active_monitor_configs(output))
+        .collect::<Result<Vec<Vec<ParsedMonitorConfig>>>>()?;
+
+    let origin = find_origin(&screens.concat());
+    let grids = screens
+        .into_iter()
+        .map(|monitor_configs| MonitorGrid::new(build_monitors_grid(monitor_configs)?))
+        .collect::<Result<Vec<MonitorGrid>>>()?;
+
+    Ok(Workspace::new(MonitorGrid::merge_screens(grids)).with_origin(origin))
+}
+
+/// Reads the stable (EDID-based) identity of each connected monitor, keyed by output name (e.g. `DisplayPort-0`).
+///
+/// Monitors without an EDID are left out.
+pub fn get_monitor_identities() -> Result<HashMap<String, String>> {
+    Ok(parse_output_properties()?
+        .into_iter()
+        .filter_map(|(name, props)| Some((name, props.identity()?)))
+        .collect())
+}
 
-    Ok(Workspace::new(MonitorGrid(parsed_monitors_grid)))
+/// Reads the properties of each connected output, keyed by output name (e.g. `DisplayPort-0`).
+pub fn parse_output_properties() -> Result<HashMap<String, OutputProps>> {
+    let output = get_command_output(&["xrandr", "--prop"])?;
+    Ok(parse_prop_output(&output))
 }
 
 /// Sample output:
@@ -30,34 +134,238 @@ pub fn parse_workspace() -> Result<Workspace> {
 ///     "DisplayPort-2 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm",
 ///     "HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm"
 /// ]
-fn get_raw_monitors_config() -> Vec<MonitorConfig> {
-    let output = get_command_output(&["xrandr"]).trim().to_owned();
+pub fn get_raw_monitors_config() -> Result<Vec<MonitorConfig>> {
+    let output = get_command_output(&["xrandr"])?;
+    Ok(filter_connected_monitors(&output))
+}
+
+/// A short summary of the active monitors that's much cheaper to get than the full config, which makes it suitable for
+/// checking whether the monitors have changed.
+///
+/// Sample output:
+///
+/// Monitors: 2
+///  0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0
+///  1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0
+pub fn get_active_monitors_summary() -> Result<String> {
+    let output = get_command_output(&["xrandr", "--listactivemonitors"])?;
+    Ok(normalize_monitors_summary(&output))
+}
+
+fn normalize_monitors_summary(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
 
+fn filter_connected_monitors(output: &str) -> Vec<MonitorConfig> {
     output
+        .trim()
         .split("\n")
         .filter(|line| line.contains(" connected "))
         .map(|line| line.to_owned())
         .collect()
 }
 
-fn parse_raw_monitors_config(raw_monitors: &[MonitorConfig]) -> Result<Vec<Vec<Monitor>>> {
+fn parse_monitor_configs(raw_monitors: &[MonitorConfig]) -> Result<Vec<ParsedMonitorConfig>> {
     // Parse the xrandr output.
-    let mut monitor_configs: Vec<ParsedMonitorConfig> = raw_monitors
+    raw_monitors
         .iter()
         .map(parse_monitor_config)
+        .collect::<Result<Vec<ParsedMonitorConfig>>>()
+}
+
+/// Sample output:
+///
+/// Monitors: 2
+///  0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0
+///  1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0
+fn parse_active_monitor_configs(output: &str) -> Result<Vec<ParsedMonitorConfig>> {
+    let monitor_configs: Vec<ParsedMonitorConfig> = output
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("Monitors:"))
+        .map(parse_active_monitor_config)
         .collect::<Result<Vec<ParsedMonitorConfig>>>()?;
 
+    if monitor_configs.is_empty() {
+        return Err(anyhow::anyhow!("No active monitors found"));
+    }
+
+    Ok(monitor_configs)
+}
+
+/// Parses a line like `0: +*DP-2 1920/527x1080/296+0+0  DP-2`, where the geometry is of the form
+/// `width/mmwidth x height/mmheight + x + y`.
+fn parse_active_monitor_config(line: &str) -> Result<ParsedMonitorConfig> {
+    let invalid_config = || anyhow::anyhow!("Invalid active monitor config: {line}");
+
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let name = parts.get(1).ok_or_else(invalid_config)?;
+    let geometry = parts.get(2).ok_or_else(invalid_config)?;
+    let offsets: Vec<&str> = geometry.split('+').collect();
+
+    if offsets.len() != 3 {
+        return Err(invalid_config());
+    }
+
+    let (width, height) = offsets[0].split_once('x').ok_or_else(invalid_config)?;
+
+    // Each size is followed by its physical size (in mm), e.g. `1920/527`.
+    let (width, width_mm) = width.split_once('/').unwrap_or((width, "0"));
+    let (height, height_mm) = height.split_once('/').unwrap_or((height, "0"));
+
+    let dimensions = format!("{width}x{height}");
+
+    let x_offset = offsets[1].parse::<i32>()?;
+    let y_offset = offsets[2].parse::<i32>()?;
+
+    // The name is prefixed with `+` (and `*` for the primary monitor).
+    let monitor = Monitor {
+        name: Some(name.trim_start_matches(['+', '*']).to_owned()),
+        primary: name.contains('*'),
+        ..Monitor::from_string_dimensions(&dimensions)?
+    }
+    .with_physical_size(
+        width_mm.parse::<i32>().unwrap_or(0),
+        height_mm.parse::<i32>().unwrap_or(0),
+    );
+
+    Ok((monitor, x_offset, y_offset))
+}
+
+/// Parses the output of `xrandr --prop`, where each output's header line is followed by its properties (indented
+/// by one tab), any multi-line values or supported values/ranges (indented by two tabs), and finally its modes:
+///
+/// ```text
+/// DisplayPort-0 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+///         EDID:
+///                 00ffffffffffff001e6d095bb4e20100
+///                 ...
+///         scaling mode: None
+///                 supported: None, Full, Center, Full aspect
+///    1920x1080     60.00*+
+/// ```
+///
+/// Disconnected outputs are skipped.
+fn parse_prop_output(output: &str) -> HashMap<String, OutputProps> {
+    let mut outputs = HashMap::new();
+    let mut current_output: Option<(String, OutputProps)> = None;
+    let mut current_property: Option<String> = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            outputs.extend(current_output.take());
+            current_property = None;
+
+            if let [name, "connected", ..] = line.split_whitespace().collect::<Vec<&str>>()[..] {
+                current_output = Some((name.to_owned(), OutputProps::default()));
+            }
+
+            continue;
+        }
+
+        let Some((_, props)) = current_output.as_mut() else {
+            continue;
+        };
+
+        if let Some(value) = line.strip_prefix("\t\t") {
+            // Only the EDID continues onto the following lines; the rest are the supported values/ranges.
+            if current_property.as_deref() == Some("EDID") {
+                if let Some(edid) = props.edid.as_mut() {
+                    edid.push_str(value.trim());
+                }
+            }
+        } else if let Some(property) = line.strip_prefix('\t') {
+            let Some((name, value)) = property.split_once(':') else {
+                continue;
+            };
+
+            if name == "EDID" {
+                props.edid = Some(value.trim().to_owned());
+            } else {
+                props
+                    .properties
+                    .insert(name.to_owned(), value.trim().to_owned());
+            }
+
+            current_property = Some(name.to_owned());
+        } else {
+            // The modes, which are indented with spaces, come after the properties.
+            current_property = None;
+        }
+    }
+
+    outputs.extend(current_output);
+
+    // An empty EDID means that the output doesn't have one.
+    for props in outputs.values_mut() {
+        props.edid = props.edid.take().filter(|edid| !edid.is_empty());
+    }
+
+    outputs
+}
+
+/// Derives an identity from the EDID's manufacturer ID, product code, and serial number (e.g. `GSM-5B09-0001E2B4`).
+///
+/// Ref: https://en.wikipedia.org/wiki/Extended_Display_Identification_Data#Structure,_version_1.4
+fn edid_identity(edid: &str) -> Option<String> {
+    const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+    if edid.len() < 32 || !edid.is_ascii() {
+        return None;
+    }
+
+    let bytes = (0..32)
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&edid[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    if bytes[..8] != HEADER {
+        return None;
+    }
+
+    // The manufacturer ID is three letters packed into 5 bits each (big-endian), with 1 being 'A'.
+    let manufacturer_id = u16::from_be_bytes([bytes[8], bytes[9]]);
+    let manufacturer = [10, 5, 0]
+        .iter()
+        .map(|shift| match ((manufacturer_id >> shift) & 0x1f) as u8 {
+            letter @ 1..=26 => Some(char::from(b'A' + letter - 1)),
+            _ => None,
+        })
+        .collect::<Option<String>>()?;
+
+    let product_code = u16::from_le_bytes([bytes[10], bytes[11]]);
+    let serial_number = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+
+    Some(format!(
+        "{manufacturer}-{product_code:04X}-{serial_number:08X}"
+    ))
+}
+
+/// Arranges the monitors into columns (by x-offset) of rows (by y-offset).
+///
+/// Mirrored monitors (i.e. ones with the same size at the same position) are collapsed into a single monitor first,
+/// since they show the same part of the workspace.
+pub(super) fn build_monitors_grid(
+    monitor_configs: Vec<ParsedMonitorConfig>,
+) -> Result<Vec<Vec<Monitor>>> {
+    let mut monitor_configs = collapse_mirrored_monitors(monitor_configs);
+
     // Sort monitors by x_offset and then by y_offset.
     monitor_configs.sort_by_key(|&(_, x_offset, y_offset)| (x_offset, y_offset));
 
     // Create a BTreeMap to hold columns.
-    let mut columns: BTreeMap<i32, Vec<(String, i32)>> = BTreeMap::new();
+    let mut columns: BTreeMap<i32, Vec<(Monitor, i32)>> = BTreeMap::new();
 
-    for (dimensions, x_offset, y_offset) in monitor_configs {
+    for (monitor, x_offset, y_offset) in monitor_configs {
         columns
             .entry(x_offset)
             .or_default()
-            .push((dimensions, y_offset));
+            .push((monitor, y_offset));
     }
 
     // Sort each column by y_offset.
@@ -68,49 +376,154 @@ fn parse_raw_monitors_config(raw_monitors: &[MonitorConfig]) -> Result<Vec<Vec<M
     // Convert the BTreeMap to a 2D array.
     let grid: Vec<Vec<Monitor>> = columns
         .into_values()
-        .map(|column| {
-            column
-                .into_iter()
-                .map(|(dimensions, _)| Monitor::from_string_dimensions(&dimensions))
-                .collect::<Result<Vec<Monitor>>>()
-        })
-        .collect::<Result<Vec<Vec<Monitor>>>>()?;
+        .map(|column| column.into_iter().map(|(monitor, _)| monitor).collect())
+        .collect();
 
     Ok(grid)
 }
 
-fn parse_monitor_config(monitor_config: &MonitorConfig) -> Result<ParsedMonitorConfig> {
-    let config_parts: Vec<&str> = monitor_config.split_whitespace().collect();
+/// Finds the top-left corner of the monitors' bounding box, i.e. their smallest x and y offsets, which is only not
+/// (0, 0) when monitors are placed at negative offsets.
+///
+/// The grid itself is built from the monitors' relative positions, so it's the same either way; it's only the
+/// windows' offsets that have to be shifted to match (see `Workspace::normalize_window`).
+fn find_origin(monitor_configs: &[ParsedMonitorConfig]) -> (i32, i32) {
+    let min_x = monitor_configs
+        .iter()
+        .map(|(_, x_offset, _)| *x_offset)
+        .min();
+    let min_y = monitor_configs
+        .iter()
+        .map(|(_, _, y_offset)| *y_offset)
+        .min();
 
-    if config_parts.len() < 3 {
-        return Err(anyhow::anyhow!("Invalid monitor config: {monitor_config}"));
-    }
+    (min_x.unwrap_or(0), min_y.unwrap_or(0))
+}
+
+/// Keeps only the first of each set of mirrored monitors, which is marked as primary if any of them are.
+fn collapse_mirrored_monitors(
+    monitor_configs: Vec<ParsedMonitorConfig>,
+) -> Vec<ParsedMonitorConfig> {
+    let mut collapsed: Vec<ParsedMonitorConfig> = Vec::new();
 
-    let position_index = if config_parts[2] == "primary" { 3 } else { 2 };
+    for (monitor, x_offset, y_offset) in monitor_configs {
+        let mirrored = collapsed.iter_mut().find(|(other, other_x, other_y)| {
+            (other.width, other.height, *other_x, *other_y)
+                == (monitor.width, monitor.height, x_offset, y_offset)
+        });
 
-    if let Some(position) = config_parts.get(position_index) {
-        let offsets: Vec<&str> = position.split('+').collect();
+        match mirrored {
+            Some((other, _, _)) => {
+                log::debug!(
+                    "Treating {} as a mirror of {}",
+                    monitor.name.as_deref().unwrap_or("unknown"),
+                    other.name.as_deref().unwrap_or("unknown")
+                );
 
-        if offsets.len() != 3 {
-            return Err(anyhow::anyhow!("Invalid monitor config: {monitor_config}"));
+                other.primary |= monitor.primary;
+            }
+            None => collapsed.push((monitor, x_offset, y_offset)),
         }
+    }
+
+    collapsed
+}
+
+/// Parses the physical size at the end of a monitor config line, e.g. `800mm x 337mm`.
+fn parse_physical_size(config_parts: &[&str]) -> Option<(i32, i32)> {
+    let [width, "x", height] = config_parts.get(config_parts.len().checked_sub(3)?..)? else {
+        return None;
+    };
+
+    let parse_mm = |size: &str| size.strip_suffix("mm")?.parse::<i32>().ok();
+
+    Some((parse_mm(width)?, parse_mm(height)?))
+}
+
+/// Whether the token is a geometry of the form `WIDTHxHEIGHT+X+Y`, e.g. `1920x1080+0+1080`.
+///
+/// The offsets can be negative in some layouts, e.g. `1920x1080+-1920+0` for a monitor left of the origin.
+fn is_geometry(token: &str) -> bool {
+    let is_number =
+        |value: &str| !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit());
+    let is_offset = |value: &str| is_number(value.strip_prefix('-').unwrap_or(value));
+
+    let Some((size, offsets)) = token.split_once('+') else {
+        return false;
+    };
+
+    matches!(size.split_once('x'), Some((width, height)) if is_number(width) && is_number(height))
+        && matches!(offsets.split_once('+'), Some((x, y)) if is_offset(x) && is_offset(y))
+}
+
+/// Parses a line like `HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x
+/// 296mm`.
+///
+/// Rather than relying on fixed positions, the geometry is the first token that looks like one, and the monitor is
+/// primary if a `primary` token comes anywhere before it.
+fn parse_monitor_config(monitor_config: &MonitorConfig) -> Result<ParsedMonitorConfig> {
+    let invalid_config = || anyhow::anyhow!("Invalid monitor config: {monitor_config}");
+
+    let config_parts: Vec<&str> = monitor_config.split_whitespace().collect();
+    let name = config_parts.first().ok_or_else(invalid_config)?;
+
+    let position_index = config_parts
+        .iter()
+        .position(|part| is_geometry(part))
+        .ok_or_else(invalid_config)?;
+
+    let primary = config_parts[1..position_index].contains(&"primary");
+    let offsets: Vec<&str> = config_parts[position_index].split('+').collect();
 
-        let dimensions = offsets[0].to_owned();
-        let x_offset = offsets[1].parse::<i32>()?;
-        let y_offset = offsets[2].parse::<i32>()?;
+    let mut monitor = Monitor {
+        name: Some(name.to_string()),
+        primary,
+        ..Monitor::from_string_dimensions(offsets[0])?
+    };
 
-        Ok((dimensions, x_offset, y_offset))
-    } else {
-        Err(anyhow::anyhow!("Invalid monitor config: {monitor_config}"))
+    if let Some((width_mm, height_mm)) = parse_physical_size(&config_parts) {
+        monitor = monitor.with_physical_size(width_mm, height_mm);
     }
+
+    let x_offset = offsets[1].parse::<i32>()?;
+    let y_offset = offsets[2].parse::<i32>()?;
+
+    Ok((monitor, x_offset, y_offset))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::MonitorIndex;
+
+    fn parse_raw_monitors_config(raw_monitors: &[MonitorConfig]) -> Result<Vec<Vec<Monitor>>> {
+        build_monitors_grid(parse_monitor_configs(raw_monitors)?)
+    }
+
+    fn parse_active_monitors_output(output: &str) -> Result<Vec<Vec<Monitor>>> {
+        build_monitors_grid(parse_active_monitor_configs(output)?)
+    }
+
+    fn create_named_monitor(name: &str, monitor: Monitor) -> Monitor {
+        Monitor {
+            name: Some(name.to_owned()),
+            ..monitor
+        }
+    }
+
+    #[test]
+    fn test_normalize_monitors_summary() {
+        let output = "Monitors: 2\n 0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0\n 1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0\n\n";
+
+        assert_eq!(
+            normalize_monitors_summary(output),
+            "Monitors: 2\n0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0\n1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0"
+        );
+    }
 
     mod parse_raw_monitors_config {
         use super::*;
+        use crate::models::MonitorIndex;
 
         #[test]
         fn test_can_parse_quad_monitor_config() {
@@ -126,12 +539,333 @@ mod tests {
             assert_eq!(
                 monitor_grid,
                 vec![
-                    vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
-                    vec![Monitor::new(3440, 1440)],
-                    vec![Monitor::new(1440, 2560)],
+                    vec![
+                        create_named_monitor(
+                            "DisplayPort-2",
+                            Monitor::new(1920, 1080).with_physical_size(527, 296)
+                        ),
+                        create_named_monitor(
+                            "HDMI-A-0",
+                            Monitor::new_primary(1920, 1080).with_physical_size(527, 296)
+                        ),
+                    ],
+                    vec![create_named_monitor(
+                        "DisplayPort-0",
+                        Monitor::new(3440, 1440).with_physical_size(800, 337)
+                    )],
+                    vec![create_named_monitor(
+                        "DisplayPort-1",
+                        Monitor::new(1440, 2560).with_physical_size(597, 336)
+                    )],
                 ]
             );
         }
+
+        #[test]
+        fn test_identifies_primary_monitor_in_quad_monitor_config() {
+            let mock_config = vec![
+                "DisplayPort-0 connected 3440x1440+1920+540 (normal left inverted right x axis y axis) 800mm x 337mm".to_owned(),
+                "DisplayPort-1 connected 1440x2560+5360+0 right (normal left inverted right x axis y axis) 597mm x 336mm".to_owned(),
+                "DisplayPort-2 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned()
+            ];
+
+            let monitor_grid = MonitorGrid(parse_raw_monitors_config(&mock_config).unwrap());
+
+            // HDMI-A-0 is the bottom monitor of the left-most column.
+            assert_eq!(monitor_grid.get_primary_monitor(), Some(MonitorIndex(1)));
+        }
+
+        #[test]
+        fn test_resolves_monitor_by_name_in_quad_monitor_config() {
+            let mock_config = vec![
+                "DisplayPort-0 connected 3440x1440+1920+540 (normal left inverted right x axis y axis) 800mm x 337mm".to_owned(),
+                "DisplayPort-1 connected 1440x2560+5360+0 right (normal left inverted right x axis y axis) 597mm x 336mm".to_owned(),
+                "DisplayPort-2 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned()
+            ];
+
+            let monitor_grid = MonitorGrid(parse_raw_monitors_config(&mock_config).unwrap());
+
+            assert_eq!(
+                monitor_grid.index_of_name("DisplayPort-2"),
+                Some(MonitorIndex(0))
+            );
+            assert_eq!(
+                monitor_grid.index_of_name("HDMI-A-0"),
+                Some(MonitorIndex(1))
+            );
+            assert_eq!(
+                monitor_grid.index_of_name("DisplayPort-0"),
+                Some(MonitorIndex(2))
+            );
+            assert_eq!(
+                monitor_grid.index_of_name("DisplayPort-1"),
+                Some(MonitorIndex(3))
+            );
+        }
+    }
+
+    mod find_origin {
+        use super::*;
+
+        #[test]
+        fn test_origin_at_zero() {
+            let monitor_configs = vec![
+                (Monitor::new(1920, 1080), 0, 0),
+                (Monitor::new(1920, 1080), 1920, 0),
+            ];
+
+            assert_eq!(find_origin(&monitor_configs), (0, 0));
+        }
+
+        #[test]
+        fn test_negative_offsets() {
+            let monitor_configs = vec![
+                (Monitor::new(1920, 1080), -1920, 0),
+                (Monitor::new(1920, 1080), 0, -540),
+                (Monitor::new(1920, 1080), 0, 540),
+            ];
+
+            assert_eq!(find_origin(&monitor_configs), (-1920, -540));
+        }
+
+        #[test]
+        fn test_negative_layout_matches_shifted_layout() {
+            let negative_config = vec![
+                "DP-1 connected 1920x1080+-1920+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "HDMI-A-0 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "DP-2 connected 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+            ];
+            let shifted_config = vec![
+                "DP-1 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "HDMI-A-0 connected primary 1920x1080+1920+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "DP-2 connected 1920x1080+1920+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+            ];
+
+            let negative_workspace = parse_workspace_from(&negative_config).unwrap();
+            let shifted_workspace = parse_workspace_from(&shifted_config).unwrap();
+
+            assert_eq!(
+                negative_workspace.monitor_grid.0,
+                shifted_workspace.monitor_grid.0
+            );
+            assert_eq!(negative_workspace.width(), shifted_workspace.width());
+            assert_eq!(negative_workspace.origin(), (-1920, 0));
+            assert_eq!(shifted_workspace.origin(), (0, 0));
+        }
+    }
+
+    mod build_all_screens_workspace {
+        use super::*;
+
+        #[test]
+        fn test_screen_with_negative_offsets() {
+            let screen_outputs = [
+                "Monitors: 1\n 0: +*HDMI-A-0 1920/527x1080/296+0+0  HDMI-A-0\n".to_owned(),
+                "Monitors: 2\n 0: +DP-1 1920/527x1080/296+-1920+0  DP-1\n 1: +DP-2 1920/527x1080/296+0+-540  DP-2\n"
+                    .to_owned(),
+            ];
+
+            let workspace = build_all_screens_workspace(&screen_outputs).unwrap();
+
+            assert_eq!(workspace.origin(), (-1920, -540));
+            assert_eq!(workspace.monitor_grid.calculate_monitor_count(), 3);
+            assert_eq!(
+                workspace.monitor_grid.get_primary_monitor(),
+                Some(MonitorIndex(0))
+            );
+        }
+
+        #[test]
+        fn test_invalid_screen() {
+            let screen_outputs = [
+                "Monitors: 1\n 0: +*HDMI-A-0 1920/527x1080/296+0+0  HDMI-A-0\n".to_owned(),
+                "Monitors: 0\n".to_owned(),
+            ];
+
+            assert!(build_all_screens_workspace(&screen_outputs).is_err());
+        }
+    }
+
+    mod collapse_mirrored_monitors {
+        use super::*;
+
+        #[test]
+        fn test_collapses_mirrored_outputs() {
+            let mock_config = vec![
+                "eDP-1 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 193mm".to_owned(),
+                "HDMI-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+                "DP-1 connected 1920x1080+1920+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+            ];
+
+            let monitor_grid = parse_raw_monitors_config(&mock_config).unwrap();
+
+            assert_eq!(
+                monitor_grid,
+                vec![
+                    vec![create_named_monitor(
+                        "eDP-1",
+                        Monitor::new_primary(1920, 1080).with_physical_size(344, 193)
+                    )],
+                    vec![create_named_monitor(
+                        "DP-1",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )],
+                ]
+            );
+        }
+
+        #[test]
+        fn test_collapses_mirrored_active_monitors() {
+            let output = [
+                "Monitors: 2",
+                " 0: +*eDP-1 1920/344x1080/193+0+0  eDP-1",
+                " 1: +HDMI-1 1920/527x1080/296+0+0  HDMI-1",
+            ]
+            .join("\n");
+
+            let monitor_grid = parse_active_monitors_output(&output).unwrap();
+
+            assert_eq!(monitor_grid.len(), 1);
+            assert_eq!(monitor_grid[0].len(), 1);
+            assert_eq!(monitor_grid[0][0].name.as_deref(), Some("eDP-1"));
+        }
+
+        #[test]
+        fn test_keeps_overlapping_monitors_of_different_sizes() {
+            // Same position, but a different size, so they're not mirrors of each other.
+            let monitor_configs = vec![
+                (Monitor::new(1920, 1080), 0, 0),
+                (Monitor::new(1280, 720), 0, 0),
+            ];
+
+            assert_eq!(collapse_mirrored_monitors(monitor_configs).len(), 2);
+        }
+    }
+
+    mod parse_active_monitors_output {
+        use super::*;
+
+        #[test]
+        fn test_multi_monitor_layout() {
+            let output = [
+                "Monitors: 4",
+                " 0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0",
+                " 1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0",
+                " 2: +DisplayPort-1 1440/597x2560/336+5360+0  DisplayPort-1",
+                " 3: +DisplayPort-2 1920/527x1080/296+0+0  DisplayPort-2",
+            ]
+            .join("\n");
+
+            // Same layout as the quad monitor config parsed from the full xrandr output.
+            let expected = vec![
+                vec![
+                    create_named_monitor(
+                        "DisplayPort-2",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296),
+                    ),
+                    create_named_monitor(
+                        "HDMI-A-0",
+                        Monitor::new_primary(1920, 1080).with_physical_size(527, 296),
+                    ),
+                ],
+                vec![create_named_monitor(
+                    "DisplayPort-0",
+                    Monitor::new(3440, 1440).with_physical_size(800, 337),
+                )],
+                vec![create_named_monitor(
+                    "DisplayPort-1",
+                    Monitor::new(1440, 2560).with_physical_size(597, 336),
+                )],
+            ];
+
+            assert_eq!(parse_active_monitors_output(&output).unwrap(), expected);
+        }
+
+        #[test]
+        fn test_primary_monitor() {
+            let output = "Monitors: 2\n 0: +*DP-2 1920/527x1080/296+0+0  DP-2\n 1: +DP-3 1920/527x1080/296+1920+0  DP-3\n";
+
+            assert_eq!(
+                parse_active_monitors_output(output).unwrap(),
+                vec![
+                    vec![create_named_monitor(
+                        "DP-2",
+                        Monitor::new_primary(1920, 1080).with_physical_size(527, 296)
+                    )],
+                    vec![create_named_monitor(
+                        "DP-3",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )]
+                ]
+            );
+        }
+
+        #[test]
+        fn test_no_monitors() {
+            assert!(parse_active_monitors_output("Monitors: 0\n").is_err());
+        }
+
+        #[test]
+        fn test_invalid_geometry() {
+            assert!(parse_active_monitor_config("0: +*DP-2 1920x1080  DP-2").is_err());
+            assert!(parse_active_monitor_config("0: +*DP-2 1920/527+0+0  DP-2").is_err());
+            assert!(parse_active_monitor_config("0: +*DP-2 1920/527x1080/296+a+0  DP-2").is_err());
+            assert!(parse_active_monitor_config("0: +*DP-2").is_err());
+        }
+    }
+
+    mod parse_physical_size {
+        use super::*;
+
+        fn get_result(config: &str) -> Option<(i32, i32)> {
+            parse_physical_size(&config.split_whitespace().collect::<Vec<&str>>())
+        }
+
+        #[test]
+        fn test_normal_line() {
+            assert_eq!(
+                get_result("DisplayPort-0 connected 3440x1440+1920+540 (normal left inverted right x axis y axis) 800mm x 337mm"),
+                Some((800, 337))
+            );
+        }
+
+        #[test]
+        fn test_primary_line() {
+            assert_eq!(
+                get_result("HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm"),
+                Some((527, 296))
+            );
+        }
+
+        #[test]
+        fn test_rotated_line() {
+            assert_eq!(
+                get_result("DisplayPort-1 connected 1440x2560+5360+0 right (normal left inverted right x axis y axis) 597mm x 336mm"),
+                Some((597, 336))
+            );
+        }
+
+        #[test]
+        fn test_missing_physical_size() {
+            assert_eq!(get_result("DisplayPort-0 connected 1920x1080+0+0"), None);
+            assert_eq!(get_result("x"), None);
+            assert_eq!(
+                get_result("DisplayPort-0 connected 1920x1080+0+0 (normal left inverted right x axis y axis)"),
+                None
+            );
+        }
+
+        #[test]
+        fn test_unknown_physical_size() {
+            let config = "VIRTUAL1 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 0mm x 0mm"
+                .to_string();
+            let (monitor, _, _) = parse_monitor_config(&config).unwrap();
+
+            assert_eq!(monitor.width_mm, None);
+            assert_eq!(monitor.height_mm, None);
+        }
     }
 
     mod parse_monitor_config {
@@ -141,28 +875,68 @@ mod tests {
         fn test_parse_normal_monitor() {
             let config = "DisplayPort-0 connected 3440x1440+1920+540 (normal left inverted right x axis y axis) 800mm x 337mm".to_string();
             let result = parse_monitor_config(&config).unwrap();
-            assert_eq!(result, ("3440x1440".to_string(), 1920, 540));
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "DisplayPort-0",
+                        Monitor::new(3440, 1440).with_physical_size(800, 337)
+                    ),
+                    1920,
+                    540
+                )
+            );
         }
 
         #[test]
         fn test_parse_primary_monitor() {
             let config = "HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_string();
             let result = parse_monitor_config(&config).unwrap();
-            assert_eq!(result, ("1920x1080".to_string(), 0, 1080));
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "HDMI-A-0",
+                        Monitor::new_primary(1920, 1080).with_physical_size(527, 296)
+                    ),
+                    0,
+                    1080
+                )
+            );
         }
 
         #[test]
         fn test_parse_monitor_at_origin() {
             let config = "DisplayPort-2 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_string();
             let result = parse_monitor_config(&config).unwrap();
-            assert_eq!(result, ("1920x1080".to_string(), 0, 0));
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "DisplayPort-2",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    ),
+                    0,
+                    0
+                )
+            );
         }
 
         #[test]
         fn test_parse_monitor_large_offsets() {
             let config = "DisplayPort-1 connected 1440x2560+5360+0 right (normal left inverted right x axis y axis) 597mm x 336mm".to_string();
             let result = parse_monitor_config(&config).unwrap();
-            assert_eq!(result, ("1440x2560".to_string(), 5360, 0));
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "DisplayPort-1",
+                        Monitor::new(1440, 2560).with_physical_size(597, 336)
+                    ),
+                    5360,
+                    0
+                )
+            );
         }
 
         #[test]
@@ -200,6 +974,230 @@ mod tests {
             let result = parse_monitor_config(&config);
             assert!(result.is_err());
         }
+
+        #[test]
+        fn test_parse_primary_after_extra_tokens() {
+            let config = "HDMI-A-0 connected (extra) primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_string();
+            let result = parse_monitor_config(&config).unwrap();
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "HDMI-A-0",
+                        Monitor::new_primary(1920, 1080).with_physical_size(527, 296)
+                    ),
+                    0,
+                    1080
+                )
+            );
+        }
+
+        #[test]
+        fn test_parse_primary_before_connected() {
+            let config = "HDMI-A-0 primary connected 1920x1080+0+1080 527mm x 296mm".to_string();
+            let (monitor, _, _) = parse_monitor_config(&config).unwrap();
+            assert!(monitor.primary);
+        }
+
+        #[test]
+        fn test_parse_geometry_not_at_fixed_index() {
+            let config = "DP-1 connected extra tokens here 2560x1440+1920+0 left (normal left inverted right x axis y axis) 597mm x 336mm".to_string();
+            let result = parse_monitor_config(&config).unwrap();
+            assert_eq!(
+                result,
+                (
+                    create_named_monitor(
+                        "DP-1",
+                        Monitor::new(2560, 1440).with_physical_size(597, 336)
+                    ),
+                    1920,
+                    0
+                )
+            );
+        }
+
+        #[test]
+        fn test_parse_negative_offsets() {
+            let config = "DP-1 connected 1920x1080+-1920+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_string();
+            let (_, x_offset, y_offset) = parse_monitor_config(&config).unwrap();
+            assert_eq!((x_offset, y_offset), (-1920, 0));
+
+            let config = "DP-1 connected primary 1920x1080+0+-1080 527mm x 296mm".to_string();
+            let (monitor, x_offset, y_offset) = parse_monitor_config(&config).unwrap();
+            assert!(monitor.primary);
+            assert_eq!((x_offset, y_offset), (0, -1080));
+        }
+
+        #[test]
+        fn test_parse_primary_after_geometry_is_ignored() {
+            // Only a `primary` token before the geometry marks the monitor as primary.
+            let config = "DP-1 connected 2560x1440+1920+0 primary".to_string();
+            let (monitor, _, _) = parse_monitor_config(&config).unwrap();
+            assert!(!monitor.primary);
+        }
+    }
+
+    mod is_geometry {
+        use super::*;
+
+        #[test]
+        fn test_geometries() {
+            assert!(is_geometry("1920x1080+0+1080"));
+            assert!(is_geometry("3440x1440+1920+540"));
+        }
+
+        #[test]
+        fn test_negative_offsets() {
+            assert!(is_geometry("1920x1080+-1920+0"));
+            assert!(is_geometry("1920x1080+0+-1080"));
+            assert!(!is_geometry("1920x1080+-+0"));
+            assert!(!is_geometry("-1920x1080+0+0"));
+        }
+
+        #[test]
+        fn test_not_geometries() {
+            assert!(!is_geometry("connected"));
+            assert!(!is_geometry("1920x1080"));
+            assert!(!is_geometry("1920x1080+0"));
+            assert!(!is_geometry("1920x1080+0+0+0"));
+            assert!(!is_geometry("1920x1080+abc+0"));
+            assert!(!is_geometry("x1080+0+0"));
+            assert!(!is_geometry("(normal"));
+        }
+    }
+
+    mod parse_prop_output {
+        use super::*;
+
+        const PROP_OUTPUT: &str = "Screen 0: minimum 320 x 200, current 3840 x 1080, maximum 16384 x 16384
+DisplayPort-0 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+\tEDID: 
+\t\t00ffffffffffff001e6d095bb4e20100
+\t\t0c1f0103803c2278ea3e31ae5047ac27
+\t\t0c5054210800714081c08100818095c0
+\t\t9500b300d1c0023a801871382d40582c
+\t\t450056502100001e000000fd00383d1e
+\t\t5311000a202020202020000000fc0032
+\t\t374d4b3630300a2020202020000000ff
+\t\t003131304e54424b35383832380a0144
+\tscaling mode: None 
+\t\tsupported: None, Full, Center, Full aspect
+\tnon-desktop: 0 
+\t\trange: (0, 1)
+   1920x1080     60.00*+  50.00    59.94  
+HDMI-A-0 connected 1920x1080+1920+0 (normal left inverted right x axis y axis) 527mm x 296mm
+\tEDID: 
+\t\t00ffffffffffff0010acb1a04c313030
+\t\t1a1e010380351e78eaee95a3544c9926
+\tnon-desktop: 0 
+\t\trange: (0, 1)
+   1920x1080     60.00*+
+HDMI-A-1 disconnected (normal left inverted right x axis y axis)
+\tnon-desktop: 0 
+\t\trange: (0, 1)
+DisplayPort-1 connected (normal left inverted right x axis y axis)
+\tEDID: 
+\t\t00ffffffffffff001e6d";
+
+        #[test]
+        fn test_connected_outputs() {
+            let outputs = parse_prop_output(PROP_OUTPUT);
+
+            let mut names = outputs.keys().collect::<Vec<&String>>();
+            names.sort();
+
+            assert_eq!(names, vec!["DisplayPort-0", "DisplayPort-1", "HDMI-A-0"]);
+        }
+
+        #[test]
+        fn test_multi_line_edid() {
+            let outputs = parse_prop_output(PROP_OUTPUT);
+
+            assert_eq!(
+                outputs["DisplayPort-0"].edid.as_deref(),
+                Some(
+                    [
+                        "00ffffffffffff001e6d095bb4e20100",
+                        "0c1f0103803c2278ea3e31ae5047ac27",
+                        "0c5054210800714081c08100818095c0",
+                        "9500b300d1c0023a801871382d40582c",
+                        "450056502100001e000000fd00383d1e",
+                        "5311000a202020202020000000fc0032",
+                        "374d4b3630300a2020202020000000ff",
+                        "003131304e54424b35383832380a0144",
+                    ]
+                    .concat()
+                    .as_str()
+                )
+            );
+            assert_eq!(
+                outputs["HDMI-A-0"].edid.as_deref(),
+                Some("00ffffffffffff0010acb1a04c3130301a1e010380351e78eaee95a3544c9926")
+            );
+        }
+
+        #[test]
+        fn test_properties() {
+            let outputs = parse_prop_output(PROP_OUTPUT);
+
+            assert_eq!(
+                outputs["DisplayPort-0"].properties,
+                HashMap::from([
+                    ("scaling mode".to_owned(), "None".to_owned()),
+                    ("non-desktop".to_owned(), "0".to_owned()),
+                ])
+            );
+            assert_eq!(
+                outputs["HDMI-A-0"].properties,
+                HashMap::from([("non-desktop".to_owned(), "0".to_owned())])
+            );
+        }
+
+        #[test]
+        fn test_identities() {
+            let outputs = parse_prop_output(PROP_OUTPUT);
+
+            assert_eq!(
+                outputs["DisplayPort-0"].identity().as_deref(),
+                Some("GSM-5B09-0001E2B4")
+            );
+            assert_eq!(
+                outputs["HDMI-A-0"].identity().as_deref(),
+                Some("DEL-A0B1-3030314C")
+            );
+
+            // Truncated EDIDs don't have an identity.
+            assert_eq!(outputs["DisplayPort-1"].identity(), None);
+        }
+
+        #[test]
+        fn test_no_edid() {
+            let output = "HDMI-A-0 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+\tEDID: 
+\tnon-desktop: 0 
+\t\trange: (0, 1)";
+
+            let outputs = parse_prop_output(output);
+
+            assert_eq!(outputs["HDMI-A-0"].edid, None);
+            assert_eq!(outputs["HDMI-A-0"].identity(), None);
+        }
+
+        #[test]
+        fn test_edid_identity() {
+            assert_eq!(
+                edid_identity("00ffffffffffff001e6d095bb4e20100"),
+                Some("GSM-5B09-0001E2B4".to_owned())
+            );
+        }
+
+        #[test]
+        fn test_invalid_edid_identity() {
+            assert_eq!(edid_identity("00ffffffffffff00"), None);
+            assert_eq!(edid_identity("11ffffffffffff001e6d095bb4e20100"), None);
+            assert_eq!(edid_identity("00ffffffffffff001e6d095bb4e2zz00"), None);
+            assert_eq!(edid_identity("00ffffffffffff000000095bb4e20100"), None);
+        }
     }
 
     mod get_raw_monitors_config {
@@ -213,6 +1211,24 @@ mod tests {
         // need integration tests or mocking to test properly.
     }
 
+    mod parse_workspace_from {
+        use super::*;
+
+        #[test]
+        fn test_no_connected_monitors() {
+            // Only the connected monitors make it into the raw config, so there's nothing left.
+            let mock_config = vec![];
+
+            assert_eq!(
+                parse_workspace_from(&mock_config)
+                    .err()
+                    .unwrap()
+                    .to_string(),
+                "No active monitors detected"
+            );
+        }
+    }
+
     mod additional_parse_raw_monitors_config_tests {
         use super::*;
 
@@ -229,7 +1245,13 @@ mod tests {
                 "DisplayPort-0 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned()
             ];
             let monitor_grid = parse_raw_monitors_config(&mock_config).unwrap();
-            assert_eq!(monitor_grid, vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(
+                monitor_grid,
+                vec![vec![create_named_monitor(
+                    "DisplayPort-0",
+                    Monitor::new(1920, 1080).with_physical_size(527, 296)
+                )]]
+            );
         }
 
         #[test]
@@ -241,7 +1263,16 @@ mod tests {
             let monitor_grid = parse_raw_monitors_config(&mock_config).unwrap();
             assert_eq!(
                 monitor_grid,
-                vec![vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)]]
+                vec![vec![
+                    create_named_monitor(
+                        "DisplayPort-0",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    ),
+                    create_named_monitor(
+                        "DisplayPort-1",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )
+                ]]
             );
         }
 
@@ -255,8 +1286,14 @@ mod tests {
             assert_eq!(
                 monitor_grid,
                 vec![
-                    vec![Monitor::new(1920, 1080)],
-                    vec![Monitor::new(1920, 1080)]
+                    vec![create_named_monitor(
+                        "DisplayPort-0",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )],
+                    vec![create_named_monitor(
+                        "DisplayPort-1",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )]
                 ]
             );
         }
@@ -271,8 +1308,14 @@ mod tests {
             assert_eq!(
                 monitor_grid,
                 vec![
-                    vec![Monitor::new(1920, 1080)],
-                    vec![Monitor::new(2560, 1440)]
+                    vec![create_named_monitor(
+                        "DisplayPort-0",
+                        Monitor::new(1920, 1080).with_physical_size(527, 296)
+                    )],
+                    vec![create_named_monitor(
+                        "DisplayPort-1",
+                        Monitor::new(2560, 1440).with_physical_size(597, 336)
+                    )]
                 ]
             );
         }
@@ -1,4 +1,62 @@
+//! Focus windows by direction or monitor on X11, using `wmctrl`, `xdotool`, and `xrandr` under the hood.
+//!
+//! Besides the CLI, the core operations can be used directly as a library. None of them exit the process; any failure
+//! (including a missing tool) is returned as an error. They're configured the same way as the CLI, through the `EWS_*`
+//! environment variables (see [`Config`]).
+//!
+//! ```no_run
+//! use easy_window_switcher_rs::FocusDirection;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     easy_window_switcher_rs::external_tools::check_if_all_tools_installed()?;
+//!
+//!     let workspace = easy_window_switcher_rs::current_workspace()?;
+//!     println!("{} monitor(s)", workspace.monitor_grid.calculate_monitor_count());
+//!
+//!     for window in easy_window_switcher_rs::current_windows()? {
+//!         println!("{} {}", window.window_class, window.title);
+//!     }
+//!
+//!     easy_window_switcher_rs::focus(FocusDirection::Right)?;
+//!     easy_window_switcher_rs::focus_monitor(0)
+//! }
+//! ```
+
+#[cfg(feature = "async")]
+pub mod async_api;
 pub mod cli;
+pub mod config;
 pub mod external_tools;
 pub mod models;
 pub mod services;
+
+use anyhow::Result;
+
+pub use config::{Backend, Config, EmptyMonitorPreference, FocusStrategy};
+pub use models::{
+    FocusDirection, Monitor, MonitorGrid, MonitorIndex, MonitorSelector, Region, SortOrder, Window,
+    WindowId, Workspace,
+};
+
+use external_tools::xrandr;
+use services::window_focuser;
+
+/// Lists the windows on the current workspace, sorted from left to right.
+pub fn current_windows() -> Result<Vec<Window>> {
+    window_focuser::current_windows(&Config::from_env())
+}
+
+/// Describes the current workspace, i.e. the layout of the monitors.
+pub fn current_workspace() -> Result<Workspace> {
+    xrandr::parse_workspace()
+}
+
+/// Focuses onto the closest window in the given direction.
+pub fn focus(direction: FocusDirection) -> Result<()> {
+    window_focuser::focus_by_direction(direction, &Config::from_env())
+}
+
+/// Focuses onto the window on the monitor with the given (0-based, left-to-right) index.
+pub fn focus_monitor(index: usize) -> Result<()> {
+    window_focuser::focus_by_monitor_index(MonitorIndex(index), &Config::from_env())
+}
@@ -4,6 +4,8 @@ use anyhow::Result;
 pub enum FocusDirection {
     Left,
     Right,
+    Up,
+    Down,
 }
 
 impl FocusDirection {
@@ -12,6 +14,10 @@ impl FocusDirection {
             Ok(FocusDirection::Left)
         } else if value == "right" {
             Ok(FocusDirection::Right)
+        } else if value == "up" {
+            Ok(FocusDirection::Up)
+        } else if value == "down" {
+            Ok(FocusDirection::Down)
         } else {
             Err(anyhow::anyhow!("Invalid focus direction: {}", value))
         }
@@ -19,8 +25,23 @@ impl FocusDirection {
 
     pub fn to_int(&self) -> i32 {
         match self {
-            FocusDirection::Left => -1,
-            FocusDirection::Right => 1,
+            FocusDirection::Left | FocusDirection::Up => -1,
+            FocusDirection::Right | FocusDirection::Down => 1,
+        }
+    }
+
+    /// Whether the direction moves along the horizontal axis (i.e. between columns of monitors)
+    /// as opposed to the vertical axis (i.e. between monitors within a column).
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, FocusDirection::Left | FocusDirection::Right)
+    }
+
+    /// Swaps Left and Right, keeping Up and Down as they are.
+    pub fn mirrored_horizontally(&self) -> Self {
+        match self {
+            FocusDirection::Left => FocusDirection::Right,
+            FocusDirection::Right => FocusDirection::Left,
+            direction => direction.clone(),
         }
     }
 }
@@ -72,14 +93,26 @@ mod tests {
             assert_eq!(result, FocusDirection::Right);
         }
 
+        #[test]
+        fn test_valid_up() {
+            let result = FocusDirection::try_from_string("up").unwrap();
+            assert_eq!(result, FocusDirection::Up);
+        }
+
+        #[test]
+        fn test_valid_down() {
+            let result = FocusDirection::try_from_string("down").unwrap();
+            assert_eq!(result, FocusDirection::Down);
+        }
+
         #[test]
         fn test_invalid_direction() {
-            let result = FocusDirection::try_from_string("up");
+            let result = FocusDirection::try_from_string("diagonal");
             assert!(result.is_err());
             assert!(result
                 .unwrap_err()
                 .to_string()
-                .contains("Invalid focus direction: up"));
+                .contains("Invalid focus direction: diagonal"));
         }
 
         #[test]
@@ -107,6 +140,60 @@ mod tests {
         fn test_right_to_int() {
             assert_eq!(FocusDirection::Right.to_int(), 1);
         }
+
+        #[test]
+        fn test_up_to_int() {
+            assert_eq!(FocusDirection::Up.to_int(), -1);
+        }
+
+        #[test]
+        fn test_down_to_int() {
+            assert_eq!(FocusDirection::Down.to_int(), 1);
+        }
+    }
+
+    mod is_horizontal {
+        use super::*;
+
+        #[test]
+        fn test_horizontal_directions() {
+            assert!(FocusDirection::Left.is_horizontal());
+            assert!(FocusDirection::Right.is_horizontal());
+        }
+
+        #[test]
+        fn test_vertical_directions() {
+            assert!(!FocusDirection::Up.is_horizontal());
+            assert!(!FocusDirection::Down.is_horizontal());
+        }
+    }
+
+    mod mirrored_horizontally {
+        use super::*;
+
+        #[test]
+        fn test_swaps_left_and_right() {
+            assert_eq!(
+                FocusDirection::Left.mirrored_horizontally(),
+                FocusDirection::Right
+            );
+            assert_eq!(
+                FocusDirection::Right.mirrored_horizontally(),
+                FocusDirection::Left
+            );
+        }
+
+        #[test]
+        fn test_keeps_up_and_down() {
+            assert_eq!(
+                FocusDirection::Up.mirrored_horizontally(),
+                FocusDirection::Up
+            );
+            assert_eq!(
+                FocusDirection::Down.mirrored_horizontally(),
+                FocusDirection::Down
+            );
+        }
     }
 
     mod from_implementations {
@@ -1,11 +1,15 @@
 pub mod focus_direction;
 pub mod monitor;
 pub mod monitor_grid;
+pub mod region;
+pub mod sort_order;
 pub mod window;
 pub mod workspace;
 
 pub use focus_direction::*;
 pub use monitor::*;
 pub use monitor_grid::*;
+pub use region::*;
+pub use sort_order::*;
 pub use window::*;
 pub use workspace::*;
@@ -9,15 +9,129 @@ impl std::fmt::Display for MonitorIndex {
     }
 }
 
+/// Identifies a monitor either by its index within the grid or by its xrandr output name (e.g. `DP-2`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum MonitorSelector {
+    Index(MonitorIndex),
+    Name(String),
+}
+
+impl MonitorSelector {
+    /// Numbers are treated as indices and anything else as a name.
+    pub fn from_string(value: &str) -> Self {
+        match value.parse::<usize>() {
+            Ok(index) => MonitorSelector::Index(MonitorIndex(index)),
+            Err(_) => MonitorSelector::Name(value.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for MonitorSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorSelector::Index(index) => write!(f, "{index}"),
+            MonitorSelector::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+const MM_PER_INCH: f64 = 25.4;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Monitor {
     pub width: i32,
     pub height: i32,
+    /// Whether xrandr marks this as the primary monitor.
+    pub primary: bool,
+    /// The xrandr output name (e.g. `DisplayPort-0`), if known.
+    pub name: Option<String>,
+    /// A stable identifier derived from the monitor's EDID (e.g. `GSM-5B09-0001E2B4`), if known.
+    /// Unlike the output name, it doesn't change when the monitor is plugged into a different port.
+    pub identity: Option<String>,
+    /// How much larger the monitor is in the coordinate space that windows are positioned in than its reported
+    /// size (e.g. 2.0 for a monitor that some compositors scale 2x on a mixed-DPI setup). Normally 1.0.
+    pub scale: f64,
+    /// The physical width in millimeters as reported by xrandr, if known.
+    pub width_mm: Option<i32>,
+    /// The physical height in millimeters as reported by xrandr, if known.
+    pub height_mm: Option<i32>,
 }
 
 impl Monitor {
     pub const fn new(width: i32, height: i32) -> Self {
-        Monitor { width, height }
+        Monitor {
+            width,
+            height,
+            primary: false,
+            name: None,
+            identity: None,
+            scale: 1.0,
+            width_mm: None,
+            height_mm: None,
+        }
+    }
+
+    pub const fn new_primary(width: i32, height: i32) -> Self {
+        Monitor {
+            width,
+            height,
+            primary: true,
+            name: None,
+            identity: None,
+            scale: 1.0,
+            width_mm: None,
+            height_mm: None,
+        }
+    }
+
+    pub fn with_name(self, name: &str) -> Self {
+        Monitor {
+            name: Some(name.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn with_identity(self, identity: &str) -> Self {
+        Monitor {
+            identity: Some(identity.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn with_primary(self, primary: bool) -> Self {
+        Monitor { primary, ..self }
+    }
+
+    pub fn with_scale(self, scale: f64) -> Self {
+        Monitor { scale, ..self }
+    }
+
+    /// Sets the physical size (in millimeters). Outputs without a physical size (e.g. projectors and some virtual
+    /// displays) report 0x0, which is treated as unknown.
+    pub fn with_physical_size(self, width_mm: i32, height_mm: i32) -> Self {
+        let known = |size: i32| (size > 0).then_some(size);
+
+        Monitor {
+            width_mm: known(width_mm),
+            height_mm: known(height_mm),
+            ..self
+        }
+    }
+
+    /// The horizontal pixel density in dots per inch, if the monitor's physical width is known.
+    pub fn dpi(&self) -> Option<f64> {
+        self.width_mm
+            .map(|width_mm| self.width as f64 / (width_mm as f64 / MM_PER_INCH))
+    }
+
+    /// The width of the monitor in the coordinate space that windows are positioned in, i.e. with its scale applied.
+    pub fn scaled_width(&self) -> i32 {
+        (self.width as f64 * self.scale).round() as i32
+    }
+
+    /// The height of the monitor in the coordinate space that windows are positioned in, i.e. with its scale applied.
+    pub fn scaled_height(&self) -> i32 {
+        (self.height as f64 * self.scale).round() as i32
     }
 
     pub fn from_string_dimensions(raw_dimensions: &str) -> Result<Self> {
@@ -73,9 +187,73 @@ mod tests {
         }
     }
 
+    mod scaled_dimensions {
+        use super::*;
+
+        #[test]
+        fn test_unscaled() {
+            let monitor = Monitor::new(1920, 1080);
+
+            assert_eq!(monitor.scaled_width(), 1920);
+            assert_eq!(monitor.scaled_height(), 1080);
+        }
+
+        #[test]
+        fn test_scaled() {
+            let monitor = Monitor {
+                scale: 2.0,
+                ..Monitor::new(1920, 1080)
+            };
+
+            assert_eq!(monitor.scaled_width(), 3840);
+            assert_eq!(monitor.scaled_height(), 2160);
+        }
+
+        #[test]
+        fn test_fractional_scale() {
+            let monitor = Monitor {
+                scale: 1.25,
+                ..Monitor::new(2560, 1440)
+            };
+
+            assert_eq!(monitor.scaled_width(), 3200);
+            assert_eq!(monitor.scaled_height(), 1800);
+        }
+    }
+
+    mod monitor_selector {
+        use super::*;
+
+        #[test]
+        fn test_from_string() {
+            assert_eq!(
+                MonitorSelector::from_string("2"),
+                MonitorSelector::Index(MonitorIndex(2))
+            );
+            assert_eq!(
+                MonitorSelector::from_string("DP-2"),
+                MonitorSelector::Name("DP-2".to_owned())
+            );
+        }
+
+        #[test]
+        fn test_display_round_trip() {
+            for value in ["0", "HDMI-A-0"] {
+                assert_eq!(MonitorSelector::from_string(value).to_string(), value);
+            }
+        }
+    }
+
     mod monitor {
         use super::*;
 
+        #[test]
+        fn test_new_primary() {
+            assert!(!Monitor::new(1920, 1080).primary);
+            assert!(Monitor::new_primary(1920, 1080).primary);
+            assert_ne!(Monitor::new(1920, 1080), Monitor::new_primary(1920, 1080));
+        }
+
         #[test]
         fn test_new() {
             let monitor = Monitor::new(1920, 1080);
@@ -83,6 +261,59 @@ mod tests {
             assert_eq!(monitor.height, 1080);
         }
 
+        #[test]
+        fn test_builder() {
+            let monitor = Monitor::new(1920, 1080)
+                .with_name("DP-2")
+                .with_identity("GSM-5B09-0001E2B4")
+                .with_primary(true)
+                .with_scale(2.0)
+                .with_physical_size(527, 296);
+
+            assert_eq!(
+                monitor,
+                Monitor {
+                    width: 1920,
+                    height: 1080,
+                    primary: true,
+                    name: Some("DP-2".to_owned()),
+                    identity: Some("GSM-5B09-0001E2B4".to_owned()),
+                    scale: 2.0,
+                    width_mm: Some(527),
+                    height_mm: Some(296),
+                }
+            );
+        }
+
+        #[test]
+        fn test_unknown_physical_size() {
+            let monitor = Monitor::new(1920, 1080).with_physical_size(0, 0);
+
+            assert_eq!(monitor.width_mm, None);
+            assert_eq!(monitor.height_mm, None);
+            assert_eq!(monitor.dpi(), None);
+        }
+
+        #[test]
+        fn test_dpi_within_tolerance() {
+            let dpi = Monitor::new(1920, 1080)
+                .with_physical_size(527, 296)
+                .dpi()
+                .unwrap();
+
+            // 1920px / (527mm / 25.4mm per inch)
+            assert!((dpi - 92.54).abs() < 0.01, "unexpected DPI: {dpi}");
+        }
+
+        #[test]
+        fn test_dpi() {
+            let monitor = Monitor::new(3440, 1440).with_physical_size(800, 337);
+            assert_eq!(monitor.dpi().map(f64::round), Some(109.0));
+
+            let monitor = Monitor::new(1920, 1080).with_physical_size(527, 296);
+            assert_eq!(monitor.dpi().map(f64::round), Some(93.0));
+        }
+
         #[test]
         fn test_from_string_dimensions_valid() {
             let monitor = Monitor::from_string_dimensions("1920x1080").unwrap();
@@ -1,49 +1,334 @@
 use anyhow::Result;
+use std::collections::HashMap;
 
-use super::{FocusDirection, Monitor, MonitorIndex, Window, WINDOW_DECORATION};
+use super::{FocusDirection, Monitor, MonitorIndex, MonitorSelector, Window};
 
 #[derive(Clone)]
 pub struct MonitorGrid(pub Vec<Vec<Monitor>>);
 
 impl MonitorGrid {
+    /// Builds a grid from its columns (left-to-right), each of which holds its monitors top-to-bottom.
+    ///
+    /// Errors if there are no monitors at all (e.g. on a headless machine or with every cable unplugged), or if any
+    /// column is empty, since navigation assumes that every column has at least one monitor.
+    pub fn new(columns: Vec<Vec<Monitor>>) -> Result<Self> {
+        if columns.is_empty() {
+            return Err(anyhow::anyhow!("No active monitors detected"));
+        }
+
+        if let Some(column) = columns.iter().position(|column| column.is_empty()) {
+            return Err(anyhow::anyhow!(
+                "Invalid monitor grid: column {column} has no monitors"
+            ));
+        }
+
+        Ok(MonitorGrid(columns))
+    }
+
+    /// Finds the monitor adjacent to the current monitor in the given direction.
+    ///
+    /// Navigation works on the (column, row) coordinates of the monitors in the grid rather than their flat index:
+    /// Left/Right move between columns (staying on the same row where possible) and Up/Down move between the
+    /// monitors within a column. Each axis wraps around independently.
+    ///
+    /// Errors if there are no monitors at all, since there's nothing to move between.
     pub fn get_next_monitor(
         &self,
         current_monitor: &MonitorIndex,
         direction: &FocusDirection,
-    ) -> MonitorIndex {
-        let monitors_count = self.calculate_monitor_count();
+    ) -> Result<MonitorIndex> {
+        if self.calculate_monitor_count() == 0 {
+            return Err(anyhow::anyhow!("No active monitors detected"));
+        }
+
+        Ok(self
+            .get_next_monitor_with_wrap(current_monitor, direction, true)
+            .unwrap_or_else(|| current_monitor.clone()))
+    }
+
+    /// Same as `get_next_monitor`, but when `wrap` is false, moving past the edge of the grid along the direction's
+    /// axis returns `None` instead of wrapping around to the other side.
+    pub fn get_next_monitor_with_wrap(
+        &self,
+        current_monitor: &MonitorIndex,
+        direction: &FocusDirection,
+        wrap: bool,
+    ) -> Option<MonitorIndex> {
+        let Some((column, row)) = self.get_monitor_coordinates(current_monitor) else {
+            return Some(current_monitor.clone());
+        };
+
+        let step = |index: usize, count: usize| {
+            let next_index = index as i32 + direction.to_int();
+
+            if wrap {
+                Some(wrap_index(next_index, count as i32))
+            } else {
+                usize::try_from(next_index)
+                    .ok()
+                    .filter(|next_index| *next_index < count)
+            }
+        };
+
+        let (next_column, next_row) = if direction.is_horizontal() {
+            let next_column = step(column, self.0.len())?;
+
+            // When the next column is shorter than the current one, land on its bottom-most monitor.
+            let next_row = row.min(self.0[next_column].len().saturating_sub(1));
+
+            (next_column, next_row)
+        } else {
+            (column, step(row, self.0[column].len())?)
+        };
+
+        Some(self.get_monitor_index(next_column, next_row))
+    }
+
+    /// Converts a monitor's (flat) index into its (column, row) coordinates within the grid.
+    ///
+    /// Monitors are indexed column by column, from top to bottom within each column.
+    pub fn get_monitor_coordinates(&self, monitor: &MonitorIndex) -> Option<(usize, usize)> {
+        let mut remaining = monitor.0;
+
+        for (column_index, column) in self.0.iter().enumerate() {
+            if remaining < column.len() {
+                return Some((column_index, remaining));
+            }
+
+            remaining -= column.len();
+        }
+
+        None
+    }
 
+    /// Converts (column, row) coordinates within the grid into the monitor's (flat) index.
+    pub fn get_monitor_index(&self, column: usize, row: usize) -> MonitorIndex {
         MonitorIndex(
-            // Need to do this "multiple module operations" song and dance to get the modulo behavior we want.
-            // Otherwise, we can get a negative remainder.
-            //
-            // Ref: https://stackoverflow.com/q/31210357
-            ((((current_monitor.0 as i32 + direction.to_int()) % monitors_count) + monitors_count)
-                % monitors_count) as usize,
+            self.0
+                .iter()
+                .take(column)
+                .map(|column| column.len())
+                .sum::<usize>()
+                + row,
+        )
+    }
+
+    /// Calculates the position of the monitor's top-left corner within the workspace.
+    ///
+    /// Like `determine_which_monitor_window_is_on`, columns are laid out next to each other based on their widest monitor
+    /// and the monitors within a column are stacked on top of each other.
+    pub fn get_monitor_origin(&self, monitor: &MonitorIndex) -> Option<(i32, i32)> {
+        let (column, row) = self.get_monitor_coordinates(monitor)?;
+
+        let x = self.0[..column]
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|monitor| monitor.scaled_width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .sum();
+
+        let y = self.0[column][..row]
+            .iter()
+            .map(|monitor| monitor.scaled_height())
+            .sum();
+
+        Some((x, y))
+    }
+
+    /// Finds the index of the primary monitor, if there is one.
+    pub fn get_primary_monitor(&self) -> Option<MonitorIndex> {
+        self.0
+            .iter()
+            .flatten()
+            .position(|monitor| monitor.primary)
+            .map(MonitorIndex)
+    }
+
+    /// Finds the index of the monitor with the given xrandr output name (e.g. `DisplayPort-0`)
+    /// or EDID-based identity (e.g. `GSM-5B09-0001E2B4`).
+    pub fn index_of_name(&self, name: &str) -> Option<MonitorIndex> {
+        self.0
+            .iter()
+            .flatten()
+            .position(|monitor| {
+                monitor.name.as_deref() == Some(name) || monitor.identity.as_deref() == Some(name)
+            })
+            .map(MonitorIndex)
+    }
+
+    /// Fills in the identities of the monitors from the given identities, keyed by output name.
+    pub fn with_identities(&self, identities: &HashMap<String, String>) -> MonitorGrid {
+        MonitorGrid(
+            self.0
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|monitor| Monitor {
+                            identity: monitor
+                                .name
+                                .as_ref()
+                                .and_then(|name| identities.get(name))
+                                .cloned()
+                                .or_else(|| monitor.identity.clone()),
+                            ..monitor.clone()
+                        })
+                        .collect()
+                })
+                .collect(),
         )
     }
 
+    /// Applies the scale factors to the monitors with the given output names (or identities).
+    pub fn with_scales(&self, scales: &HashMap<String, f64>) -> MonitorGrid {
+        MonitorGrid(
+            self.0
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|monitor| Monitor {
+                            scale: [monitor.name.as_ref(), monitor.identity.as_ref()]
+                                .into_iter()
+                                .flatten()
+                                .find_map(|name| scales.get(name))
+                                .copied()
+                                .unwrap_or(monitor.scale),
+                            ..monitor.clone()
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Reorders the columns to match the given order of monitors (by output name or identity), for when the
+    /// left-to-right order that's detected from the monitors' positions doesn't match how they're actually arranged.
+    ///
+    /// Each name picks out the column of the monitor with that name; the columns that aren't picked out keep their
+    /// detected order after the ones that are. Errors if a name doesn't match any monitor or picks out a column twice.
+    pub fn reorder_columns(&self, order: &[String]) -> Result<MonitorGrid> {
+        let mut column_order: Vec<usize> = Vec::new();
+
+        for name in order {
+            let column = self
+                .index_of_name(name)
+                .and_then(|monitor| self.get_monitor_coordinates(&monitor))
+                .map(|(column, _)| column)
+                .ok_or_else(|| anyhow::anyhow!("Unknown monitor in monitor order: {name}"))?;
+
+            if column_order.contains(&column) {
+                return Err(anyhow::anyhow!(
+                    "Monitor order lists the column of monitor {name} more than once"
+                ));
+            }
+
+            column_order.push(column);
+        }
+
+        let remaining_columns = (0..self.0.len())
+            .filter(|column| !column_order.contains(column))
+            .collect::<Vec<usize>>();
+        column_order.extend(remaining_columns);
+
+        Ok(MonitorGrid(
+            column_order
+                .into_iter()
+                .map(|column| self.0[column].clone())
+                .collect(),
+        ))
+    }
+
+    /// Resolves the selector to a monitor index, erroring (with the available names) if there's no monitor with the name.
+    ///
+    /// Indices are passed through as-is; whether there's actually a monitor with the index is checked when focusing it.
+    pub fn resolve(&self, selector: &MonitorSelector) -> Result<MonitorIndex> {
+        match selector {
+            MonitorSelector::Index(index) => Ok(index.clone()),
+            MonitorSelector::Name(name) => self.index_of_name(name).ok_or_else(|| {
+                let available_names = self
+                    .0
+                    .iter()
+                    .flatten()
+                    .flat_map(|monitor| [monitor.name.as_deref(), monitor.identity.as_deref()])
+                    .flatten()
+                    .collect::<Vec<&str>>();
+
+                anyhow::anyhow!(
+                    "Unknown monitor: {name} (available: {})",
+                    if available_names.is_empty() {
+                        "none".to_owned()
+                    } else {
+                        available_names.join(", ")
+                    }
+                )
+            }),
+        }
+    }
+
+    /// Calculates the monitor's bounds within the workspace as `(x, y, width, height)`.
+    pub fn get_monitor_bounds(&self, monitor: &MonitorIndex) -> Option<(i32, i32, i32, i32)> {
+        let (x, y) = self.get_monitor_origin(monitor)?;
+        let (column, row) = self.get_monitor_coordinates(monitor)?;
+        let monitor = &self.0[column][row];
+
+        Some((x, y, monitor.scaled_width(), monitor.scaled_height()))
+    }
+
+    /// Converts a point within the workspace into the monitor that it's on and its offset from that monitor's top-left
+    /// corner, erroring if the point isn't on any monitor (e.g. below a shorter monitor).
+    pub fn to_monitor_local(&self, x: i32, y: i32) -> Result<(MonitorIndex, i32, i32)> {
+        (0..self.calculate_monitor_count() as usize)
+            .map(MonitorIndex)
+            .find_map(|monitor| {
+                let (monitor_x, monitor_y, width, height) = self.get_monitor_bounds(&monitor)?;
+
+                let is_on_monitor = (monitor_x..monitor_x + width).contains(&x)
+                    && (monitor_y..monitor_y + height).contains(&y);
+
+                is_on_monitor.then_some((monitor, x - monitor_x, y - monitor_y))
+            })
+            .ok_or_else(|| anyhow::anyhow!("Point ({x}, {y}) isn't on any monitor"))
+    }
+
+    /// Converts an offset from the monitor's top-left corner into a point within the workspace; the inverse of
+    /// `to_monitor_local`. Errors if there's no monitor with the index.
+    pub fn from_monitor_local(&self, monitor: &MonitorIndex, x: i32, y: i32) -> Result<(i32, i32)> {
+        let (monitor_x, monitor_y) = self
+            .get_monitor_origin(monitor)
+            .ok_or_else(|| anyhow::anyhow!("Invalid monitor index: {}", monitor.0))?;
+
+        Ok((monitor_x + x, monitor_y + y))
+    }
+
     /// Given a window (with its position via the x and y offsets), determines which monitor it is on within the grid.
     ///
     /// The algorithm intuitively works follows: for each monitor, check if the window's x/y offsets shows that it's within the bounds of the monitor's size.
     /// Calculate this by accumulating the width of all previous monitors as each column is checked, and similarly with the height of all previous monitors as each column is checked.
+    ///
+    /// Errors if the window's geometry isn't known (see `Window::has_geometry`), since there's no position to go by.
     pub fn determine_which_monitor_window_is_on(&self, window: &Window) -> Result<MonitorIndex> {
-        // This is the index of the monitor that the monitor is on (0-indexed).
-        // Start it at negative one since each loop through the monitors will increment it by one.
-        let mut monitor_index: i32 = -1;
+        if !window.has_geometry() {
+            return Err(anyhow::anyhow!(
+                "The position of window {} isn't known (wmctrl doesn't support -G); this command needs window positions",
+                window.id
+            ));
+        }
 
         // This is the accumulated current x position after processing each monitor.
         // Each column of monitors will have its width added to this (the widest monitor of each column only).
         let mut x_position = 0;
 
-        for column in &self.0 {
-            monitor_index += 1;
-
+        for (column_index, column) in self.0.iter().enumerate() {
             // This is the accumulated current y position after processing each monitor in the current column.
             // Because of how the grid is represented (rows then columns), this value only needs to be accumulated once per column.
             //
-            // Start it with negative WINDOW_DECORATION so that we don't have to subtract it out later.
-            let mut y_position = -(WINDOW_DECORATION);
+            // Start it with the negative window decoration so that we don't have to subtract it out later.
+            let mut y_position = -window.decoration_height();
 
             // Tracks which monitors in the current column has the greatest width, so that we can calculate x_position for the next column correctly.
             let mut greatest_column_width = 0;
@@ -52,17 +337,14 @@ impl MonitorGrid {
             let base_x_position = x_position;
 
             for (row_index, monitor) in column.iter().enumerate() {
-                // Add the current row in the column to the index.
-                //
-                // Note: Adding 0 for the first index in a column is intentional, since it's handled by the increment that happens in the column loop above.
-                monitor_index += row_index as i32;
-
                 // Accumulate the current column's y position based on the monitor's height.
-                y_position += monitor.height;
+                //
+                // Window offsets are in the (possibly scaled) global coordinate space, so compare against the scaled sizes.
+                y_position += monitor.scaled_height();
 
-                if monitor.width > greatest_column_width {
+                if monitor.scaled_width() > greatest_column_width {
                     // Update the greatest width if the current monitor is wider than the last one in the column.
-                    greatest_column_width = monitor.width;
+                    greatest_column_width = monitor.scaled_width();
 
                     // Also update the overall x_position based on the new greatest width.
                     x_position = base_x_position + greatest_column_width;
@@ -74,7 +356,7 @@ impl MonitorGrid {
                 // each monitor _in order_. If we weren't doing it in order, we wouldn't be able to ignore previous monitors and would have to
                 // do a bounds check based on each monitor's dimensions.
                 if window.x_offset < x_position && window.y_offset < y_position {
-                    return Ok(MonitorIndex(monitor_index as usize));
+                    return Ok(self.get_monitor_index(column_index, row_index));
                 }
             }
         }
@@ -86,22 +368,280 @@ impl MonitorGrid {
         ))
     }
 
-    fn calculate_monitor_count(&self) -> i32 {
+    /// Finds the monitor whose bounds are closest to the window's position, for windows that
+    /// `determine_which_monitor_window_is_on` can't place (e.g. ones just past the edge of the workspace).
+    ///
+    /// A window that's within a monitor's bounds is on that monitor; ties go to the last (i.e. rightmost) monitor.
+    pub fn find_nearest_monitor(&self, window: &Window) -> Option<MonitorIndex> {
+        let distance_to = |(x, y, width, height): (i32, i32, i32, i32)| {
+            let x_distance = (x - window.x_offset)
+                .max(window.x_offset - (x + width - 1))
+                .max(0) as i64;
+            let y_distance = (y - window.y_offset)
+                .max(window.y_offset - (y + height - 1))
+                .max(0) as i64;
+
+            x_distance * x_distance + y_distance * y_distance
+        };
+
+        (0..self.calculate_monitor_count() as usize)
+            .rev()
+            .map(MonitorIndex)
+            .filter_map(|monitor| Some((self.get_monitor_bounds(&monitor)?, monitor)))
+            .min_by_key(|(bounds, _)| distance_to(*bounds))
+            .map(|(_, monitor)| monitor)
+    }
+
+    /// Merges the monitors of each column into a single logical monitor, as wide as the column's widest monitor
+    /// and as tall as all of its monitors combined.
+    ///
+    /// This way, navigation treats a stack of monitors as a single unit: Left/Right skip over the whole stack
+    /// and Up/Down never move between its monitors.
+    pub fn merge_stacked_monitors(&self) -> MonitorGrid {
+        MonitorGrid(
+            self.0
+                .iter()
+                .filter(|column| !column.is_empty())
+                .map(|column| {
+                    let names = column
+                        .iter()
+                        .map(|monitor| monitor.name.as_deref())
+                        .collect::<Option<Vec<&str>>>();
+
+                    // The merged monitor's size is already in the windows' coordinate space, so it isn't scaled again.
+                    vec![Monitor {
+                        width: column
+                            .iter()
+                            .map(|monitor| monitor.scaled_width())
+                            .max()
+                            .unwrap_or(0),
+                        height: column.iter().map(|monitor| monitor.scaled_height()).sum(),
+                        scale: 1.0,
+                        // A stack of monitors doesn't have a single physical size.
+                        width_mm: match column.as_slice() {
+                            [monitor] => monitor.width_mm,
+                            _ => None,
+                        },
+                        height_mm: match column.as_slice() {
+                            [monitor] => monitor.height_mm,
+                            _ => None,
+                        },
+                        primary: column.iter().any(|monitor| monitor.primary),
+                        name: names.map(|names| names.join("+")),
+                        identity: match column.as_slice() {
+                            [monitor] => monitor.identity.clone(),
+                            _ => None,
+                        },
+                    }]
+                })
+                .collect(),
+        )
+    }
+
+    /// Finds the closest monitor (that passes `is_candidate`) to the given one along the line that runs across the
+    /// direction: within its column for Left/Right, and within its row for Up/Down. Ties go to the monitor that's
+    /// higher up or further left.
+    pub fn find_nearest_across(
+        &self,
+        monitor: &MonitorIndex,
+        direction: &FocusDirection,
+        is_candidate: impl Fn(&MonitorIndex) -> bool,
+    ) -> Option<MonitorIndex> {
+        let (column, row) = self.get_monitor_coordinates(monitor)?;
+
+        let mut candidates = if direction.is_horizontal() {
+            (0..self.0[column].len())
+                .filter(|other_row| *other_row != row)
+                .map(|other_row| (other_row.abs_diff(row), other_row, column, other_row))
+                .collect::<Vec<(usize, usize, usize, usize)>>()
+        } else {
+            (0..self.0.len())
+                .filter(|other_column| *other_column != column && row < self.0[*other_column].len())
+                .map(|other_column| {
+                    (
+                        other_column.abs_diff(column),
+                        other_column,
+                        other_column,
+                        row,
+                    )
+                })
+                .collect::<Vec<(usize, usize, usize, usize)>>()
+        };
+
+        candidates.sort();
+
+        candidates
+            .into_iter()
+            .map(|(_, _, column, row)| self.get_monitor_index(column, row))
+            .find(|candidate| is_candidate(candidate))
+    }
+
+    /// Combines the grids of separate X screens (e.g. `:0.0` and `:0.1`) into a single grid, placing each screen's
+    /// monitors to the right of the previous screen's.
+    ///
+    /// Each screen can have its own primary monitor, so only the first screen's primary monitor is kept as primary.
+    pub fn merge_screens(grids: Vec<MonitorGrid>) -> MonitorGrid {
+        let mut has_primary = false;
+
+        MonitorGrid(
+            grids
+                .into_iter()
+                .flat_map(|grid| grid.0)
+                .map(|column| {
+                    column
+                        .into_iter()
+                        .map(|monitor| {
+                            let primary = monitor.primary && !has_primary;
+                            has_primary |= primary;
+
+                            Monitor { primary, ..monitor }
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn calculate_monitor_count(&self) -> i32 {
         self.0
             .iter()
             .fold(0, |acc, column| acc + column.len() as i32)
     }
 }
 
+/// Wraps the given (possibly negative) index around so that it is always within `0..count`.
+fn wrap_index(index: i32, count: i32) -> usize {
+    // Need to do this "multiple module operations" song and dance to get the modulo behavior we want.
+    // Otherwise, we can get a negative remainder.
+    //
+    // Ref: https://stackoverflow.com/q/31210357
+    (((index % count) + count) % count) as usize
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    mod determine_which_monitor_window_is_on {
+    mod new {
         use super::*;
 
+        #[test]
+        fn test_quad_layout() {
+            let columns = vec![
+                vec![
+                    Monitor::new(1920, 1080).with_name("DisplayPort-2"),
+                    Monitor::new(1920, 1080)
+                        .with_name("HDMI-A-0")
+                        .with_primary(true),
+                ],
+                vec![Monitor::new(3440, 1440).with_name("DisplayPort-0")],
+                vec![Monitor::new(1440, 2560)
+                    .with_name("DisplayPort-1")
+                    .with_scale(1.25)],
+            ];
+
+            let monitor_grid = MonitorGrid::new(columns.clone()).unwrap();
+
+            assert_eq!(monitor_grid.0, columns);
+            assert_eq!(monitor_grid.calculate_monitor_count(), 4);
+            assert_eq!(monitor_grid.get_primary_monitor(), Some(MonitorIndex(1)));
+            assert_eq!(
+                monitor_grid.index_of_name("DisplayPort-1"),
+                Some(MonitorIndex(3))
+            );
+        }
+
+        #[test]
+        fn test_empty_column() {
+            let result = MonitorGrid::new(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            assert_eq!(
+                result.err().unwrap().to_string(),
+                "Invalid monitor grid: column 1 has no monitors"
+            );
+        }
+
+        #[test]
+        fn test_no_monitors() {
+            assert_eq!(
+                MonitorGrid::new(vec![]).err().unwrap().to_string(),
+                "No active monitors detected"
+            );
+        }
+    }
+
+    mod find_nearest_monitor {
+        use super::*;
         use crate::models::WindowId;
 
+        fn create_mock_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        fn get_result(x_offset: i32, y_offset: i32) -> Option<MonitorIndex> {
+            let window = Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            create_mock_grid().find_nearest_monitor(&window)
+        }
+
+        #[test]
+        fn test_just_past_right_edge() {
+            // The workspace is 1920 + 3440 + 1440 = 6800 pixels wide.
+            assert_eq!(get_result(6800, 24), Some(MonitorIndex(3)));
+            assert_eq!(get_result(6850, 2000), Some(MonitorIndex(3)));
+        }
+
+        #[test]
+        fn test_below_shorter_monitor() {
+            // The ultrawide is only 1440 pixels tall, while its neighbours are 2160 and 2560 pixels tall.
+            assert_eq!(get_result(3000, 2000), Some(MonitorIndex(2)));
+            assert_eq!(get_result(1950, 2000), Some(MonitorIndex(1)));
+        }
+
+        #[test]
+        fn test_within_monitor() {
+            assert_eq!(get_result(100, 100), Some(MonitorIndex(0)));
+            assert_eq!(get_result(100, 1200), Some(MonitorIndex(1)));
+            assert_eq!(get_result(6000, 2000), Some(MonitorIndex(3)));
+        }
+
+        #[test]
+        fn test_no_monitors() {
+            let window = Window::new(
+                WindowId(1),
+                0,
+                0,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            assert_eq!(MonitorGrid(vec![]).find_nearest_monitor(&window), None);
+        }
+    }
+
+    mod determine_which_monitor_window_is_on {
+        use super::*;
+
+        use crate::models::{WindowId, UNKNOWN_GEOMETRY};
+
         fn create_mock_window(x_offset: i32, y_offset: i32) -> Window {
             // Only values that matter are the offsets; everything else can be arbitrary.
             Window {
@@ -112,9 +652,80 @@ mod tests {
                 height: 1056,
                 window_class: "chrome".to_string(),
                 title: "Chrome".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            }
+        }
+
+        #[test]
+        fn test_unknown_geometry() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            let window = Window {
+                width: UNKNOWN_GEOMETRY,
+                height: UNKNOWN_GEOMETRY,
+                ..create_mock_window(UNKNOWN_GEOMETRY, UNKNOWN_GEOMETRY)
+            };
+
+            let error = grid
+                .determine_which_monitor_window_is_on(&window)
+                .unwrap_err();
+            assert!(error.to_string().contains("isn't known"));
+        }
+
+        #[test]
+        fn test_scaled_monitor() {
+            // A 1920x1080 monitor that's scaled 2x takes up 3840x2160 of the windows' coordinate space,
+            // so the 1440x2560 monitor next to it starts at x = 3840 rather than 1920.
+            let grid = MonitorGrid(vec![
+                vec![Monitor {
+                    scale: 2.0,
+                    ..Monitor::new(1920, 1080)
+                }],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            for (x_offset, y_offset, expected) in [
+                (0, 24, 0),
+                (1920, 24, 0),
+                (3000, 1500, 0),
+                (3839, 24, 0),
+                (3840, 24, 1),
+                (5000, 2000, 1),
+            ] {
+                assert_eq!(
+                    grid.determine_which_monitor_window_is_on(&create_mock_window(
+                        x_offset, y_offset
+                    ))
+                    .unwrap(),
+                    MonitorIndex(expected),
+                    "window at {x_offset},{y_offset}"
+                );
             }
         }
 
+        #[test]
+        fn test_scaled_stacked_monitor() {
+            let grid = MonitorGrid(vec![vec![
+                Monitor {
+                    scale: 2.0,
+                    ..Monitor::new(1920, 1080)
+                },
+                Monitor::new(1920, 1080),
+            ]]);
+
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&create_mock_window(0, 2000))
+                    .unwrap(),
+                MonitorIndex(0)
+            );
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&create_mock_window(0, 2200))
+                    .unwrap(),
+                MonitorIndex(1)
+            );
+        }
+
         fn create_mock_grid() -> MonitorGrid {
             MonitorGrid(vec![
                 vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
@@ -167,6 +778,53 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_three_stacked_monitors() {
+            let grid = MonitorGrid(vec![
+                vec![
+                    Monitor::new(1920, 1080),
+                    Monitor::new(1920, 1080),
+                    Monitor::new(1920, 1080),
+                ],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            for (y_offset, expected) in [(0, 0), (1500, 1), (2500, 2)] {
+                let monitor = grid
+                    .determine_which_monitor_window_is_on(&create_mock_window(0, y_offset))
+                    .unwrap();
+
+                assert_eq!(monitor, MonitorIndex(expected), "window at y {y_offset}");
+                assert_eq!(grid.get_monitor_coordinates(&monitor), Some((0, expected)));
+            }
+
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&create_mock_window(2000, 0))
+                    .unwrap(),
+                MonitorIndex(3)
+            );
+        }
+
+        #[test]
+        fn test_uses_window_frame_top() {
+            let grid = create_mock_grid();
+
+            // With the default decoration, a window this close to the bottom of the top-left monitor
+            // is considered to be on the monitor below it.
+            let mut window = create_mock_window(0, 1070);
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&window).unwrap(),
+                MonitorIndex(1)
+            );
+
+            // Whereas a window without any decoration is still on the top-left monitor.
+            window.frame_top = Some(0);
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&window).unwrap(),
+                MonitorIndex(0)
+            );
+        }
+
         #[test]
         fn test_invalid_monitor() {
             let window = create_mock_window(100000, 0);
@@ -179,6 +837,10 @@ mod tests {
     mod get_next_monitor {
         use super::*;
 
+        // Monitor layout (indices):
+        //
+        // [0] [2] [3]
+        // [1]
         fn create_mock_grid() -> MonitorGrid {
             MonitorGrid(vec![
                 vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
@@ -187,63 +849,864 @@ mod tests {
             ])
         }
 
+        fn get_result(current: usize, direction: FocusDirection) -> MonitorIndex {
+            create_mock_grid()
+                .get_next_monitor(&MonitorIndex(current), &direction)
+                .unwrap()
+        }
+
         #[test]
-        fn test_next_monitor_right() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(0);
-            let next = grid.get_next_monitor(&current, &FocusDirection::Right);
-            assert_eq!(next, MonitorIndex(1));
+        fn test_empty_grid() {
+            let grid = MonitorGrid(vec![]);
+
+            for direction in [
+                FocusDirection::Left,
+                FocusDirection::Right,
+                FocusDirection::Up,
+                FocusDirection::Down,
+            ] {
+                assert_eq!(
+                    grid.get_next_monitor(&MonitorIndex(0), &direction)
+                        .unwrap_err()
+                        .to_string(),
+                    "No active monitors detected"
+                );
+                assert_eq!(
+                    grid.get_next_monitor_with_wrap(&MonitorIndex(0), &direction, false),
+                    Some(MonitorIndex(0))
+                );
+            }
         }
 
         #[test]
-        fn test_next_monitor_left() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(1);
-            let next = grid.get_next_monitor(&current, &FocusDirection::Left);
-            assert_eq!(next, MonitorIndex(0));
+        fn test_top_left_corner() {
+            assert_eq!(get_result(0, FocusDirection::Left), MonitorIndex(3));
+            assert_eq!(get_result(0, FocusDirection::Right), MonitorIndex(2));
+            assert_eq!(get_result(0, FocusDirection::Up), MonitorIndex(1));
+            assert_eq!(get_result(0, FocusDirection::Down), MonitorIndex(1));
         }
 
         #[test]
-        fn test_wrap_around_right() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(3); // Last monitor
-            let next = grid.get_next_monitor(&current, &FocusDirection::Right);
-            assert_eq!(next, MonitorIndex(0)); // Should wrap to first
+        fn test_bottom_left_corner() {
+            assert_eq!(get_result(1, FocusDirection::Left), MonitorIndex(3));
+            assert_eq!(get_result(1, FocusDirection::Right), MonitorIndex(2));
+            assert_eq!(get_result(1, FocusDirection::Up), MonitorIndex(0));
+            assert_eq!(get_result(1, FocusDirection::Down), MonitorIndex(0));
         }
 
         #[test]
-        fn test_wrap_around_left() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(0); // First monitor
-            let next = grid.get_next_monitor(&current, &FocusDirection::Left);
-            assert_eq!(next, MonitorIndex(3)); // Should wrap to last
+        fn test_center_monitor() {
+            assert_eq!(get_result(2, FocusDirection::Left), MonitorIndex(0));
+            assert_eq!(get_result(2, FocusDirection::Right), MonitorIndex(3));
+            assert_eq!(get_result(2, FocusDirection::Up), MonitorIndex(2));
+            assert_eq!(get_result(2, FocusDirection::Down), MonitorIndex(2));
+        }
+
+        #[test]
+        fn test_right_corner() {
+            assert_eq!(get_result(3, FocusDirection::Left), MonitorIndex(2));
+            assert_eq!(get_result(3, FocusDirection::Right), MonitorIndex(0));
+            assert_eq!(get_result(3, FocusDirection::Up), MonitorIndex(3));
+            assert_eq!(get_result(3, FocusDirection::Down), MonitorIndex(3));
+        }
+
+        #[test]
+        fn test_keeps_row_between_stacked_columns() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+            ]);
+
+            assert_eq!(
+                grid.get_next_monitor(&MonitorIndex(1), &FocusDirection::Right)
+                    .unwrap(),
+                MonitorIndex(3)
+            );
+
+            assert_eq!(
+                grid.get_next_monitor(&MonitorIndex(2), &FocusDirection::Left)
+                    .unwrap(),
+                MonitorIndex(0)
+            );
         }
 
         #[test]
         fn test_single_monitor() {
             let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
             let current = MonitorIndex(0);
-            let next_right = grid.get_next_monitor(&current, &FocusDirection::Right);
-            let next_left = grid.get_next_monitor(&current, &FocusDirection::Left);
 
-            assert_eq!(next_right, MonitorIndex(0));
-            assert_eq!(next_left, MonitorIndex(0));
+            for direction in [
+                FocusDirection::Left,
+                FocusDirection::Right,
+                FocusDirection::Up,
+                FocusDirection::Down,
+            ] {
+                assert_eq!(
+                    grid.get_next_monitor(&current, &direction).unwrap(),
+                    MonitorIndex(0)
+                );
+            }
         }
+    }
 
-        #[test]
-        fn test_middle_monitor_right() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(2);
-            let next = grid.get_next_monitor(&current, &FocusDirection::Right);
-            assert_eq!(next, MonitorIndex(3));
-        }
+    mod get_next_monitor_with_wrap {
+        use super::*;
 
-        #[test]
-        fn test_middle_monitor_left() {
-            let grid = create_mock_grid();
-            let current = MonitorIndex(2);
-            let next = grid.get_next_monitor(&current, &FocusDirection::Left);
-            assert_eq!(next, MonitorIndex(1));
+        // Monitor layout (indices):
+        //
+        // [0] [2] [3]
+        // [1]
+        fn get_result(
+            current: usize,
+            direction: FocusDirection,
+            wrap: bool,
+        ) -> Option<MonitorIndex> {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+            .get_next_monitor_with_wrap(&MonitorIndex(current), &direction, wrap)
+        }
+
+        #[test]
+        fn test_wrap() {
+            assert_eq!(
+                get_result(0, FocusDirection::Left, true),
+                Some(MonitorIndex(3))
+            );
+            assert_eq!(
+                get_result(3, FocusDirection::Right, true),
+                Some(MonitorIndex(0))
+            );
+            assert_eq!(
+                get_result(1, FocusDirection::Down, true),
+                Some(MonitorIndex(0))
+            );
+            assert_eq!(
+                get_result(2, FocusDirection::Up, true),
+                Some(MonitorIndex(2))
+            );
+        }
+
+        #[test]
+        fn test_no_wrap_at_edges() {
+            assert_eq!(get_result(0, FocusDirection::Left, false), None);
+            assert_eq!(get_result(3, FocusDirection::Right, false), None);
+            assert_eq!(get_result(0, FocusDirection::Up, false), None);
+            assert_eq!(get_result(1, FocusDirection::Down, false), None);
+            assert_eq!(get_result(2, FocusDirection::Up, false), None);
+        }
+
+        #[test]
+        fn test_no_wrap_inside_grid() {
+            assert_eq!(
+                get_result(1, FocusDirection::Right, false),
+                Some(MonitorIndex(2))
+            );
+            assert_eq!(
+                get_result(3, FocusDirection::Left, false),
+                Some(MonitorIndex(2))
+            );
+            assert_eq!(
+                get_result(0, FocusDirection::Down, false),
+                Some(MonitorIndex(1))
+            );
+            assert_eq!(
+                get_result(1, FocusDirection::Up, false),
+                Some(MonitorIndex(0))
+            );
+        }
+    }
+
+    mod get_monitor_origin {
+        use super::*;
+
+        #[test]
+        fn test_quad_monitor_setup() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(0)), Some((0, 0)));
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(1)), Some((0, 1080)));
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(2)), Some((1920, 0)));
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(3)), Some((5360, 0)));
+        }
+
+        #[test]
+        fn test_uses_widest_monitor_of_column() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(2560, 1440)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(2)), Some((2560, 0)));
+        }
+
+        #[test]
+        fn test_scaled_monitor() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor {
+                    scale: 2.0,
+                    ..Monitor::new(1920, 1080)
+                }],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(0)),
+                Some((0, 0, 3840, 2160))
+            );
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(1)),
+                Some((3840, 0, 1440, 2560))
+            );
+        }
+
+        #[test]
+        fn test_out_of_range() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(1)), None);
+        }
+    }
+
+    mod merge_stacked_monitors {
+        use super::*;
+
+        use crate::models::WindowId;
+
+        // The quad monitor layout from the README:
+        //
+        // [0] [2] [3]
+        // [1]
+        fn create_quad_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![
+                    Monitor {
+                        name: Some("DisplayPort-2".to_owned()),
+                        ..Monitor::new(1920, 1080)
+                    },
+                    Monitor {
+                        name: Some("HDMI-A-0".to_owned()),
+                        ..Monitor::new_primary(1920, 1080)
+                    },
+                ],
+                vec![Monitor {
+                    name: Some("DisplayPort-0".to_owned()),
+                    ..Monitor::new(3440, 1440)
+                }],
+                vec![Monitor {
+                    name: Some("DisplayPort-1".to_owned()),
+                    ..Monitor::new(1440, 2560)
+                }],
+            ])
+        }
+
+        #[test]
+        fn test_quad_monitor_setup() {
+            let grid = create_quad_grid().merge_stacked_monitors();
+
+            assert_eq!(grid.calculate_monitor_count(), 3);
+            assert_eq!(
+                grid.0,
+                vec![
+                    vec![Monitor {
+                        name: Some("DisplayPort-2+HDMI-A-0".to_owned()),
+                        ..Monitor::new_primary(1920, 2160)
+                    }],
+                    vec![Monitor {
+                        name: Some("DisplayPort-0".to_owned()),
+                        ..Monitor::new(3440, 1440)
+                    }],
+                    vec![Monitor {
+                        name: Some("DisplayPort-1".to_owned()),
+                        ..Monitor::new(1440, 2560)
+                    }],
+                ]
+            );
+        }
+
+        #[test]
+        fn test_quad_monitor_setup_navigation() {
+            let grid = create_quad_grid().merge_stacked_monitors();
+
+            assert_eq!(
+                grid.get_next_monitor(&MonitorIndex(0), &FocusDirection::Right)
+                    .unwrap(),
+                MonitorIndex(1)
+            );
+            assert_eq!(
+                grid.get_next_monitor(&MonitorIndex(1), &FocusDirection::Left)
+                    .unwrap(),
+                MonitorIndex(0)
+            );
+            assert_eq!(
+                grid.get_next_monitor(&MonitorIndex(0), &FocusDirection::Down)
+                    .unwrap(),
+                MonitorIndex(0)
+            );
+        }
+
+        #[test]
+        fn test_quad_monitor_setup_window_on_bottom_monitor() {
+            let grid = create_quad_grid().merge_stacked_monitors();
+            let window = Window::new(
+                WindowId(1),
+                100,
+                1500,
+                800,
+                400,
+                "app".to_owned(),
+                "App".to_owned(),
+            );
+
+            assert_eq!(
+                grid.determine_which_monitor_window_is_on(&window).unwrap(),
+                MonitorIndex(0)
+            );
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(1)),
+                Some((1920, 0, 3440, 1440))
+            );
+        }
+
+        #[test]
+        fn test_unnamed_monitors() {
+            let grid = MonitorGrid(vec![vec![
+                Monitor::new(1920, 1080),
+                Monitor {
+                    name: Some("HDMI-A-0".to_owned()),
+                    ..Monitor::new(1920, 1080)
+                },
+            ]])
+            .merge_stacked_monitors();
+
+            assert_eq!(grid.0, vec![vec![Monitor::new(1920, 2160)]]);
+        }
+    }
+
+    mod find_nearest_across {
+        use super::*;
+
+        // [0] [2] [4]
+        // [1] [3]
+        fn create_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ])
+        }
+
+        fn get_result(
+            monitor: usize,
+            direction: FocusDirection,
+            candidates: &[usize],
+        ) -> Option<usize> {
+            create_grid()
+                .find_nearest_across(&MonitorIndex(monitor), &direction, |monitor| {
+                    candidates.contains(&monitor.0)
+                })
+                .map(|monitor| monitor.0)
+        }
+
+        #[test]
+        fn test_horizontal_searches_column() {
+            assert_eq!(get_result(2, FocusDirection::Right, &[0, 1, 3, 4]), Some(3));
+            assert_eq!(get_result(3, FocusDirection::Left, &[0, 1, 2, 4]), Some(2));
+            assert_eq!(get_result(2, FocusDirection::Right, &[0, 1, 4]), None);
+            assert_eq!(get_result(4, FocusDirection::Right, &[0, 1, 2, 3]), None);
+        }
+
+        #[test]
+        fn test_vertical_searches_row() {
+            assert_eq!(get_result(2, FocusDirection::Up, &[0, 1, 3, 4]), Some(0));
+            assert_eq!(get_result(2, FocusDirection::Up, &[1, 3, 4]), Some(4));
+            assert_eq!(get_result(3, FocusDirection::Down, &[0, 1, 2, 4]), Some(1));
+        }
+
+        #[test]
+        fn test_nearest_first() {
+            assert_eq!(get_result(0, FocusDirection::Down, &[2, 4]), Some(2));
+            assert_eq!(get_result(0, FocusDirection::Down, &[4]), Some(4));
+        }
+    }
+
+    mod merge_screens {
+        use super::*;
+
+        #[test]
+        fn test_two_screens() {
+            let first = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new_primary(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+            ]);
+            let second = MonitorGrid(vec![vec![Monitor::new_primary(2560, 1440)]]);
+
+            let grid = MonitorGrid::merge_screens(vec![first, second]);
+
+            assert_eq!(
+                grid.0,
+                vec![
+                    vec![Monitor::new(1920, 1080), Monitor::new_primary(1920, 1080)],
+                    vec![Monitor::new(3440, 1440)],
+                    vec![Monitor::new(2560, 1440)],
+                ]
+            );
+            assert_eq!(grid.calculate_monitor_count(), 4);
+            assert_eq!(grid.get_primary_monitor(), Some(MonitorIndex(1)));
+            assert_eq!(grid.get_monitor_origin(&MonitorIndex(3)), Some((5360, 0)));
+        }
+
+        #[test]
+        fn test_primary_on_second_screen() {
+            let first = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            let second = MonitorGrid(vec![vec![Monitor::new_primary(2560, 1440)]]);
+
+            let grid = MonitorGrid::merge_screens(vec![first, second]);
+
+            assert_eq!(grid.get_primary_monitor(), Some(MonitorIndex(1)));
+        }
+
+        #[test]
+        fn test_single_screen() {
+            let grid = MonitorGrid(vec![vec![Monitor::new_primary(1920, 1080)]]);
+
+            assert_eq!(MonitorGrid::merge_screens(vec![grid.clone()]).0, grid.0);
+        }
+    }
+
+    mod get_primary_monitor {
+        use super::*;
+
+        #[test]
+        fn test_primary_monitor() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new_primary(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+            ]);
+
+            assert_eq!(grid.get_primary_monitor(), Some(MonitorIndex(1)));
+        }
+
+        #[test]
+        fn test_no_primary_monitor() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(grid.get_primary_monitor(), None);
+        }
+    }
+
+    mod with_scales {
+        use super::*;
+
+        fn create_named_monitor(name: &str, identity: Option<&str>) -> Monitor {
+            Monitor {
+                name: Some(name.to_owned()),
+                identity: identity.map(|identity| identity.to_owned()),
+                ..Monitor::new(1920, 1080)
+            }
+        }
+
+        #[test]
+        fn test_by_name_and_identity() {
+            let grid = MonitorGrid(vec![
+                vec![create_named_monitor("DP-2", None)],
+                vec![create_named_monitor("HDMI-A-0", Some("GSM-5B09-0001E2B4"))],
+                vec![create_named_monitor("eDP-1", None)],
+            ]);
+
+            let scales = HashMap::from([
+                ("DP-2".to_owned(), 2.0),
+                ("GSM-5B09-0001E2B4".to_owned(), 1.5),
+            ]);
+
+            let scales = grid
+                .with_scales(&scales)
+                .0
+                .iter()
+                .flatten()
+                .map(|monitor| monitor.scale)
+                .collect::<Vec<f64>>();
+
+            assert_eq!(scales, vec![2.0, 1.5, 1.0]);
+        }
+    }
+
+    mod reorder_columns {
+        use super::*;
+
+        fn create_named_monitor(name: &str, identity: Option<&str>) -> Monitor {
+            Monitor {
+                name: Some(name.to_owned()),
+                identity: identity.map(|identity| identity.to_owned()),
+                ..Monitor::new(1920, 1080)
+            }
+        }
+
+        fn create_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![create_named_monitor("DP-2", None)],
+                vec![
+                    create_named_monitor("DP-0", None),
+                    create_named_monitor("HDMI-A-0", Some("GSM-5B09-0001E2B4")),
+                ],
+                vec![create_named_monitor("eDP-1", None)],
+            ])
+        }
+
+        fn get_names(grid: &MonitorGrid) -> Vec<Vec<&str>> {
+            grid.0
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|monitor| monitor.name.as_deref().unwrap())
+                        .collect()
+                })
+                .collect()
+        }
+
+        fn to_order(names: &[&str]) -> Vec<String> {
+            names.iter().map(|name| name.to_string()).collect()
+        }
+
+        #[test]
+        fn test_full_order() {
+            let grid = create_grid()
+                .reorder_columns(&to_order(&["eDP-1", "DP-2", "HDMI-A-0"]))
+                .unwrap();
+
+            assert_eq!(
+                get_names(&grid),
+                vec![vec!["eDP-1"], vec!["DP-2"], vec!["DP-0", "HDMI-A-0"]]
+            );
+        }
+
+        #[test]
+        fn test_partial_order_keeps_the_rest_in_detected_order() {
+            let grid = create_grid()
+                .reorder_columns(&to_order(&["GSM-5B09-0001E2B4"]))
+                .unwrap();
+
+            assert_eq!(
+                get_names(&grid),
+                vec![vec!["DP-0", "HDMI-A-0"], vec!["DP-2"], vec!["eDP-1"]]
+            );
+        }
+
+        #[test]
+        fn test_empty_order() {
+            let grid = create_grid().reorder_columns(&[]).unwrap();
+
+            assert_eq!(get_names(&grid), get_names(&create_grid()));
+        }
+
+        #[test]
+        fn test_unknown_name() {
+            let error = create_grid()
+                .reorder_columns(&to_order(&["DP-2", "DP-9"]))
+                .err()
+                .unwrap();
+
+            assert_eq!(error.to_string(), "Unknown monitor in monitor order: DP-9");
+        }
+
+        #[test]
+        fn test_same_column_twice() {
+            assert!(create_grid()
+                .reorder_columns(&to_order(&["DP-0", "HDMI-A-0"]))
+                .is_err());
+        }
+    }
+
+    mod index_of_name {
+        use super::*;
+
+        fn create_named_monitor(name: &str, width: i32, height: i32) -> Monitor {
+            Monitor {
+                name: Some(name.to_owned()),
+                ..Monitor::new(width, height)
+            }
+        }
+
+        fn create_mock_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![
+                    create_named_monitor("DisplayPort-2", 1920, 1080),
+                    create_named_monitor("HDMI-A-0", 1920, 1080),
+                ],
+                vec![create_named_monitor("DisplayPort-0", 3440, 1440)],
+                vec![create_named_monitor("DisplayPort-1", 1440, 2560)],
+            ])
+        }
+
+        #[test]
+        fn test_resolves_names() {
+            let grid = create_mock_grid();
+
+            assert_eq!(grid.index_of_name("DisplayPort-2"), Some(MonitorIndex(0)));
+            assert_eq!(grid.index_of_name("HDMI-A-0"), Some(MonitorIndex(1)));
+            assert_eq!(grid.index_of_name("DisplayPort-0"), Some(MonitorIndex(2)));
+            assert_eq!(grid.index_of_name("DisplayPort-1"), Some(MonitorIndex(3)));
+        }
+
+        #[test]
+        fn test_unknown_name() {
+            assert_eq!(create_mock_grid().index_of_name("DP-9"), None);
+        }
+
+        #[test]
+        fn test_unnamed_monitors() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(grid.index_of_name("DisplayPort-0"), None);
+        }
+
+        #[test]
+        fn test_resolves_identities() {
+            let identities = HashMap::from([
+                ("HDMI-A-0".to_owned(), "GSM-5B09-0001E2B4".to_owned()),
+                ("DisplayPort-5".to_owned(), "DEL-A0B1-3030314C".to_owned()),
+            ]);
+
+            let grid = create_mock_grid().with_identities(&identities);
+
+            assert_eq!(
+                grid.index_of_name("GSM-5B09-0001E2B4"),
+                Some(MonitorIndex(1))
+            );
+            assert_eq!(grid.index_of_name("DEL-A0B1-3030314C"), None);
+            assert_eq!(grid.0[0][0].identity, None);
+        }
+
+        #[test]
+        fn test_resolve_name() {
+            let selector = MonitorSelector::Name("DisplayPort-0".to_owned());
+            assert_eq!(
+                create_mock_grid().resolve(&selector).unwrap(),
+                MonitorIndex(2)
+            );
+        }
+
+        #[test]
+        fn test_resolve_index() {
+            let selector = MonitorSelector::Index(MonitorIndex(3));
+            assert_eq!(
+                create_mock_grid().resolve(&selector).unwrap(),
+                MonitorIndex(3)
+            );
+        }
+
+        #[test]
+        fn test_resolve_unknown_name() {
+            let selector = MonitorSelector::Name("DP-9".to_owned());
+
+            assert_eq!(
+                create_mock_grid().resolve(&selector).unwrap_err().to_string(),
+                "Unknown monitor: DP-9 (available: DisplayPort-2, HDMI-A-0, DisplayPort-0, DisplayPort-1)"
+            );
+        }
+
+        #[test]
+        fn test_resolve_unknown_name_without_names() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            let selector = MonitorSelector::Name("DP-9".to_owned());
+
+            assert_eq!(
+                grid.resolve(&selector).unwrap_err().to_string(),
+                "Unknown monitor: DP-9 (available: none)"
+            );
+        }
+    }
+
+    mod to_monitor_local {
+        use super::*;
+
+        fn create_mock_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        #[test]
+        fn test_quad_layout() {
+            let grid = create_mock_grid();
+
+            assert_eq!(
+                grid.to_monitor_local(0, 0).unwrap(),
+                (MonitorIndex(0), 0, 0)
+            );
+            assert_eq!(
+                grid.to_monitor_local(100, 1200).unwrap(),
+                (MonitorIndex(1), 100, 120)
+            );
+            assert_eq!(
+                grid.to_monitor_local(5359, 1439).unwrap(),
+                (MonitorIndex(2), 3439, 1439)
+            );
+            assert_eq!(
+                grid.to_monitor_local(5360, 2000).unwrap(),
+                (MonitorIndex(3), 0, 2000)
+            );
+        }
+
+        #[test]
+        fn test_off_monitor() {
+            let grid = create_mock_grid();
+
+            // Below the shorter monitor in the middle column.
+            assert_eq!(
+                grid.to_monitor_local(2000, 1500).unwrap_err().to_string(),
+                "Point (2000, 1500) isn't on any monitor"
+            );
+            assert!(grid.to_monitor_local(-1, 0).is_err());
+            assert!(grid.to_monitor_local(6800, 0).is_err());
+            assert!(MonitorGrid(vec![]).to_monitor_local(0, 0).is_err());
+        }
+
+        #[test]
+        fn test_scaled_monitor() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(3840, 2160).with_scale(0.5)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            assert_eq!(
+                grid.to_monitor_local(1919, 0).unwrap(),
+                (MonitorIndex(0), 1919, 0)
+            );
+            assert_eq!(
+                grid.to_monitor_local(1920, 0).unwrap(),
+                (MonitorIndex(1), 0, 0)
+            );
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let grid = create_mock_grid();
+
+            for (x, y) in [
+                (0, 0),
+                (1919, 1079),
+                (960, 1080),
+                (1920, 0),
+                (4000, 720),
+                (5360, 0),
+                (6799, 2559),
+            ] {
+                let (monitor, local_x, local_y) = grid.to_monitor_local(x, y).unwrap();
+
+                assert_eq!(
+                    grid.from_monitor_local(&monitor, local_x, local_y).unwrap(),
+                    (x, y)
+                );
+            }
+        }
+    }
+
+    mod from_monitor_local {
+        use super::*;
+
+        #[test]
+        fn test_quad_layout() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            assert_eq!(
+                grid.from_monitor_local(&MonitorIndex(1), 100, 120).unwrap(),
+                (100, 1200)
+            );
+            assert_eq!(
+                grid.from_monitor_local(&MonitorIndex(3), 0, 0).unwrap(),
+                (5360, 0)
+            );
+            assert_eq!(
+                grid.from_monitor_local(&MonitorIndex(4), 0, 0)
+                    .unwrap_err()
+                    .to_string(),
+                "Invalid monitor index: 4"
+            );
+        }
+    }
+
+    mod get_monitor_bounds {
+        use super::*;
+
+        #[test]
+        fn test_non_origin_monitors() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(0)),
+                Some((0, 0, 1920, 1080))
+            );
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(1)),
+                Some((0, 1080, 1920, 1080))
+            );
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(2)),
+                Some((1920, 0, 3440, 1440))
+            );
+            assert_eq!(
+                grid.get_monitor_bounds(&MonitorIndex(3)),
+                Some((5360, 0, 1440, 2560))
+            );
+        }
+
+        #[test]
+        fn test_out_of_range() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(grid.get_monitor_bounds(&MonitorIndex(1)), None);
+        }
+    }
+
+    mod get_monitor_coordinates {
+        use super::*;
+
+        fn create_mock_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        #[test]
+        fn test_quad_monitor_setup() {
+            let grid = create_mock_grid();
+
+            assert_eq!(grid.get_monitor_coordinates(&MonitorIndex(0)), Some((0, 0)));
+            assert_eq!(grid.get_monitor_coordinates(&MonitorIndex(1)), Some((0, 1)));
+            assert_eq!(grid.get_monitor_coordinates(&MonitorIndex(2)), Some((1, 0)));
+            assert_eq!(grid.get_monitor_coordinates(&MonitorIndex(3)), Some((2, 0)));
+        }
+
+        #[test]
+        fn test_out_of_range() {
+            let grid = create_mock_grid();
+            assert_eq!(grid.get_monitor_coordinates(&MonitorIndex(4)), None);
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let grid = create_mock_grid();
+
+            for index in 0..4 {
+                let (column, row) = grid.get_monitor_coordinates(&MonitorIndex(index)).unwrap();
+                assert_eq!(grid.get_monitor_index(column, row), MonitorIndex(index));
+            }
         }
     }
 
@@ -0,0 +1,137 @@
+use anyhow::Result;
+
+/// A horizontal slice of the workspace given as a range of percentages of its width, e.g. `0-25` for its leftmost
+/// quarter, regardless of how many monitors make it up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    /// The start of the range (inclusive), in percent of the workspace's width.
+    pub start: u32,
+    /// The end of the range (exclusive), in percent of the workspace's width.
+    pub end: u32,
+}
+
+impl Region {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid region: {} (expected a range of percentages like 0-25 or 25-75)",
+                value
+            )
+        };
+
+        let (start, end) = value.trim().split_once('-').ok_or_else(invalid)?;
+        let start = start.trim().parse::<u32>().map_err(|_| invalid())?;
+        let end = end.trim().parse::<u32>().map_err(|_| invalid())?;
+
+        if start >= end || end > 100 {
+            return Err(invalid());
+        }
+
+        Ok(Region { start, end })
+    }
+
+    /// Converts the region into the range of x positions (in pixels, start inclusive and end exclusive)
+    /// that it covers on a workspace of the given width.
+    pub fn to_pixel_range(&self, workspace_width: i32) -> (i32, i32) {
+        let to_pixels = |percent: u32| (workspace_width as i64 * percent as i64 / 100) as i32;
+
+        (to_pixels(self.start), to_pixels(self.end))
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl TryFrom<String> for Region {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Region::try_from_string(&value)
+    }
+}
+
+impl TryFrom<&str> for Region {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Region::try_from_string(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod try_from_string {
+        use super::*;
+
+        #[test]
+        fn test_valid_regions() {
+            assert_eq!(
+                Region::try_from_string("0-25").unwrap(),
+                Region { start: 0, end: 25 }
+            );
+            assert_eq!(
+                Region::try_from_string(" 25 - 75 ").unwrap(),
+                Region { start: 25, end: 75 }
+            );
+            assert_eq!(
+                Region::try_from_string("75-100").unwrap(),
+                Region {
+                    start: 75,
+                    end: 100
+                }
+            );
+        }
+
+        #[test]
+        fn test_invalid_regions() {
+            for value in [
+                "", "25", "25-", "-25", "a-b", "50-25", "50-50", "0-101", "-5-10",
+            ] {
+                assert!(Region::try_from_string(value).is_err(), "{value}");
+            }
+        }
+
+        #[test]
+        fn test_display_round_trip() {
+            let region = Region { start: 25, end: 75 };
+            assert_eq!(Region::try_from(region.to_string()).unwrap(), region);
+        }
+    }
+
+    mod to_pixel_range {
+        use super::*;
+
+        #[test]
+        fn test_pixel_ranges() {
+            // E.g. a 1920 + 3440 + 1440 wide workspace.
+            let workspace_width = 6800;
+
+            assert_eq!(
+                Region { start: 0, end: 25 }.to_pixel_range(workspace_width),
+                (0, 1700)
+            );
+            assert_eq!(
+                Region { start: 25, end: 75 }.to_pixel_range(workspace_width),
+                (1700, 5100)
+            );
+            assert_eq!(
+                Region {
+                    start: 75,
+                    end: 100
+                }
+                .to_pixel_range(workspace_width),
+                (5100, 6800)
+            );
+        }
+
+        #[test]
+        fn test_rounds_down() {
+            assert_eq!(Region { start: 0, end: 33 }.to_pixel_range(1920), (0, 633));
+        }
+    }
+}
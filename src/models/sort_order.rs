@@ -0,0 +1,96 @@
+use anyhow::Result;
+
+/// The order in which windows are listed and cycled through.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SortOrder {
+    /// From left to right (the order used for directional focusing).
+    #[default]
+    Position,
+    /// Alphabetically by window class.
+    Class,
+    /// Alphabetically by title.
+    Title,
+    /// Most recently used first, going by each window's `_NET_WM_USER_TIME` (read through `xprop`). Windows without
+    /// one come last, from left to right.
+    Mru,
+    /// Top-most first, by the window manager's own stacking order (`_NET_CLIENT_LIST_STACKING`) rather than the
+    /// order that `wmctrl` lists the windows in.
+    Stacking,
+}
+
+impl SortOrder {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        match value {
+            "position" => Ok(SortOrder::Position),
+            "class" => Ok(SortOrder::Class),
+            "title" => Ok(SortOrder::Title),
+            "mru" => Ok(SortOrder::Mru),
+            "stacking" => Ok(SortOrder::Stacking),
+            _ => Err(anyhow::anyhow!(
+                "Invalid sort order: {} (expected one of position, class, title, mru, stacking)",
+                value
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Position => "position",
+            SortOrder::Class => "class",
+            SortOrder::Title => "title",
+            SortOrder::Mru => "mru",
+            SortOrder::Stacking => "stacking",
+        }
+    }
+}
+
+impl TryFrom<String> for SortOrder {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        SortOrder::try_from_string(&value)
+    }
+}
+
+impl TryFrom<&str> for SortOrder {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        SortOrder::try_from_string(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod try_from_string {
+        use super::*;
+
+        #[test]
+        fn test_valid_sort_orders() {
+            for sort_order in [
+                SortOrder::Position,
+                SortOrder::Class,
+                SortOrder::Title,
+                SortOrder::Mru,
+                SortOrder::Stacking,
+            ] {
+                assert_eq!(
+                    SortOrder::try_from_string(sort_order.as_str()).unwrap(),
+                    sort_order
+                );
+            }
+        }
+
+        #[test]
+        fn test_invalid_sort_order() {
+            let result = SortOrder::try_from_string("size");
+            assert!(result.is_err());
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid sort order: size"));
+        }
+    }
+}
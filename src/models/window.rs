@@ -3,6 +3,46 @@ use anyhow::Result;
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct WindowId(pub usize);
 
+impl WindowId {
+    /// The hex representation of the ID, as used by tools like `wmctrl` and `xprop` (e.g. "0x05000006").
+    pub fn to_hex_string(&self) -> String {
+        format!("{:#010x}", self.0)
+    }
+
+    /// Parses a hex ID, with or without its `0x` prefix (e.g. "0x05000006" or "05000006").
+    pub fn from_hex_str(value: &str) -> Result<Self> {
+        Ok(WindowId(usize::from_str_radix(
+            value.trim_start_matches("0x"),
+            16,
+        )?))
+    }
+}
+
+impl std::str::FromStr for WindowId {
+    type Err = anyhow::Error;
+
+    /// Parses either a hex ID with a `0x` prefix (e.g. "0x05000006", as printed by `wmctrl` and `list`) or a decimal
+    /// one (e.g. "83886086", as printed by `xdotool`).
+    ///
+    /// Without a prefix, the ID is decimal unless it can only be hex (e.g. "3a00003").
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+
+        let id = match value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => value
+                .parse::<usize>()
+                .or_else(|_| usize::from_str_radix(value, 16)),
+        };
+
+        id.map(WindowId)
+            .map_err(|_| anyhow::anyhow!("Invalid window ID: {value}"))
+    }
+}
+
 impl std::fmt::Display for WindowId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -12,6 +52,9 @@ impl std::fmt::Display for WindowId {
 /// The height of the window decoration that is constant in Ubuntu.
 pub const WINDOW_DECORATION: i32 = 24;
 
+/// The value of the offsets and dimensions of windows whose geometry isn't known (see `Window::has_geometry`).
+pub const UNKNOWN_GEOMETRY: i32 = -1;
+
 /// Models the attributes of a single window (on a Monitor).
 /// Specifically, it cares about things like where the window is positioned relative to the current
 /// Workspace (i.e. x and y offset) as well as the ID/title of the window.
@@ -20,17 +63,24 @@ pub const WINDOW_DECORATION: i32 = 24;
 ///
 /// - id: An integer representation of the window's ID (normally in hex).
 /// - x_offset and y_offset:
-///     x and y offset are how windows (specifically, their top-left corner, not including window decoration)
-///     are positioned relative to the current workspace. Some examples (given a triple 1080p monitor setup):
-///         - An x,y offset of 0,0 would put the window on the left-most monitor.
-///         - An x,y offset of 0,24 also puts the window on the left-most monitor,
-///             but the y-offset has accounted for window decoration (this is what's most commonly seen).
-///         - An x,y offset of 1920,24 puts the window in the center monitor, because it is positioned 1920 pixels
-///             from the left-most edge of the workspace.
+///   x and y offset are how windows (specifically, their top-left corner, not including window decoration)
+///   are positioned relative to the current workspace. Some examples (given a triple 1080p monitor setup):
+///     - An x,y offset of 0,0 would put the window on the left-most monitor.
+///     - An x,y offset of 0,24 also puts the window on the left-most monitor,
+///       but the y-offset has accounted for window decoration (this is what's most commonly seen).
+///     - An x,y offset of 1920,24 puts the window in the center monitor, because it is positioned 1920 pixels
+///       from the left-most edge of the workspace.
 /// - height: The height of the window (in pixels).
 /// - width: The width of the window (in pixels).
+///   All four are `UNKNOWN_GEOMETRY` for windows listed without their geometry (i.e. by a `wmctrl` without `-G`).
 /// - window_class: The class of the window (e.g. "google-chrome.Google-chrome")
 /// - title: The title of the window.
+/// - frame_top: The height of the window's top frame extent (i.e. its decoration), if it could be queried.
+///   Falls back to `WINDOW_DECORATION` when absent.
+/// - desktop: The index of the virtual desktop that the window is on, if known. -1 means the window is on all desktops.
+/// - stacking_index: The position of the window in wmctrl's output (the window manager's client list order, i.e.
+///   `_NET_CLIENT_LIST`, which is the order the windows were mapped in rather than their stacking order). Replaced with
+///   the position in the actual stacking order when sorting by `SortOrder::Stacking`.
 #[derive(Clone, Debug)]
 pub struct Window {
     pub id: WindowId,
@@ -40,6 +90,9 @@ pub struct Window {
     pub height: i32,
     pub window_class: String,
     pub title: String,
+    pub frame_top: Option<i32>,
+    pub desktop: Option<i32>,
+    pub stacking_index: usize,
 }
 
 impl Window {
@@ -60,6 +113,9 @@ impl Window {
             height,
             window_class,
             title,
+            frame_top: None,
+            desktop: None,
+            stacking_index: 0,
         }
     }
 
@@ -77,16 +133,57 @@ impl Window {
     /// Column 6 is the WM_CLASS property from the '-x' option (gnome-terminal-server.Gnome-terminal)
     /// Column 7 is the hostname (devin-Desktop)
     /// Column 8+ is the title of the window (Terminal)
+    ///
+    /// Rather than blindly indexing into the columns, parsing anchors on the run of numeric columns
+    /// that follows the ID (the desktop index and the four geometry columns) and treats whatever comes
+    /// after them as the class, hostname, and title. Malformed lines produce an error instead of a panic.
+    ///
+    /// The columns can be separated by any (amount of) whitespace, including tabs. The title is taken
+    /// as-is from the line (minus any trailing whitespace) so that its original spacing is kept.
     pub fn from_raw_config(raw_config: &str) -> Result<Self> {
-        let split_config: Vec<&str> = raw_config.split_whitespace().collect();
+        let tokens = Self::split_whitespace_with_offsets(raw_config);
+        let split_config: Vec<&str> = tokens.iter().map(|(_, token)| *token).collect();
+
+        let (raw_id, columns) = split_config
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Invalid window config: {raw_config}"))?;
+
+        let id = Self::parse_id(raw_id)?;
+
+        // The desktop index is normally present, but only the four geometry columns are required.
+        let numeric_count = columns
+            .iter()
+            .take(5)
+            .take_while(|column| column.parse::<i32>().is_ok())
+            .count();
+
+        if numeric_count < 4 {
+            return Err(anyhow::anyhow!("Invalid window config: {raw_config}"));
+        }
+
+        let desktop = match numeric_count {
+            5 => Some(columns[0].parse::<i32>()?),
+            _ => None,
+        };
+
+        let geometry = &columns[numeric_count - 4..numeric_count];
+        let x_offset = geometry[0].parse::<i32>()?;
+        let y_offset = geometry[1].parse::<i32>()?;
+        let width = geometry[2].parse::<i32>()?;
+        let height = geometry[3].parse::<i32>()?;
+
+        let remaining = &columns[numeric_count..];
 
-        let id = Self::parse_id(split_config[0])?;
-        let x_offset = split_config[2].parse::<i32>()?;
-        let y_offset = split_config[3].parse::<i32>()?;
-        let width = split_config[4].parse::<i32>()?;
-        let height = split_config[5].parse::<i32>()?;
-        let window_class = split_config[6].to_string();
-        let title: String = split_config[8..].join(" "); // Skip column 7 since we don't care about the hostname.
+        let window_class = remaining
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Invalid window config: {raw_config}"))?
+            .to_string();
+
+        // Skip the hostname (if there is one) since we don't care about it.
+        let title = tokens
+            .get(1 + numeric_count + 2)
+            .map_or("", |(offset, _)| raw_config[*offset..].trim_end())
+            .to_string();
 
         Ok(Self {
             id,
@@ -96,14 +193,142 @@ impl Window {
             width,
             window_class,
             title,
+            frame_top: None,
+            desktop,
+            stacking_index: 0,
         })
     }
 
+    /// Same as `from_raw_config`, but also accepts lines without the geometry columns (as listed by `wmctrl -l -x`
+    /// without `-G`), giving those windows an `UNKNOWN_GEOMETRY`.
+    ///
+    /// Example: "0x05000006  0 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal"
+    ///
+    /// To keep malformed geometry from being mistaken for a class, the class of such a line has to look like a
+    /// `WM_CLASS` from `-x` (i.e. `instance.Class` or `N/A`). Lines that can't be parsed either way get the error of the
+    /// geometry-rich format.
+    pub fn from_raw_config_lenient(raw_config: &str) -> Result<Self> {
+        Self::from_raw_config(raw_config)
+            .or_else(|error| Self::from_raw_config_without_geometry(raw_config).map_err(|_| error))
+    }
+
+    fn from_raw_config_without_geometry(raw_config: &str) -> Result<Self> {
+        let tokens = Self::split_whitespace_with_offsets(raw_config);
+        let split_config: Vec<&str> = tokens.iter().map(|(_, token)| *token).collect();
+
+        let (raw_id, columns) = split_config
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Invalid window config: {raw_config}"))?;
+
+        let id = Self::parse_id(raw_id)?;
+
+        let desktop = columns
+            .first()
+            .and_then(|column| column.parse::<i32>().ok());
+        let desktop_count = usize::from(desktop.is_some());
+
+        let window_class = columns
+            .get(desktop_count)
+            .filter(|class| **class == "N/A" || class.contains('.'))
+            .ok_or_else(|| anyhow::anyhow!("Invalid window config: {raw_config}"))?
+            .to_string();
+
+        // Skip the hostname (if there is one) since we don't care about it.
+        let title = tokens
+            .get(1 + desktop_count + 2)
+            .map_or("", |(offset, _)| raw_config[*offset..].trim_end())
+            .to_string();
+
+        Ok(Self {
+            desktop,
+            ..Self::new(
+                id,
+                UNKNOWN_GEOMETRY,
+                UNKNOWN_GEOMETRY,
+                UNKNOWN_GEOMETRY,
+                UNKNOWN_GEOMETRY,
+                window_class,
+                title,
+            )
+        })
+    }
+
+    /// Whether the window's position and size are known, which every command that works with positions (e.g. picking
+    /// a window by direction or monitor) needs.
+    pub fn has_geometry(&self) -> bool {
+        self.width != UNKNOWN_GEOMETRY || self.height != UNKNOWN_GEOMETRY
+    }
+
+    /// Splits the value on whitespace like `str::split_whitespace`, along with the byte offset that each token starts
+    /// at in the value.
+    fn split_whitespace_with_offsets(value: &str) -> Vec<(usize, &str)> {
+        let mut tokens = Vec::new();
+        let mut token_start = None;
+
+        for (index, character) in value.char_indices() {
+            if character.is_whitespace() {
+                if let Some(start) = token_start.take() {
+                    tokens.push((start, &value[start..index]));
+                }
+            } else if token_start.is_none() {
+                token_start = Some(index);
+            }
+        }
+
+        if let Some(start) = token_start {
+            tokens.push((start, &value[start..]));
+        }
+
+        tokens
+    }
+
+    /// The height of the window's decoration, preferring the window's actual top frame extent when it is known.
+    pub fn decoration_height(&self) -> i32 {
+        self.frame_top.unwrap_or(WINDOW_DECORATION)
+    }
+
+    /// Whether the window's class matches the given class (case-insensitively). Since a `WM_CLASS` is made up of
+    /// an instance and a class (e.g. "code.Code"), either part, or the whole thing, is considered a match.
+    pub fn matches_class(&self, class: &str) -> bool {
+        self.window_class.eq_ignore_ascii_case(class)
+            || self
+                .window_class
+                .split('.')
+                .any(|part| part.eq_ignore_ascii_case(class))
+    }
+
+    /// Whether the window's class contains the given substring (case-insensitively), e.g. "slack" for "slack.Slack".
+    pub fn class_contains(&self, substring: &str) -> bool {
+        self.window_class
+            .to_lowercase()
+            .contains(&substring.to_lowercase())
+    }
+
+    /// The position of the window's center (in pixels) as `(x, y)`, relative to the workspace like its offsets.
+    pub fn center(&self) -> (i32, i32) {
+        (
+            self.x_offset + self.width / 2,
+            self.y_offset + self.height / 2,
+        )
+    }
+
+    /// The area of the window (in pixels).
+    pub fn area(&self) -> i32 {
+        self.width * self.height
+    }
+
+    /// The area (in pixels) of the part of the window that's within the given bounds (`(x, y, width, height)`, e.g.
+    /// a monitor's bounds).
+    pub fn overlap_area(&self, (x, y, width, height): (i32, i32, i32, i32)) -> i32 {
+        let overlap_width = (self.x_offset + self.width).min(x + width) - self.x_offset.max(x);
+        let overlap_height = (self.y_offset + self.height).min(y + height) - self.y_offset.max(y);
+
+        overlap_width.max(0) * overlap_height.max(0)
+    }
+
     fn parse_id(hex_string: &str) -> Result<WindowId> {
-        Ok(WindowId(usize::from_str_radix(
-            hex_string.trim_start_matches("0x"),
-            16,
-        )?))
+        // wmctrl always prints hex IDs, so unlike `WindowId::from_str`, there's no guessing at decimal.
+        WindowId::from_hex_str(hex_string)
     }
 }
 
@@ -153,6 +378,73 @@ mod tests {
             assert_ne!(id1, id3);
         }
 
+        #[test]
+        fn test_window_id_to_hex_string() {
+            assert_eq!(WindowId(83886086).to_hex_string(), "0x05000006");
+            assert_eq!(WindowId(0x0340000b).to_hex_string(), "0x0340000b");
+        }
+
+        #[test]
+        fn test_window_id_from_str() {
+            assert_eq!(
+                "0x05000006".parse::<WindowId>().unwrap(),
+                WindowId(0x05000006)
+            );
+            assert_eq!(
+                "0X0340000B".parse::<WindowId>().unwrap(),
+                WindowId(0x0340000b)
+            );
+            assert_eq!(
+                "83886086".parse::<WindowId>().unwrap(),
+                WindowId(0x05000006)
+            );
+            assert_eq!(" 42\n".parse::<WindowId>().unwrap(), WindowId(42));
+        }
+
+        #[test]
+        fn test_window_id_from_str_hex_without_prefix() {
+            assert_eq!("3a00003".parse::<WindowId>().unwrap(), WindowId(0x3a00003));
+            assert_eq!(
+                "05A0000B".parse::<WindowId>().unwrap(),
+                WindowId(0x05a0000b)
+            );
+
+            // Digits alone are always decimal.
+            assert_eq!("05000006".parse::<WindowId>().unwrap(), WindowId(5000006));
+        }
+
+        #[test]
+        fn test_window_id_from_hex_str() {
+            assert_eq!(
+                WindowId::from_hex_str("0x05000006").unwrap(),
+                WindowId(0x05000006)
+            );
+            assert_eq!(
+                WindowId::from_hex_str("05000006").unwrap(),
+                WindowId(0x05000006)
+            );
+            assert!(WindowId::from_hex_str("0X05000006").is_err());
+            assert!(WindowId::from_hex_str("").is_err());
+        }
+
+        #[test]
+        fn test_window_id_from_str_invalid() {
+            for value in ["", "0x", "0xzz", "0x-1", "-1", "window", "12 34"] {
+                assert_eq!(
+                    value.parse::<WindowId>().unwrap_err().to_string(),
+                    format!("Invalid window ID: {value}")
+                );
+            }
+        }
+
+        #[test]
+        fn test_window_id_from_str_round_trip() {
+            let id = WindowId(0x05000006);
+
+            assert_eq!(id.to_hex_string().parse::<WindowId>().unwrap(), id);
+            assert_eq!(id.to_string().parse::<WindowId>().unwrap(), id);
+        }
+
         #[test]
         fn test_window_id_clone() {
             let id1 = WindowId(42);
@@ -223,6 +515,205 @@ mod tests {
         }
     }
 
+    mod decoration_height {
+        use super::*;
+
+        fn create_window(frame_top: Option<i32>) -> Window {
+            let mut window = Window::new(
+                WindowId(1),
+                0,
+                0,
+                1920,
+                1056,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            window.frame_top = frame_top;
+            window
+        }
+
+        #[test]
+        fn test_falls_back_to_constant() {
+            assert_eq!(create_window(None).decoration_height(), WINDOW_DECORATION);
+        }
+
+        #[test]
+        fn test_uses_frame_top() {
+            assert_eq!(create_window(Some(37)).decoration_height(), 37);
+            assert_eq!(create_window(Some(0)).decoration_height(), 0);
+        }
+    }
+
+    mod matches_class {
+        use super::*;
+
+        fn create_window(window_class: &str) -> Window {
+            Window::new(
+                WindowId(1),
+                0,
+                0,
+                1920,
+                1056,
+                window_class.to_string(),
+                "title".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_full_class() {
+            assert!(create_window("code.Code").matches_class("code.Code"));
+        }
+
+        #[test]
+        fn test_class_parts() {
+            let window = create_window("gnome-terminal-server.Gnome-terminal");
+            assert!(window.matches_class("gnome-terminal-server"));
+            assert!(window.matches_class("Gnome-terminal"));
+        }
+
+        #[test]
+        fn test_case_insensitive() {
+            assert!(create_window("code.Code").matches_class("CODE"));
+        }
+
+        #[test]
+        fn test_no_partial_match() {
+            let window = create_window("google-chrome.Google-chrome");
+            assert!(!window.matches_class("chrome"));
+            assert!(!window.matches_class("code"));
+        }
+    }
+
+    mod class_contains {
+        use super::*;
+
+        fn create_window(window_class: &str) -> Window {
+            Window::new(
+                WindowId(1),
+                0,
+                0,
+                1920,
+                1056,
+                window_class.to_string(),
+                "title".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_substring() {
+            let window = create_window("gnome-terminal-server.Gnome-terminal");
+            assert!(window.class_contains("terminal"));
+            assert!(window.class_contains("server.Gnome"));
+        }
+
+        #[test]
+        fn test_case_insensitive() {
+            assert!(create_window("slack.Slack").class_contains("SLACK"));
+        }
+
+        #[test]
+        fn test_no_match() {
+            assert!(!create_window("code.Code").class_contains("slack"));
+        }
+    }
+
+    mod center {
+        use super::*;
+
+        #[test]
+        fn test_center() {
+            let window = Window::new(
+                WindowId(1),
+                1920,
+                24,
+                1920,
+                1056,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            assert_eq!(window.center(), (2880, 552));
+        }
+    }
+
+    mod area {
+        use super::*;
+
+        #[test]
+        fn test_area() {
+            let window = Window::new(
+                WindowId(1),
+                0,
+                0,
+                1920,
+                1056,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            assert_eq!(window.area(), 1920 * 1056);
+        }
+
+        #[test]
+        fn test_zero_area() {
+            let window = Window::new(
+                WindowId(1),
+                0,
+                0,
+                0,
+                1056,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            assert_eq!(window.area(), 0);
+        }
+    }
+
+    mod overlap_area {
+        use super::*;
+
+        fn create_window(x_offset: i32, y_offset: i32, width: i32, height: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                width,
+                height,
+                "class".to_string(),
+                "title".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_fully_inside() {
+            let window = create_window(100, 100, 800, 600);
+            assert_eq!(window.overlap_area((0, 0, 1920, 1080)), window.area());
+        }
+
+        #[test]
+        fn test_partially_inside() {
+            // Half of the window hangs off the right edge of the first monitor.
+            let window = create_window(1520, 0, 800, 600);
+            assert_eq!(window.overlap_area((0, 0, 1920, 1080)), 400 * 600);
+            assert_eq!(window.overlap_area((1920, 0, 1920, 1080)), 400 * 600);
+        }
+
+        #[test]
+        fn test_covers_bounds() {
+            let window = create_window(-100, -100, 4000, 3000);
+            assert_eq!(window.overlap_area((0, 0, 1920, 1080)), 1920 * 1080);
+        }
+
+        #[test]
+        fn test_outside() {
+            let window = create_window(1920, 0, 800, 600);
+            assert_eq!(window.overlap_area((0, 0, 1920, 1080)), 0);
+            assert_eq!(window.overlap_area((0, 1080, 1920, 1080)), 0);
+        }
+    }
+
     mod additional_from_raw_config_tests {
         use super::*;
 
@@ -251,12 +742,148 @@ mod tests {
         }
 
         #[test]
-        #[should_panic(expected = "index out of bounds")]
         fn test_from_raw_config_too_few_parts() {
             let raw_config = "0x05000006  0 1920";
-            let _result = Window::from_raw_config(raw_config);
-            // This will panic when trying to access split_config[3] because there are only 3 parts (indices 0,1,2)
-            // The function doesn't validate the input length before accessing array elements
+            let result = Window::from_raw_config(raw_config);
+            assert!(result.is_err());
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid window config"));
+        }
+
+        #[test]
+        fn test_from_raw_config_empty_line() {
+            assert!(Window::from_raw_config("").is_err());
+            assert!(Window::from_raw_config("   ").is_err());
+        }
+
+        #[test]
+        fn test_from_raw_config_missing_class() {
+            let raw_config = "0x05000006  0 1920 24   1920 1056";
+            assert!(Window::from_raw_config(raw_config).is_err());
+        }
+
+        #[test]
+        fn test_from_raw_config_pathological_spacing() {
+            let raw_config = "  0x05000006 \t 0    1920\t24 1920     1056   gnome-terminal-server.Gnome-terminal \t devin-Desktop   Terminal  ";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.id, WindowId(83886086));
+            assert_eq!(window.x_offset, 1920);
+            assert_eq!(window.y_offset, 24);
+            assert_eq!(window.width, 1920);
+            assert_eq!(window.height, 1056);
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "Terminal");
+        }
+
+        #[test]
+        fn test_from_raw_config_keeps_title_spacing() {
+            let raw_config = "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop build  -  log\tviewer";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.title, "build  -  log\tviewer");
+        }
+
+        #[test]
+        fn test_from_raw_config_tab_separated() {
+            let raw_config = "\t0x05000006\t0\t1920\t24\t1920\t1056\tgnome-terminal-server.Gnome-terminal\tdevin-Desktop\tTerminal  1\t";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.id, WindowId(83886086));
+            assert_eq!(window.desktop, Some(0));
+            assert_eq!(window.x_offset, 1920);
+            assert_eq!(window.y_offset, 24);
+            assert_eq!(window.width, 1920);
+            assert_eq!(window.height, 1056);
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "Terminal  1");
+        }
+
+        #[test]
+        fn test_from_raw_config_multibyte_title() {
+            let raw_config = "0x05000006  0 1920 24   1920 1056 firefox.Firefox  devin-Desktop Café  —  Mozilla Firefox";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.title, "Café  —  Mozilla Firefox");
+        }
+
+        #[test]
+        fn test_from_raw_config_na_class() {
+            let raw_config = "0x0340000b  0 -159 -1156 59   1056 N/A                   devin-5900x unity-launcher";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.id, WindowId(0x0340000b));
+            assert_eq!(window.x_offset, -159);
+            assert_eq!(window.y_offset, -1156);
+            assert_eq!(window.width, 59);
+            assert_eq!(window.height, 1056);
+            assert_eq!(window.window_class, "N/A");
+            assert_eq!(window.title, "unity-launcher");
+        }
+
+        #[test]
+        fn test_from_raw_config_na_class_multi_word_title() {
+            let raw_config = "0x01e00003 -1 0    0    1920 24   N/A                                   devin-Desktop Top Panel";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.desktop, Some(-1));
+            assert_eq!(window.x_offset, 0);
+            assert_eq!(window.y_offset, 0);
+            assert_eq!(window.width, 1920);
+            assert_eq!(window.height, 24);
+            assert_eq!(window.window_class, "N/A");
+            assert_eq!(window.title, "Top Panel");
+        }
+
+        #[test]
+        fn test_from_raw_config_na_class_compound_hostname() {
+            let raw_config =
+                "0x0340000b  0 -159 -1156 59   1056 N/A   devin-5900x.home.lan  Unity  Launcher";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.x_offset, -159);
+            assert_eq!(window.height, 1056);
+            assert_eq!(window.window_class, "N/A");
+            assert_eq!(window.title, "Unity  Launcher");
+
+            // Classless windows can also be missing their hostname, which wmctrl then also renders as `N/A`.
+            let raw_config = "0x0340000b  0 -159 -1156 59   1056 N/A N/A unity-launcher";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.window_class, "N/A");
+            assert_eq!(window.title, "unity-launcher");
+        }
+
+        #[test]
+        fn test_from_raw_config_desktop() {
+            let raw_config = "0x05000006  1 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal";
+            assert_eq!(
+                Window::from_raw_config(raw_config).unwrap().desktop,
+                Some(1)
+            );
+
+            let raw_config =
+                "0x03800003 -1 0    1080 1920 1080 nemo-desktop.Nemo-desktop  devin-5900x Desktop";
+            assert_eq!(
+                Window::from_raw_config(raw_config).unwrap().desktop,
+                Some(-1)
+            );
+        }
+
+        #[test]
+        fn test_from_raw_config_without_desktop_column() {
+            let raw_config = "0x05000006 1920 24 1920 1056 gnome-terminal-server.Gnome-terminal devin-Desktop Terminal";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.desktop, None);
+            assert_eq!(window.x_offset, 1920);
+            assert_eq!(window.y_offset, 24);
+            assert_eq!(window.width, 1920);
+            assert_eq!(window.height, 1056);
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "Terminal");
+        }
+
+        #[test]
+        fn test_from_raw_config_without_hostname() {
+            let raw_config =
+                "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal";
+            let window = Window::from_raw_config(raw_config).unwrap();
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "");
         }
 
         #[test]
@@ -281,6 +908,78 @@ mod tests {
             assert_eq!(window.y_offset, -50);
         }
 
+        #[test]
+        fn test_from_raw_config_lenient_with_geometry() {
+            let raw_config = "0x05000006  0 1920 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal";
+            let window = Window::from_raw_config_lenient(raw_config).unwrap();
+
+            assert!(window.has_geometry());
+            assert_eq!(
+                (
+                    window.x_offset,
+                    window.y_offset,
+                    window.width,
+                    window.height
+                ),
+                (1920, 24, 1920, 1056)
+            );
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "Terminal");
+        }
+
+        #[test]
+        fn test_from_raw_config_lenient_without_geometry() {
+            let raw_config =
+                "0x05000006  0 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal - Tab 1";
+            let window = Window::from_raw_config_lenient(raw_config).unwrap();
+
+            assert!(!window.has_geometry());
+            assert_eq!(window.id, WindowId(0x05000006));
+            assert_eq!(window.desktop, Some(0));
+            assert_eq!(
+                (
+                    window.x_offset,
+                    window.y_offset,
+                    window.width,
+                    window.height
+                ),
+                (
+                    UNKNOWN_GEOMETRY,
+                    UNKNOWN_GEOMETRY,
+                    UNKNOWN_GEOMETRY,
+                    UNKNOWN_GEOMETRY
+                )
+            );
+            assert_eq!(window.window_class, "gnome-terminal-server.Gnome-terminal");
+            assert_eq!(window.title, "Terminal - Tab 1");
+        }
+
+        #[test]
+        fn test_from_raw_config_lenient_without_geometry_or_desktop() {
+            let window =
+                Window::from_raw_config_lenient("0x04400003 N/A devin-Desktop Top Panel").unwrap();
+
+            assert!(!window.has_geometry());
+            assert_eq!(window.desktop, None);
+            assert_eq!(window.window_class, "N/A");
+            assert_eq!(window.title, "Top Panel");
+        }
+
+        #[test]
+        fn test_from_raw_config_lenient_malformed_geometry() {
+            // Neither a full geometry nor a WM_CLASS after the desktop, so this isn't taken as a geometry-less line.
+            let raw_config = "0x05000006  0 abc 24   1920 1056 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal";
+            assert!(Window::from_raw_config_lenient(raw_config).is_err());
+
+            let raw_config = "0x04400003  0 1920 code.Code devin-Desktop Visual Studio Code";
+            assert_eq!(
+                Window::from_raw_config_lenient(raw_config)
+                    .unwrap_err()
+                    .to_string(),
+                format!("Invalid window config: {raw_config}")
+            );
+        }
+
         #[test]
         fn test_from_raw_config_zero_dimensions() {
             let raw_config = "0x05000006  0 0 0   0 0 gnome-terminal-server.Gnome-terminal  devin-Desktop Terminal";
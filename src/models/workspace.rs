@@ -10,6 +10,12 @@ pub struct Workspace {
 
     /// The height of a single workspace (in pixels) that is made up of the monitors.
     workspace_height: i32,
+
+    /// Where the workspace's top-left corner is in the screen's coordinates, i.e. the smallest x and y offsets of the
+    /// monitors. Normally (0, 0), but monitors can be placed at negative offsets (left of or above the origin).
+    ///
+    /// Window offsets are made relative to it (see `normalize_window`) so that they can be compared with the grid.
+    origin: (i32, i32),
 }
 
 impl Workspace {
@@ -20,9 +26,46 @@ impl Workspace {
             monitor_grid,
             workspace_width,
             workspace_height,
+            origin: (0, 0),
+        }
+    }
+
+    /// Sets where the workspace's top-left corner is in the screen's coordinates (see `origin`).
+    pub fn with_origin(self, origin: (i32, i32)) -> Self {
+        Workspace { origin, ..self }
+    }
+
+    pub fn origin(&self) -> (i32, i32) {
+        self.origin
+    }
+
+    /// Makes the window's offsets relative to the workspace's origin rather than the screen's.
+    ///
+    /// Windows whose geometry isn't known are kept as-is.
+    pub fn normalize_window(&self, window: Window) -> Window {
+        if !window.has_geometry() {
+            return window;
+        }
+
+        Window {
+            x_offset: window.x_offset - self.origin.0,
+            y_offset: window.y_offset - self.origin.1,
+            ..window
         }
     }
 
+    /// Converts a position relative to the workspace (e.g. a monitor's origin) back into the screen's coordinates,
+    /// e.g. for moving a window there.
+    pub fn to_screen_position(&self, x: i32, y: i32) -> (i32, i32) {
+        (x + self.origin.0, y + self.origin.1)
+    }
+
+    /// Converts a position in the screen's coordinates (e.g. the mouse pointer's) into one relative to the workspace;
+    /// the inverse of `to_screen_position`.
+    pub fn from_screen_position(&self, x: i32, y: i32) -> (i32, i32) {
+        (x - self.origin.0, y - self.origin.1)
+    }
+
     pub fn is_window_in_current_workspace(&self, window: &Window) -> bool {
         // Can find the windows in the current workspace by looking at the x and y offsets.
         //
@@ -39,6 +82,18 @@ impl Workspace {
             && window.y_offset < self.workspace_height
     }
 
+    /// The width of the workspace (in pixels), i.e. of all of the columns of monitors side by side.
+    pub fn width(&self) -> i32 {
+        self.workspace_width
+    }
+
+    /// Whether the window has been placed off of the workspace (i.e. to the left of or above it).
+    ///
+    /// Windows whose geometry isn't known never are.
+    pub fn is_window_offscreen(&self, window: &Window) -> bool {
+        window.has_geometry() && (window.x_offset < 0 || window.y_offset < 0)
+    }
+
     fn calculate_workspace_size(monitor_grid: &MonitorGrid) -> (i32, i32) {
         let mut workspace_width = 0;
         let mut workspace_height = 0;
@@ -48,10 +103,10 @@ impl Workspace {
             let mut max_column_width = 0;
 
             for monitor in column {
-                column_height += monitor.height;
+                column_height += monitor.scaled_height();
 
-                if monitor.width > max_column_width {
-                    max_column_width = monitor.width;
+                if monitor.scaled_width() > max_column_width {
+                    max_column_width = monitor.scaled_width();
                 }
             }
 
@@ -115,6 +170,23 @@ mod tests {
             assert_eq!(workspace_height, 1080);
         }
 
+        #[test]
+        fn test_scaled_arrangement() {
+            let monitor_grid = MonitorGrid(vec![
+                vec![Monitor {
+                    scale: 2.0,
+                    ..Monitor::new(1920, 1080)
+                }],
+                vec![Monitor::new(1440, 2560)],
+            ]);
+
+            let (workspace_width, workspace_height) =
+                Workspace::calculate_workspace_size(&monitor_grid);
+
+            assert_eq!(workspace_width, 3840 + 1440);
+            assert_eq!(workspace_height, 2560);
+        }
+
         #[test]
         fn test_empty_arrangement() {
             let monitor_grid = MonitorGrid(vec![]);
@@ -162,6 +234,79 @@ mod tests {
         }
     }
 
+    mod origin {
+        use super::*;
+        use crate::models::{Window, WindowId, UNKNOWN_GEOMETRY};
+
+        fn create_workspace() -> Workspace {
+            Workspace::new(MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ]))
+            .with_origin((-1920, 0))
+        }
+
+        fn create_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_defaults_to_zero() {
+            let workspace = Workspace::new(MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]));
+
+            assert_eq!(workspace.origin(), (0, 0));
+            assert_eq!(workspace.to_screen_position(100, 24), (100, 24));
+        }
+
+        #[test]
+        fn test_normalize_window() {
+            let workspace = create_workspace();
+
+            // A window on the monitor left of the screen's origin is on the workspace's first monitor.
+            let window = workspace.normalize_window(create_window(-1800, 24));
+            assert_eq!((window.x_offset, window.y_offset), (120, 24));
+            assert!(workspace.is_window_in_current_workspace(&window));
+
+            let window = workspace.normalize_window(create_window(100, 24));
+            assert_eq!((window.x_offset, window.y_offset), (2020, 24));
+            assert!(workspace.is_window_in_current_workspace(&window));
+        }
+
+        #[test]
+        fn test_normalize_window_without_geometry() {
+            let window = create_workspace().normalize_window(Window {
+                width: UNKNOWN_GEOMETRY,
+                height: UNKNOWN_GEOMETRY,
+                ..create_window(UNKNOWN_GEOMETRY, UNKNOWN_GEOMETRY)
+            });
+
+            assert_eq!(
+                (window.x_offset, window.y_offset),
+                (UNKNOWN_GEOMETRY, UNKNOWN_GEOMETRY)
+            );
+        }
+
+        #[test]
+        fn test_to_screen_position() {
+            assert_eq!(create_workspace().to_screen_position(1920, 0), (0, 0));
+            assert_eq!(create_workspace().to_screen_position(0, 24), (-1920, 24));
+        }
+
+        #[test]
+        fn test_from_screen_position() {
+            assert_eq!(create_workspace().from_screen_position(0, 0), (1920, 0));
+            assert_eq!(create_workspace().from_screen_position(-1920, 24), (0, 24));
+        }
+    }
+
     mod is_window_in_current_workspace {
         use super::*;
         use crate::models::{Window, WindowId};
@@ -183,6 +328,9 @@ mod tests {
                 height: 600,
                 window_class: "test".to_string(),
                 title: "Test Window".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
             }
         }
 
@@ -261,4 +409,38 @@ mod tests {
             assert!(!workspace.is_window_in_current_workspace(&window));
         }
     }
+
+    mod is_window_offscreen {
+        use super::*;
+        use crate::models::{Window, WindowId};
+
+        fn create_test_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "test".to_string(),
+                "Test Window".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_negative_offsets() {
+            let workspace = Workspace::new(MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]));
+
+            assert!(workspace.is_window_offscreen(&create_test_window(-100, 100)));
+            assert!(workspace.is_window_offscreen(&create_test_window(100, -100)));
+            assert!(workspace.is_window_offscreen(&create_test_window(-100, -100)));
+        }
+
+        #[test]
+        fn test_onscreen() {
+            let workspace = Workspace::new(MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]));
+
+            assert!(!workspace.is_window_offscreen(&create_test_window(0, 0)));
+            assert!(!workspace.is_window_offscreen(&create_test_window(100, 100)));
+        }
+    }
 }
@@ -0,0 +1,777 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::external_tools::xrandr;
+use crate::models::{FocusDirection, MonitorSelector, Region, SortOrder, WindowId, Workspace};
+use crate::services::window_focuser;
+
+const SOCKET_NAME: &str = "easy-window-switcher.sock";
+
+/// A command sent to the daemon, one per connection, as a single line of text (e.g. `direction left` or `monitor 2`).
+///
+/// Window classes can contain spaces (e.g. `raise-group Google Chrome`), so everything after the command name is taken
+/// as the class as-is.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Request {
+    Direction {
+        direction: FocusDirection,
+        maximize: bool,
+        current_monitor_only: bool,
+    },
+    Monitor {
+        monitor: MonitorSelector,
+        maximize: bool,
+        reverse: bool,
+        nth: Option<usize>,
+    },
+    MonitorRelative(FocusDirection),
+    Primary,
+    Focus(WindowId),
+    Largest,
+    Dominant,
+    Oldest,
+    Region(Region),
+    Flip,
+    RaiseGroup(String),
+    Flash(String),
+    PeekToggle(String),
+    NextSameClass(FocusDirection),
+    CycleMonitor(FocusDirection),
+    SoloMonitor,
+    UnsoloMonitor,
+    TileMonitor,
+    Center,
+    MoveDirection(FocusDirection),
+    Cycle(SortOrder),
+}
+
+impl Request {
+    pub fn try_from_string(value: &str) -> Result<Self> {
+        if let Some((name, class)) = value.trim().split_once(' ') {
+            match name {
+                "raise-group" => return Ok(Request::RaiseGroup(class.to_owned())),
+                "flash" => return Ok(Request::Flash(class.to_owned())),
+                "peek-toggle" => return Ok(Request::PeekToggle(class.to_owned())),
+                _ => {}
+            }
+        }
+
+        let parts = value.split_whitespace().collect::<Vec<&str>>();
+
+        // Only focusing commands accept trailing flags, e.g. `--maximize`.
+        let flag_count = parts
+            .iter()
+            .rev()
+            .take_while(|part| part.starts_with("--"))
+            .count();
+        let (parts, flags) = parts.split_at(parts.len() - flag_count);
+
+        let allowed_flags: &[&str] = match parts {
+            ["direction", _] => &["--maximize", "--current-monitor-only"],
+            ["monitor", _] => &["--maximize", "--reverse", "--nth="],
+            _ => &[],
+        };
+
+        // Flags that take a value (i.e. the ones ending in `=`) are written as e.g. `--nth=2`.
+        let is_allowed = |flag: &&str| {
+            allowed_flags.iter().any(|allowed| {
+                flag == allowed || (allowed.ends_with('=') && flag.starts_with(allowed))
+            })
+        };
+
+        if !flags.iter().all(is_allowed) {
+            return Err(anyhow::anyhow!("Invalid request: {}", value.trim()));
+        }
+
+        let maximize = flags.contains(&"--maximize");
+
+        match parts {
+            ["direction", direction] => Ok(Request::Direction {
+                direction: FocusDirection::try_from(*direction)?,
+                maximize,
+                current_monitor_only: flags.contains(&"--current-monitor-only"),
+            }),
+            ["monitor", monitor] => Ok(Request::Monitor {
+                monitor: MonitorSelector::from_string(monitor),
+                maximize,
+                reverse: flags.contains(&"--reverse"),
+                nth: flags
+                    .iter()
+                    .find_map(|flag| flag.strip_prefix("--nth="))
+                    .map(|nth| {
+                        nth.parse::<usize>()
+                            .map_err(|_| anyhow::anyhow!("Invalid request: {}", value.trim()))
+                    })
+                    .transpose()?,
+            }),
+            ["monitor-relative", direction] => Ok(Request::MonitorRelative(
+                FocusDirection::try_from(*direction)?,
+            )),
+            ["primary"] => Ok(Request::Primary),
+            ["focus", id] => Ok(Request::Focus(id.parse()?)),
+            ["largest"] => Ok(Request::Largest),
+            ["dominant"] => Ok(Request::Dominant),
+            ["oldest"] => Ok(Request::Oldest),
+            ["region", region] => Ok(Request::Region(Region::try_from(*region)?)),
+            ["flip"] => Ok(Request::Flip),
+            ["next-same-class", direction] => Ok(Request::NextSameClass(FocusDirection::try_from(
+                *direction,
+            )?)),
+            ["cycle-monitor", direction] => {
+                Ok(Request::CycleMonitor(FocusDirection::try_from(*direction)?))
+            }
+            ["solo-monitor"] => Ok(Request::SoloMonitor),
+            ["unsolo-monitor"] => Ok(Request::UnsoloMonitor),
+            ["tile-monitor"] => Ok(Request::TileMonitor),
+            ["center"] => Ok(Request::Center),
+            ["move-direction", direction] => Ok(Request::MoveDirection(FocusDirection::try_from(
+                *direction,
+            )?)),
+            ["cycle", sort_order] => Ok(Request::Cycle(SortOrder::try_from(*sort_order)?)),
+            _ => Err(anyhow::anyhow!("Invalid request: {}", value.trim())),
+        }
+    }
+
+    /// Serializes the request into the line that `try_from_string` parses.
+    pub fn to_line(&self) -> String {
+        let maximize_flag = |maximize: &bool| if *maximize { " --maximize" } else { "" };
+        let direction_name = |direction: &FocusDirection| match direction {
+            FocusDirection::Left => "left",
+            FocusDirection::Right => "right",
+            FocusDirection::Up => "up",
+            FocusDirection::Down => "down",
+        };
+
+        match self {
+            Request::Direction {
+                direction,
+                maximize,
+                current_monitor_only,
+            } => format!(
+                "direction {}{}{}",
+                direction_name(direction),
+                maximize_flag(maximize),
+                if *current_monitor_only {
+                    " --current-monitor-only"
+                } else {
+                    ""
+                }
+            ),
+            Request::Monitor {
+                monitor,
+                maximize,
+                reverse,
+                nth,
+            } => format!(
+                "monitor {monitor}{}{}{}",
+                maximize_flag(maximize),
+                if *reverse { " --reverse" } else { "" },
+                nth.map_or(String::new(), |nth| format!(" --nth={nth}"))
+            ),
+            Request::MonitorRelative(direction) => {
+                format!("monitor-relative {}", direction_name(direction))
+            }
+            Request::Primary => "primary".to_owned(),
+            Request::Focus(id) => format!("focus {}", id.to_hex_string()),
+            Request::Largest => "largest".to_owned(),
+            Request::Dominant => "dominant".to_owned(),
+            Request::Oldest => "oldest".to_owned(),
+            Request::Region(region) => format!("region {region}"),
+            Request::Flip => "flip".to_owned(),
+            Request::RaiseGroup(class) => format!("raise-group {class}"),
+            Request::Flash(class) => format!("flash {class}"),
+            Request::PeekToggle(class) => format!("peek-toggle {class}"),
+            Request::NextSameClass(direction) => {
+                format!("next-same-class {}", direction_name(direction))
+            }
+            Request::CycleMonitor(direction) => {
+                format!("cycle-monitor {}", direction_name(direction))
+            }
+            Request::SoloMonitor => "solo-monitor".to_owned(),
+            Request::UnsoloMonitor => "unsolo-monitor".to_owned(),
+            Request::TileMonitor => "tile-monitor".to_owned(),
+            Request::Center => "center".to_owned(),
+            Request::MoveDirection(direction) => {
+                format!("move-direction {}", direction_name(direction))
+            }
+            Request::Cycle(sort_order) => format!("cycle {}", sort_order.as_str()),
+        }
+    }
+}
+
+/// Runs the daemon, executing requests from the socket until the process is killed.
+pub fn run(config: &Config) -> Result<()> {
+    let socket_path = get_socket_path(config);
+    let listener = bind(&socket_path)?;
+    let mut workspace_cache = WorkspaceCache::default();
+
+    log::info!("Listening on {}", socket_path.display());
+
+    for stream in listener.incoming() {
+        let result = stream.map_err(anyhow::Error::from).and_then(|stream| {
+            handle_connection(stream, |request| {
+                let summary = xrandr::get_active_monitors_summary()?;
+                let workspace =
+                    workspace_cache.get(summary, || window_focuser::load_workspace(config))?;
+
+                execute(request, workspace, config)
+            })
+        });
+
+        if let Err(error) = result {
+            log::warn!("Failed to handle connection: {error:#}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Forwards the request to the running daemon, returning any error that it reports.
+pub fn send(request: &Request, config: &Config) -> Result<()> {
+    send_to(&get_socket_path(config), request)
+}
+
+pub fn get_socket_path(config: &Config) -> PathBuf {
+    config
+        .socket_path
+        .clone()
+        .unwrap_or_else(|| match env::var_os("XDG_RUNTIME_DIR") {
+            Some(runtime_dir) => PathBuf::from(runtime_dir).join(SOCKET_NAME),
+            // Unlike the runtime dir, the temp dir is shared between users, so each one gets their own socket.
+            None => env::temp_dir().join(get_shared_socket_name(get_uid())),
+        })
+}
+
+fn get_shared_socket_name(uid: u32) -> String {
+    format!("ews-{uid}.sock")
+}
+
+fn get_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and always succeeds.
+    unsafe { libc::getuid() }
+}
+
+fn execute(request: Request, workspace: &Workspace, config: &Config) -> Result<()> {
+    match request {
+        Request::Direction {
+            direction,
+            maximize,
+            current_monitor_only,
+        } => window_focuser::focus_by_direction_in(
+            workspace,
+            direction,
+            &Config {
+                current_monitor_only: config.current_monitor_only || current_monitor_only,
+                ..with_maximize(config, maximize)
+            },
+        ),
+        Request::Monitor {
+            monitor,
+            maximize,
+            reverse,
+            nth,
+        } => window_focuser::focus_by_monitor_in(
+            workspace,
+            &monitor,
+            &Config {
+                pick_last_window: config.pick_last_window || reverse,
+                nth_window: nth.or(config.nth_window),
+                ..with_maximize(config, maximize)
+            },
+        ),
+        Request::MonitorRelative(direction) => {
+            window_focuser::focus_relative_monitor_in(workspace, direction, config)
+        }
+        Request::Primary => window_focuser::focus_primary_in(workspace, config),
+        Request::Focus(id) => window_focuser::focus_by_id_in(workspace, &id, config),
+        Request::Largest => window_focuser::focus_largest_in(workspace, config),
+        Request::Dominant => window_focuser::focus_dominant_in(workspace, config),
+        Request::Oldest => window_focuser::focus_oldest_in(workspace, config),
+        Request::Region(region) => window_focuser::focus_region_in(workspace, &region, config),
+        Request::Flip => window_focuser::flip_in(workspace, config),
+        Request::RaiseGroup(class) => window_focuser::raise_group_in(workspace, &class, config),
+        Request::Flash(class) => window_focuser::flash_in(workspace, &class, config),
+        Request::PeekToggle(class) => window_focuser::peek_toggle_in(workspace, &class, config),
+        Request::NextSameClass(direction) => {
+            window_focuser::next_same_class_in(workspace, direction, config)
+        }
+        Request::CycleMonitor(direction) => {
+            window_focuser::cycle_monitor_in(workspace, direction, config)
+        }
+        Request::SoloMonitor => window_focuser::solo_monitor_in(workspace, config),
+        Request::UnsoloMonitor => window_focuser::unsolo_monitor_in(workspace, config),
+        Request::TileMonitor => window_focuser::tile_monitor_in(workspace, config),
+        Request::Center => window_focuser::center_in(workspace, config),
+        Request::MoveDirection(direction) => {
+            window_focuser::move_by_direction_in(workspace, direction, config)
+        }
+        Request::Cycle(sort_order) => window_focuser::cycle_in(workspace, &sort_order, config),
+    }
+}
+
+fn with_maximize(config: &Config, maximize: bool) -> Config {
+    Config {
+        maximize: config.maximize || maximize,
+        ..config.clone()
+    }
+}
+
+/// Binds the socket, cleaning up a stale one left behind by a daemon that didn't exit cleanly.
+///
+/// Only the current user can connect to it, since anyone who can could focus and move their windows.
+fn bind(socket_path: &Path) -> Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(anyhow::anyhow!(
+                "A daemon is already listening on {}",
+                socket_path.display()
+            ));
+        }
+
+        fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind socket: {}", socket_path.display()))?;
+
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600)).with_context(|| {
+        format!(
+            "Failed to restrict the socket's permissions: {}",
+            socket_path.display()
+        )
+    })?;
+
+    Ok(listener)
+}
+
+/// Reads a single request from the connection, handles it, and replies with either `ok` or `error: <reason>`.
+fn handle_connection(stream: UnixStream, handle: impl FnOnce(Request) -> Result<()>) -> Result<()> {
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+
+    let response = match Request::try_from_string(&line).and_then(handle) {
+        Ok(()) => "ok".to_owned(),
+        Err(error) => format!("error: {error:#}"),
+    };
+
+    writeln!(&stream, "{response}")?;
+    Ok(())
+}
+
+fn send_to(socket_path: &Path, request: &Request) -> Result<()> {
+    let stream = UnixStream::connect(socket_path).with_context(|| {
+        format!(
+            "Failed to connect to the daemon at {}; is it running?",
+            socket_path.display()
+        )
+    })?;
+
+    writeln!(&stream, "{}", request.to_line())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut response = String::new();
+    BufReader::new(&stream).read_line(&mut response)?;
+
+    match response.trim() {
+        "ok" => Ok(()),
+        response => Err(anyhow::anyhow!(
+            "{}",
+            response.strip_prefix("error: ").unwrap_or(response)
+        )),
+    }
+}
+
+/// Keeps the workspace around between requests, only loading it again when the summary of the active monitors (see
+/// `xrandr::get_active_monitors_summary`) changes.
+#[derive(Default)]
+struct WorkspaceCache {
+    summary: String,
+    workspace: Option<Workspace>,
+}
+
+impl WorkspaceCache {
+    fn get(
+        &mut self,
+        summary: String,
+        load_workspace: impl FnOnce() -> Result<Workspace>,
+    ) -> Result<&Workspace> {
+        if self.workspace.is_none() || summary != self.summary {
+            self.workspace = Some(load_workspace()?);
+            self.summary = summary;
+        }
+
+        Ok(self.workspace.as_ref().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MonitorIndex;
+
+    mod request {
+        use super::*;
+
+        #[test]
+        fn test_parse_requests() {
+            assert_eq!(
+                Request::try_from_string("direction left\n").unwrap(),
+                Request::Direction {
+                    direction: FocusDirection::Left,
+                    maximize: false,
+                    current_monitor_only: false
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("monitor 2").unwrap(),
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(2)),
+                    maximize: false,
+                    reverse: false,
+                    nth: None
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("monitor 2 --maximize").unwrap(),
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(2)),
+                    maximize: true,
+                    reverse: false,
+                    nth: None
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("monitor DP-2 --reverse").unwrap(),
+                Request::Monitor {
+                    monitor: MonitorSelector::Name("DP-2".to_owned()),
+                    maximize: false,
+                    reverse: true,
+                    nth: None
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("monitor 1 --nth=2 --maximize").unwrap(),
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(1)),
+                    maximize: true,
+                    reverse: false,
+                    nth: Some(2)
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("direction right --current-monitor-only --maximize")
+                    .unwrap(),
+                Request::Direction {
+                    direction: FocusDirection::Right,
+                    maximize: true,
+                    current_monitor_only: true
+                }
+            );
+            assert_eq!(
+                Request::try_from_string("largest").unwrap(),
+                Request::Largest
+            );
+            assert_eq!(
+                Request::try_from_string("raise-group code").unwrap(),
+                Request::RaiseGroup("code".to_owned())
+            );
+            assert_eq!(
+                Request::try_from_string("flash Slack").unwrap(),
+                Request::Flash("Slack".to_owned())
+            );
+            assert_eq!(
+                Request::try_from_string("peek-toggle code").unwrap(),
+                Request::PeekToggle("code".to_owned())
+            );
+        }
+
+        #[test]
+        fn test_parse_invalid_requests() {
+            assert!(Request::try_from_string("").is_err());
+            assert!(Request::try_from_string("direction").is_err());
+            assert!(Request::try_from_string("direction diagonal").is_err());
+            assert!(Request::try_from_string("monitor").is_err());
+            assert!(Request::try_from_string("largest window").is_err());
+            assert!(Request::try_from_string("restart").is_err());
+            assert!(Request::try_from_string("largest --maximize").is_err());
+            assert!(Request::try_from_string("monitor 2 --current-monitor-only").is_err());
+            assert!(Request::try_from_string("monitor 2 --nth=two").is_err());
+            assert!(Request::try_from_string("monitor 2 --nth").is_err());
+            assert!(Request::try_from_string("direction left --nth=1").is_err());
+            assert!(Request::try_from_string("direction left --raise").is_err());
+            assert!(Request::try_from_string("direction left --reverse").is_err());
+            assert!(Request::try_from_string("cycle").is_err());
+            assert!(Request::try_from_string("cycle size").is_err());
+            assert!(Request::try_from_string("raise-group").is_err());
+            assert!(Request::try_from_string("flash\n").is_err());
+        }
+
+        #[test]
+        fn test_to_line_round_trip() {
+            let requests = [
+                Request::Direction {
+                    direction: FocusDirection::Up,
+                    maximize: false,
+                    current_monitor_only: false,
+                },
+                Request::Direction {
+                    direction: FocusDirection::Down,
+                    maximize: true,
+                    current_monitor_only: false,
+                },
+                Request::Direction {
+                    direction: FocusDirection::Left,
+                    maximize: false,
+                    current_monitor_only: true,
+                },
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(1)),
+                    maximize: false,
+                    reverse: false,
+                    nth: None,
+                },
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(0)),
+                    maximize: true,
+                    reverse: false,
+                    nth: None,
+                },
+                Request::Monitor {
+                    monitor: MonitorSelector::Name("DP-2".to_owned()),
+                    maximize: false,
+                    reverse: false,
+                    nth: None,
+                },
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(3)),
+                    maximize: true,
+                    reverse: true,
+                    nth: None,
+                },
+                Request::Monitor {
+                    monitor: MonitorSelector::Index(MonitorIndex(1)),
+                    maximize: false,
+                    reverse: true,
+                    nth: Some(2),
+                },
+                Request::MonitorRelative(FocusDirection::Left),
+                Request::Primary,
+                Request::Focus(WindowId(0x05000006)),
+                Request::Largest,
+                Request::Dominant,
+                Request::Oldest,
+                Request::Region(Region { start: 25, end: 75 }),
+                Request::Flip,
+                Request::RaiseGroup("code".to_owned()),
+                Request::Flash("Slack".to_owned()),
+                Request::PeekToggle("code".to_owned()),
+                Request::RaiseGroup("Google Chrome".to_owned()),
+                Request::Flash("Visual Studio  Code".to_owned()),
+                Request::PeekToggle("code --maximize".to_owned()),
+                Request::NextSameClass(FocusDirection::Left),
+                Request::CycleMonitor(FocusDirection::Right),
+                Request::SoloMonitor,
+                Request::UnsoloMonitor,
+                Request::TileMonitor,
+                Request::Center,
+                Request::MoveDirection(FocusDirection::Right),
+                Request::Cycle(SortOrder::Position),
+                Request::Cycle(SortOrder::Mru),
+            ];
+
+            for request in requests {
+                assert_eq!(
+                    Request::try_from_string(&request.to_line()).unwrap(),
+                    request
+                );
+            }
+        }
+    }
+
+    mod socket {
+        use super::*;
+        use std::thread;
+
+        fn create_socket_path(name: &str) -> PathBuf {
+            let socket_path =
+                env::temp_dir().join(format!("ews-test-{}-{name}.sock", std::process::id()));
+
+            let _ = fs::remove_file(&socket_path);
+            socket_path
+        }
+
+        /// Serves a single connection with the given handler, returning the request that it received.
+        fn serve_once(
+            socket_path: &Path,
+            result: Result<()>,
+        ) -> thread::JoinHandle<Option<Request>> {
+            let listener = bind(socket_path).unwrap();
+
+            thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                let mut received = None;
+
+                handle_connection(stream, |request| {
+                    received = Some(request);
+                    result
+                })
+                .unwrap();
+
+                received
+            })
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let socket_path = create_socket_path("round-trip");
+            let server = serve_once(&socket_path, Ok(()));
+
+            let request = Request::Direction {
+                direction: FocusDirection::Left,
+                maximize: false,
+                current_monitor_only: false,
+            };
+
+            send_to(&socket_path, &request).unwrap();
+
+            assert_eq!(server.join().unwrap(), Some(request));
+
+            fs::remove_file(&socket_path).unwrap();
+        }
+
+        #[test]
+        fn test_round_trip_error() {
+            let socket_path = create_socket_path("round-trip-error");
+            let server = serve_once(&socket_path, Err(anyhow::anyhow!("No window found")));
+
+            let request = Request::Monitor {
+                monitor: MonitorSelector::Index(MonitorIndex(2)),
+                maximize: true,
+                reverse: false,
+                nth: None,
+            };
+
+            let error = send_to(&socket_path, &request).unwrap_err();
+
+            assert_eq!(error.to_string(), "No window found");
+            assert_eq!(server.join().unwrap(), Some(request));
+
+            fs::remove_file(&socket_path).unwrap();
+        }
+
+        #[test]
+        fn test_send_without_daemon() {
+            let socket_path = create_socket_path("no-daemon");
+            assert!(send_to(&socket_path, &Request::Largest).is_err());
+        }
+
+        #[test]
+        fn test_bind_replaces_stale_socket() {
+            let socket_path = create_socket_path("stale");
+
+            // Dropping the listener leaves the socket file behind without anything listening on it.
+            drop(bind(&socket_path).unwrap());
+            assert!(socket_path.exists());
+
+            let listener = bind(&socket_path).unwrap();
+            assert!(bind(&socket_path).is_err());
+
+            drop(listener);
+            fs::remove_file(&socket_path).unwrap();
+        }
+
+        #[test]
+        fn test_bind_restricts_permissions() {
+            let socket_path = create_socket_path("permissions");
+            let listener = bind(&socket_path).unwrap();
+
+            let mode = fs::metadata(&socket_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            drop(listener);
+            fs::remove_file(&socket_path).unwrap();
+        }
+
+        #[test]
+        fn test_shared_socket_name() {
+            assert_eq!(get_shared_socket_name(1000), "ews-1000.sock");
+            assert_ne!(get_shared_socket_name(0), get_shared_socket_name(1000));
+        }
+    }
+
+    mod workspace_cache {
+        use super::*;
+        use crate::models::{Monitor, MonitorGrid};
+        use std::cell::Cell;
+
+        fn create_workspace(monitor_count: usize) -> Workspace {
+            let columns = (0..monitor_count)
+                .map(|_| vec![Monitor::new(1920, 1080)])
+                .collect();
+
+            Workspace::new(MonitorGrid::new(columns).unwrap())
+        }
+
+        fn get_monitor_count(
+            cache: &mut WorkspaceCache,
+            summary: &str,
+            monitor_count: usize,
+            load_count: &Cell<usize>,
+        ) -> i32 {
+            cache
+                .get(summary.to_owned(), || {
+                    load_count.set(load_count.get() + 1);
+                    Ok(create_workspace(monitor_count))
+                })
+                .unwrap()
+                .monitor_grid
+                .calculate_monitor_count()
+        }
+
+        #[test]
+        fn test_reloads_only_on_change() {
+            let mut cache = WorkspaceCache::default();
+            let load_count = Cell::new(0);
+
+            assert_eq!(
+                get_monitor_count(&mut cache, "Monitors: 1", 1, &load_count),
+                1
+            );
+            assert_eq!(
+                get_monitor_count(&mut cache, "Monitors: 1", 2, &load_count),
+                1
+            );
+            assert_eq!(load_count.get(), 1);
+
+            assert_eq!(
+                get_monitor_count(&mut cache, "Monitors: 2", 2, &load_count),
+                2
+            );
+            assert_eq!(load_count.get(), 2);
+        }
+
+        #[test]
+        fn test_retries_after_error() {
+            let mut cache = WorkspaceCache::default();
+            let load_count = Cell::new(0);
+
+            assert!(cache
+                .get("Monitors: 1".to_owned(), || Err(anyhow::anyhow!(
+                    "xrandr failed"
+                )))
+                .is_err());
+            assert_eq!(
+                get_monitor_count(&mut cache, "Monitors: 1", 1, &load_count),
+                1
+            );
+            assert_eq!(load_count.get(), 1);
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use anyhow::Result;
+
+use crate::config::{Backend, Config};
+use crate::external_tools::{get_tool_version, i3, wmctrl, xprop, xrandr};
+use crate::models::{Window, Workspace};
+use crate::services::window_focuser;
+
+/// The outcome of a single check.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    /// Something that's likely to cause problems (or disables an optional feature), but doesn't break everything.
+    Warn,
+    Fail,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub status: Status,
+    /// What was checked (e.g. "wmctrl" or "monitors").
+    pub name: String,
+    pub message: String,
+}
+
+impl Check {
+    fn new(status: Status, name: &str, message: impl Into<String>) -> Self {
+        Check {
+            status,
+            name: name.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks whether the external tools are installed and whether the monitors and windows are detected correctly.
+pub fn run(config: &Config) -> Vec<Check> {
+    let mut checks = vec![
+        check_tool("wmctrl", get_tool_version("wmctrl", "--version"), true),
+        check_tool("xdotool", get_tool_version("xdotool", "--version"), true),
+        check_tool("xrandr", get_tool_version("xrandr", "--version"), true),
+        // xprop doesn't understand `--version`, only `-version`.
+        check_tool("xprop", get_tool_version("xprop", "-version"), false),
+    ];
+
+    if config.backend == Backend::I3 {
+        checks.push(check_tool(
+            "i3-msg",
+            get_tool_version("i3-msg", "--version"),
+            true,
+        ));
+    }
+
+    if config.notify {
+        checks.push(check_tool(
+            "notify-send",
+            get_tool_version("notify-send", "--version"),
+            false,
+        ));
+    }
+
+    let workspace = match config.backend {
+        Backend::X11 => xrandr::parse_workspace(),
+        Backend::I3 => i3::parse_workspace(),
+    }
+    .and_then(|workspace| window_focuser::normalize_workspace(workspace, config));
+
+    checks.push(check_workspace(&workspace));
+
+    let (windows, parse_errors) = match config.backend {
+        Backend::X11 => {
+            let parse_errors = wmctrl::get_raw_windows_config()
+                .map(|windows_config| {
+                    wmctrl::parse_windows_config_lenient(
+                        &windows_config,
+                        config,
+                        xprop::is_installed(),
+                    )
+                    .errors
+                })
+                .unwrap_or_default();
+
+            (wmctrl::get_windows_config(config), parse_errors)
+        }
+        Backend::I3 => (i3::get_windows(), Vec::new()),
+    };
+
+    let windows = match &workspace {
+        Ok(workspace) => windows.map(|windows| {
+            windows
+                .into_iter()
+                .map(|window| workspace.normalize_window(window))
+                .collect()
+        }),
+        Err(_) => windows,
+    };
+
+    checks.extend(check_windows(
+        &windows,
+        workspace.as_ref().ok(),
+        &parse_errors,
+    ));
+
+    checks
+}
+
+pub fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|check| check.status == Status::Fail)
+}
+
+/// Formats the checks one per line, e.g. `PASS xdotool: xdotool version 3.20160805.1`.
+///
+/// Any further lines of a check's message are indented underneath it.
+pub fn format_report(checks: &[Check]) -> String {
+    checks
+        .iter()
+        .map(|check| {
+            format!(
+                "{} {}: {}",
+                check.status.as_str(),
+                check.name,
+                check.message.replace('\n', "\n     ")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Checks that the tool is installed, given its version (`None` meaning it isn't installed).
+fn check_tool(tool: &str, version: Option<String>, required: bool) -> Check {
+    match version {
+        Some(version) if version.is_empty() => Check::new(Status::Pass, tool, "installed"),
+        Some(version) => Check::new(Status::Pass, tool, format!("installed ({version})")),
+        None if required => Check::new(Status::Fail, tool, "not installed"),
+        None => Check::new(
+            Status::Warn,
+            tool,
+            "not installed (optional; the features that use it are disabled)",
+        ),
+    }
+}
+
+fn check_workspace(workspace: &Result<Workspace>) -> Check {
+    let workspace = match workspace {
+        Ok(workspace) => workspace,
+        Err(error) => {
+            return Check::new(
+                Status::Fail,
+                "monitors",
+                format!("failed to detect the monitors: {error:#}"),
+            );
+        }
+    };
+
+    let monitor_grid = &workspace.monitor_grid;
+    let monitor_count = monitor_grid.calculate_monitor_count();
+
+    if monitor_count == 0 {
+        return Check::new(Status::Fail, "monitors", "no monitors were detected");
+    }
+
+    // E.g. "[1920x1080, 1920x1080] [3440x1440]" for a stack of two monitors next to a single one.
+    let columns = monitor_grid
+        .0
+        .iter()
+        .map(|column| {
+            let monitors = column
+                .iter()
+                .map(|monitor| format!("{}x{}", monitor.width, monitor.height))
+                .collect::<Vec<String>>();
+
+            format!("[{}]", monitors.join(", "))
+        })
+        .collect::<Vec<String>>();
+
+    Check::new(
+        Status::Pass,
+        "monitors",
+        format!(
+            "detected {monitor_count} monitor(s) in {} column(s): {}",
+            columns.len(),
+            columns.join(" ")
+        ),
+    )
+}
+
+/// Checks the windows, flagging when there aren't any (on the detected monitors) or some couldn't be parsed.
+///
+/// `parse_errors` are the lines of `wmctrl`'s output that couldn't be parsed, along with why.
+fn check_windows(
+    windows: &Result<Vec<Window>>,
+    workspace: Option<&Workspace>,
+    parse_errors: &[(String, String)],
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    match windows {
+        Err(error) => checks.push(Check::new(
+            Status::Fail,
+            "windows",
+            format!("failed to list the windows: {error:#}"),
+        )),
+        Ok(windows) if windows.is_empty() => {
+            checks.push(Check::new(Status::Warn, "windows", "no windows were found"))
+        }
+        Ok(windows) => {
+            checks.push(Check::new(
+                Status::Pass,
+                "windows",
+                format!("found {} window(s)", windows.len()),
+            ));
+
+            if let Some(workspace) = workspace {
+                let current_workspace_count = windows
+                    .iter()
+                    .filter(|window| workspace.is_window_in_current_workspace(window))
+                    .count();
+
+                if current_workspace_count == 0 {
+                    checks.push(Check::new(
+                        Status::Warn,
+                        "monitor layout",
+                        "none of the windows are on the detected monitors; the detected layout might not match the actual one",
+                    ));
+                }
+            }
+        }
+    }
+
+    if !parse_errors.is_empty() {
+        let lines = parse_errors
+            .iter()
+            .map(|(line, error)| format!("{line} ({error})"))
+            .collect::<Vec<String>>();
+
+        checks.push(Check::new(
+            Status::Warn,
+            "wmctrl output",
+            format!(
+                "{} line(s) couldn't be parsed:\n{}",
+                lines.len(),
+                lines.join("\n")
+            ),
+        ));
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Monitor, MonitorGrid, WindowId};
+
+    fn create_workspace() -> Workspace {
+        Workspace::new(MonitorGrid(vec![
+            vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+            vec![Monitor::new(3440, 1440)],
+        ]))
+    }
+
+    fn create_window(x_offset: i32, y_offset: i32) -> Window {
+        Window::new(
+            WindowId(1),
+            x_offset,
+            y_offset,
+            800,
+            600,
+            "code.Code".to_string(),
+            "main.rs".to_string(),
+        )
+    }
+
+    fn get_statuses(checks: &[Check]) -> Vec<(Status, &str)> {
+        checks
+            .iter()
+            .map(|check| (check.status, check.name.as_str()))
+            .collect()
+    }
+
+    mod format_report {
+        use super::*;
+
+        #[test]
+        fn test_format_report() {
+            let checks = vec![
+                Check::new(Status::Pass, "wmctrl", "installed (1.07)"),
+                Check::new(Status::Warn, "xprop", "not installed"),
+                Check::new(Status::Fail, "monitors", "no monitors were detected"),
+            ];
+
+            assert_eq!(
+                format_report(&checks),
+                [
+                    "PASS wmctrl: installed (1.07)",
+                    "WARN xprop: not installed",
+                    "FAIL monitors: no monitors were detected",
+                ]
+                .join("\n")
+            );
+        }
+
+        #[test]
+        fn test_multiline_message() {
+            let checks = vec![Check::new(
+                Status::Warn,
+                "wmctrl output",
+                "2 line(s) couldn't be parsed:\nfoo\nbar",
+            )];
+
+            assert_eq!(
+                format_report(&checks),
+                "WARN wmctrl output: 2 line(s) couldn't be parsed:\n     foo\n     bar"
+            );
+        }
+
+        #[test]
+        fn test_no_checks() {
+            assert_eq!(format_report(&[]), "");
+        }
+    }
+
+    mod has_failures {
+        use super::*;
+
+        #[test]
+        fn test_has_failures() {
+            let mut checks = vec![
+                Check::new(Status::Pass, "wmctrl", "installed"),
+                Check::new(Status::Warn, "xprop", "not installed"),
+            ];
+
+            assert!(!has_failures(&checks));
+
+            checks.push(Check::new(Status::Fail, "xrandr", "not installed"));
+            assert!(has_failures(&checks));
+        }
+    }
+
+    mod check_tool {
+        use super::*;
+
+        #[test]
+        fn test_installed() {
+            assert_eq!(
+                check_tool("wmctrl", Some("1.07".to_owned()), true),
+                Check::new(Status::Pass, "wmctrl", "installed (1.07)")
+            );
+            assert_eq!(
+                check_tool("wmctrl", Some(String::new()), true),
+                Check::new(Status::Pass, "wmctrl", "installed")
+            );
+        }
+
+        #[test]
+        fn test_missing_required() {
+            assert_eq!(check_tool("xdotool", None, true).status, Status::Fail);
+        }
+
+        #[test]
+        fn test_missing_optional() {
+            assert_eq!(check_tool("xprop", None, false).status, Status::Warn);
+        }
+    }
+
+    mod check_workspace {
+        use super::*;
+
+        #[test]
+        fn test_detected() {
+            assert_eq!(
+                check_workspace(&Ok(create_workspace())),
+                Check::new(
+                    Status::Pass,
+                    "monitors",
+                    "detected 3 monitor(s) in 2 column(s): [1920x1080, 1920x1080] [3440x1440]"
+                )
+            );
+        }
+
+        #[test]
+        fn test_no_monitors() {
+            let check = check_workspace(&Ok(Workspace::new(MonitorGrid(vec![]))));
+            assert_eq!(check.status, Status::Fail);
+        }
+
+        #[test]
+        fn test_error() {
+            let check = check_workspace(&Err(anyhow::anyhow!("xrandr timed out")));
+
+            assert_eq!(
+                check,
+                Check::new(
+                    Status::Fail,
+                    "monitors",
+                    "failed to detect the monitors: xrandr timed out"
+                )
+            );
+        }
+    }
+
+    mod check_windows {
+        use super::*;
+
+        #[test]
+        fn test_windows_found() {
+            let workspace = create_workspace();
+            let windows = Ok(vec![create_window(0, 24), create_window(1920, 24)]);
+            let checks = check_windows(&windows, Some(&workspace), &[]);
+
+            assert_eq!(
+                checks,
+                vec![Check::new(Status::Pass, "windows", "found 2 window(s)")]
+            );
+        }
+
+        #[test]
+        fn test_no_windows() {
+            let workspace = create_workspace();
+            let checks = check_windows(&Ok(vec![]), Some(&workspace), &[]);
+
+            assert_eq!(get_statuses(&checks), vec![(Status::Warn, "windows")]);
+        }
+
+        #[test]
+        fn test_no_windows_on_detected_monitors() {
+            let workspace = create_workspace();
+            let windows = Ok(vec![create_window(-2000, 24), create_window(0, -1500)]);
+            let checks = check_windows(&windows, Some(&workspace), &[]);
+
+            assert_eq!(
+                get_statuses(&checks),
+                vec![(Status::Pass, "windows"), (Status::Warn, "monitor layout")]
+            );
+        }
+
+        #[test]
+        fn test_unparsable_lines() {
+            let windows = Ok(vec![create_window(0, 24)]);
+            let parse_errors = vec![(
+                "garbage".to_owned(),
+                "Invalid window config: garbage".to_owned(),
+            )];
+            let checks = check_windows(&windows, None, &parse_errors);
+
+            assert_eq!(
+                checks[1],
+                Check::new(
+                    Status::Warn,
+                    "wmctrl output",
+                    "1 line(s) couldn't be parsed:\ngarbage (Invalid window config: garbage)"
+                )
+            );
+        }
+
+        #[test]
+        fn test_error() {
+            let checks = check_windows(&Err(anyhow::anyhow!("wmctrl failed")), None, &[]);
+
+            assert_eq!(
+                checks,
+                vec![Check::new(
+                    Status::Fail,
+                    "windows",
+                    "failed to list the windows: wmctrl failed"
+                )]
+            );
+        }
+    }
+}
@@ -1 +1,7 @@
+pub mod daemon;
+pub mod doctor;
+pub mod monitor_cache;
+pub mod peek_cache;
+pub mod report;
+pub mod snapshot;
 pub mod window_focuser;
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::external_tools::xrandr::{self, MonitorConfig};
+use crate::models::Workspace;
+
+const CACHE_DIR_NAME: &str = "easy-window-switcher";
+const CACHE_FILE_NAME: &str = "monitors.json";
+
+/// The raw monitors config from the last full `xrandr` run, along with the summary of the monitors at that time.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct CachedMonitors {
+    summary: String,
+    raw_monitors: Vec<MonitorConfig>,
+}
+
+/// Parses the workspace from the cached monitors config, as long as the monitors haven't changed since it was cached.
+/// Otherwise, the monitors are re-detected and cached again.
+pub fn load_workspace() -> Result<Workspace> {
+    let cache_path = get_cache_path();
+    let summary = xrandr::get_active_monitors_summary()?;
+
+    if let Ok(cached_monitors) = read_json::<CachedMonitors>(&cache_path) {
+        if is_cache_valid(&cached_monitors, &summary) {
+            return xrandr::parse_workspace_from(&cached_monitors.raw_monitors);
+        }
+    }
+
+    let raw_monitors = xrandr::get_raw_monitors_config()?;
+    let workspace = xrandr::parse_workspace_from(&raw_monitors)?;
+
+    let cached_monitors = CachedMonitors {
+        summary,
+        raw_monitors,
+    };
+
+    if let Err(error) = write_json(&cache_path, &cached_monitors) {
+        log::warn!("Failed to write the monitors cache: {error:#}");
+    }
+
+    Ok(workspace)
+}
+
+/// Removes the cache so that the monitors are re-detected on the next run.
+pub fn clear() -> Result<()> {
+    let cache_path = get_cache_path();
+
+    if cache_path.exists() {
+        fs::remove_file(&cache_path)
+            .with_context(|| format!("Failed to remove {}", cache_path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn get_cache_path() -> PathBuf {
+    get_cache_dir().join(CACHE_FILE_NAME)
+}
+
+/// The directory that everything cached between runs goes in, e.g. `~/.cache/easy-window-switcher`.
+pub(crate) fn get_cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+        .join(CACHE_DIR_NAME)
+}
+
+fn is_cache_valid(cached_monitors: &CachedMonitors, current_summary: &str) -> bool {
+    !cached_monitors.raw_monitors.is_empty() && cached_monitors.summary == current_summary
+}
+
+/// Reads the JSON file (e.g. one in the cache directory). Caches can treat an error (i.e. a missing or corrupt file)
+/// as the value being absent.
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    serde_json::from_str(&contents).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+/// Writes the value to the file as JSON, creating its directory first if needed.
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string(value)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMARY: &str = "Monitors: 2\n0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0\n1: +DisplayPort-0 3440/800x1440/337+1920+540  DisplayPort-0";
+
+    fn create_cached_monitors() -> CachedMonitors {
+        CachedMonitors {
+            summary: SUMMARY.to_owned(),
+            raw_monitors: vec![
+                "DisplayPort-0 connected 3440x1440+1920+540 (normal left inverted right x axis y axis) 800mm x 337mm".to_owned(),
+                "HDMI-A-0 connected primary 1920x1080+0+1080 (normal left inverted right x axis y axis) 527mm x 296mm".to_owned(),
+            ],
+        }
+    }
+
+    mod is_cache_valid {
+        use super::*;
+
+        #[test]
+        fn test_matching_summary() {
+            assert!(is_cache_valid(&create_cached_monitors(), SUMMARY));
+        }
+
+        #[test]
+        fn test_differing_summary() {
+            let summary = "Monitors: 1\n0: +*HDMI-A-0 1920/527x1080/296+0+1080  HDMI-A-0";
+            assert!(!is_cache_valid(&create_cached_monitors(), summary));
+        }
+
+        #[test]
+        fn test_moved_monitor() {
+            let summary = SUMMARY.replace("+1920+540", "+1920+0");
+            assert!(!is_cache_valid(&create_cached_monitors(), &summary));
+        }
+
+        #[test]
+        fn test_no_cached_monitors() {
+            let cached_monitors = CachedMonitors {
+                summary: SUMMARY.to_owned(),
+                raw_monitors: vec![],
+            };
+
+            assert!(!is_cache_valid(&cached_monitors, SUMMARY));
+        }
+    }
+
+    mod read_and_write_json {
+        use super::*;
+
+        fn create_cache_path(name: &str) -> PathBuf {
+            env::temp_dir()
+                .join(format!("ews-test-{}-{name}", std::process::id()))
+                .join(CACHE_FILE_NAME)
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let cache_path = create_cache_path("round-trip");
+
+            write_json(&cache_path, &create_cached_monitors()).unwrap();
+            assert_eq!(
+                read_json::<CachedMonitors>(&cache_path).unwrap(),
+                create_cached_monitors()
+            );
+
+            fs::remove_dir_all(cache_path.parent().unwrap()).unwrap();
+        }
+
+        #[test]
+        fn test_missing_file() {
+            let cache_path = create_cache_path("missing");
+
+            assert_eq!(
+                read_json::<CachedMonitors>(&cache_path)
+                    .unwrap_err()
+                    .to_string(),
+                format!("Failed to read {}", cache_path.display())
+            );
+        }
+
+        #[test]
+        fn test_corrupt_file() {
+            let cache_path = create_cache_path("corrupt");
+
+            fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+            fs::write(&cache_path, "not json").unwrap();
+            assert_eq!(
+                read_json::<CachedMonitors>(&cache_path)
+                    .unwrap_err()
+                    .to_string(),
+                format!("Invalid JSON in {}", cache_path.display())
+            );
+
+            fs::remove_dir_all(cache_path.parent().unwrap()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::models::WindowId;
+use crate::services::monitor_cache;
+
+const CACHE_FILE_NAME: &str = "peeked.json";
+
+/// The window (if any) of each class that was kept above the others by the last `peek-toggle`, by its ID.
+type PeekedWindows = BTreeMap<String, usize>;
+
+/// Finds the window of the class that's currently peeked at, i.e. was kept above the others by the last `peek-toggle`.
+///
+/// The window might have been closed since (or its ID reused after a restart), so it's up to the caller to check that
+/// it's still around.
+pub fn get_peeked_window(class: &str) -> Option<WindowId> {
+    read_peeked_windows()
+        .get(&normalize_class(class))
+        .map(|id| WindowId(*id))
+}
+
+/// Records which window of the class is peeked at (if any), for the next `peek-toggle` to undo.
+pub fn set_peeked_window(class: &str, window_id: Option<&WindowId>) -> Result<()> {
+    let peeked_windows = with_peeked_window(read_peeked_windows(), class, window_id);
+    monitor_cache::write_json(&get_cache_path(), &peeked_windows)
+}
+
+fn get_cache_path() -> PathBuf {
+    monitor_cache::get_cache_dir().join(CACHE_FILE_NAME)
+}
+
+/// Treats a missing or corrupt cache as nothing being peeked at.
+fn read_peeked_windows() -> PeekedWindows {
+    monitor_cache::read_json(&get_cache_path()).unwrap_or_default()
+}
+
+/// Classes are matched case-insensitively, so e.g. "code" and "Code" toggle the same window.
+fn normalize_class(class: &str) -> String {
+    class.to_lowercase()
+}
+
+fn with_peeked_window(
+    mut peeked_windows: PeekedWindows,
+    class: &str,
+    window_id: Option<&WindowId>,
+) -> PeekedWindows {
+    match window_id {
+        Some(window_id) => peeked_windows.insert(normalize_class(class), window_id.0),
+        None => peeked_windows.remove(&normalize_class(class)),
+    };
+
+    peeked_windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod with_peeked_window {
+        use super::*;
+
+        #[test]
+        fn test_toggle_on_and_off() {
+            let peeked_windows = with_peeked_window(BTreeMap::new(), "Code", Some(&WindowId(1)));
+            assert_eq!(peeked_windows.get("code"), Some(&1));
+
+            let peeked_windows = with_peeked_window(peeked_windows, "code", None);
+            assert!(peeked_windows.is_empty());
+        }
+
+        #[test]
+        fn test_replaces_window() {
+            let peeked_windows = with_peeked_window(BTreeMap::new(), "code", Some(&WindowId(1)));
+            let peeked_windows = with_peeked_window(peeked_windows, "code", Some(&WindowId(2)));
+
+            assert_eq!(peeked_windows, BTreeMap::from([("code".to_owned(), 2)]));
+        }
+
+        #[test]
+        fn test_keeps_other_classes() {
+            let peeked_windows = with_peeked_window(BTreeMap::new(), "code", Some(&WindowId(1)));
+            let peeked_windows = with_peeked_window(peeked_windows, "slack", Some(&WindowId(2)));
+            let peeked_windows = with_peeked_window(peeked_windows, "code", None);
+
+            assert_eq!(peeked_windows, BTreeMap::from([("slack".to_owned(), 2)]));
+        }
+
+        #[test]
+        fn test_removing_unpeeked_class() {
+            assert!(with_peeked_window(BTreeMap::new(), "code", None).is_empty());
+        }
+    }
+}
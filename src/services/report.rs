@@ -0,0 +1,658 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::models::{MonitorGrid, MonitorIndex, Window};
+
+/// The placeholders that can be used in a `list --format` template.
+const WINDOW_TEMPLATE_FIELDS: [&str; 9] = [
+    "id", "hex_id", "x", "y", "w", "h", "class", "title", "monitor",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    Field(String),
+}
+
+/// A `list --format` template, e.g. `{hex_id} {monitor} {class} {title}`, in which each placeholder is replaced with
+/// the window's value. Use `{{` and `}}` for literal braces.
+///
+/// Unknown placeholders are an error (rather than being left as-is) so that typos don't go unnoticed in scripts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowTemplate(Vec<TemplatePart>);
+
+impl WindowTemplate {
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(character) = chars.next() {
+            match character {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(character) => field.push(character),
+                            None => {
+                                return Err(anyhow::anyhow!(
+                                    "Unclosed placeholder {{{field} in format {template:?}"
+                                ));
+                            }
+                        }
+                    }
+
+                    if !WINDOW_TEMPLATE_FIELDS.contains(&field.as_str()) {
+                        return Err(anyhow::anyhow!(
+                            "Unknown placeholder {{{field}}} in format {template:?}; valid placeholders are {}",
+                            WINDOW_TEMPLATE_FIELDS.join(", ")
+                        ));
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    parts.push(TemplatePart::Field(field));
+                }
+                _ => literal.push(character),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        Ok(WindowTemplate(parts))
+    }
+
+    /// Fills in the template for the window, which is on the given monitor (shown as `-` if unknown).
+    pub fn render(&self, window: &Window, monitor: Option<&MonitorIndex>) -> String {
+        self.0
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(literal) => literal.clone(),
+                TemplatePart::Field(field) => match field.as_str() {
+                    "id" => window.id.to_string(),
+                    "hex_id" => window.id.to_hex_string(),
+                    "x" => window.x_offset.to_string(),
+                    "y" => window.y_offset.to_string(),
+                    "w" => window.width.to_string(),
+                    "h" => window.height.to_string(),
+                    "class" => window.window_class.clone(),
+                    "title" => window.title.clone(),
+                    "monitor" => monitor.map_or("-".to_owned(), |monitor| monitor.to_string()),
+                    _ => unreachable!("placeholders are validated when parsing"),
+                },
+            })
+            .collect()
+    }
+}
+
+/// A monitor's metadata along with what's currently on it, as shown by `status`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MonitorStatus {
+    pub index: usize,
+    pub name: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    pub x: i32,
+    pub y: i32,
+    pub primary: bool,
+    /// Whether the focused window is on this monitor.
+    pub active: bool,
+    pub window_count: usize,
+}
+
+/// Describes each monitor on its own line, e.g. `1: HDMI-A-0 1920x1080+0+1080 527x296mm (primary) [GSM-5B09-0001E2B4]`.
+///
+/// With `show_dpi`, each monitor's DPI (if its physical size is known) follows its physical size, e.g. `527x296mm 93dpi`.
+pub fn format_monitors(monitor_grid: &MonitorGrid, show_dpi: bool) -> String {
+    monitor_grid
+        .0
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let (x, y) = monitor_grid
+                .get_monitor_origin(&MonitorIndex(index))
+                .unwrap_or_default();
+
+            let physical_size = match (monitor.width_mm, monitor.height_mm) {
+                (Some(width_mm), Some(height_mm)) => format!(" {width_mm}x{height_mm}mm"),
+                _ => String::new(),
+            };
+
+            let dpi = match monitor.dpi() {
+                Some(dpi) if show_dpi => format!(" {dpi:.0}dpi"),
+                _ => String::new(),
+            };
+
+            format!(
+                "{index}: {} {}x{}+{x}+{y}{physical_size}{dpi}{}{}",
+                monitor.name.as_deref().unwrap_or("unknown"),
+                monitor.width,
+                monitor.height,
+                if monitor.primary { " (primary)" } else { "" },
+                monitor
+                    .identity
+                    .as_ref()
+                    .map(|identity| format!(" [{identity}]"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Describes each monitor and what's on it on its own line, e.g. `1: HDMI-A-0 1920x1080+0+1080 (primary, active) 3 windows`.
+pub fn format_status(statuses: &[MonitorStatus]) -> String {
+    statuses
+        .iter()
+        .map(|status| {
+            let flags = [(status.primary, "primary"), (status.active, "active")]
+                .into_iter()
+                .filter_map(|(is_set, flag)| is_set.then_some(flag))
+                .collect::<Vec<&str>>();
+
+            format!(
+                "{}: {} {}x{}+{}+{}{} {} window{}",
+                status.index,
+                status.name.as_deref().unwrap_or("unknown"),
+                status.width,
+                status.height,
+                status.x,
+                status.y,
+                if flags.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", flags.join(", "))
+                },
+                status.window_count,
+                if status.window_count == 1 { "" } else { "s" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Summarizes the monitors on a single line for status bars (e.g. polybar or i3blocks), like `[M0:1] [M1*:3] [M2:0]`,
+/// where each monitor's window count follows its index and the active monitor is starred.
+pub fn format_status_compact(statuses: &[MonitorStatus], separator: &str) -> String {
+    statuses
+        .iter()
+        .map(|status| {
+            format!(
+                "[M{}{}:{}]",
+                status.index,
+                if status.active { "*" } else { "" },
+                status.window_count
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// Formats the monitors' statuses as a JSON array of objects with the same fields as `MonitorStatus`.
+pub fn format_status_json(statuses: &[MonitorStatus]) -> String {
+    // Serializing plain data can't fail.
+    serde_json::to_string(statuses).unwrap_or_default()
+}
+
+/// Describes each window on its own line, e.g. `0x05000006 code.Code main.rs - Visual Studio Code`.
+pub fn format_windows(windows: &[Window]) -> String {
+    windows
+        .iter()
+        .map(|window| {
+            format!(
+                "{} {} {}",
+                window.id.to_hex_string(),
+                window.window_class,
+                window.title
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Describes each window (along with the monitor that it's on) on its own line using the template.
+pub fn format_windows_with_template(
+    windows: &[(Window, Option<MonitorIndex>)],
+    template: &WindowTemplate,
+) -> String {
+    windows
+        .iter()
+        .map(|(window, monitor)| template.render(window, monitor.as_ref()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats a monitor's bounds (`(x, y, width, height)`) as `X=1920 Y=0 W=3440 H=1440`.
+pub fn format_geometry(bounds: (i32, i32, i32, i32)) -> String {
+    let (x, y, width, height) = bounds;
+    format!("X={x} Y={y} W={width} H={height}")
+}
+
+/// Formats a monitor's bounds as a JSON object, e.g. `{"x": 1920, "y": 0, "width": 3440, "height": 1440}`.
+pub fn format_geometry_json(bounds: (i32, i32, i32, i32)) -> String {
+    let (x, y, width, height) = bounds;
+    format!(r#"{{"x": {x}, "y": {y}, "width": {width}, "height": {height}}}"#)
+}
+
+/// Lists the number of windows on each monitor, e.g. `0: 3`.
+pub fn format_counts(counts: &[usize]) -> String {
+    counts
+        .iter()
+        .enumerate()
+        .map(|(index, count)| format!("{index}: {count}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Lists each window class on its own line, optionally followed by how many windows have it, e.g. `code.Code 3`.
+pub fn format_classes(classes: &[(String, usize)], show_counts: bool) -> String {
+    classes
+        .iter()
+        .map(|(class, count)| {
+            if show_counts {
+                format!("{class} {count}")
+            } else {
+                class.clone()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats the number of windows on each monitor as a JSON object keyed by monitor index, e.g. `{"0": 3, "1": 0}`.
+pub fn format_counts_json(counts: &[usize]) -> String {
+    let entries = counts
+        .iter()
+        .enumerate()
+        .map(|(index, count)| format!("\"{index}\": {count}"))
+        .collect::<Vec<String>>();
+
+    format!("{{{}}}", entries.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Monitor, WindowId};
+
+    mod format_windows {
+        use super::*;
+
+        #[test]
+        fn test_windows() {
+            let windows = [
+                Window::new(
+                    WindowId(0x05000006),
+                    0,
+                    24,
+                    1920,
+                    1056,
+                    "code.Code".to_owned(),
+                    "main.rs - Visual Studio Code".to_owned(),
+                ),
+                Window::new(
+                    WindowId(0x03a00003),
+                    1920,
+                    24,
+                    1920,
+                    1056,
+                    "slack.Slack".to_owned(),
+                    "Slack".to_owned(),
+                ),
+            ];
+
+            assert_eq!(
+                format_windows(&windows),
+                "0x05000006 code.Code main.rs - Visual Studio Code\n0x03a00003 slack.Slack Slack"
+            );
+        }
+
+        #[test]
+        fn test_no_windows() {
+            assert_eq!(format_windows(&[]), "");
+        }
+    }
+
+    mod window_template {
+        use super::*;
+
+        fn create_window() -> Window {
+            Window::new(
+                WindowId(0x05000006),
+                1920,
+                24,
+                800,
+                600,
+                "code.Code".to_owned(),
+                "main.rs - Visual Studio Code".to_owned(),
+            )
+        }
+
+        fn render(template: &str) -> String {
+            WindowTemplate::parse(template)
+                .unwrap()
+                .render(&create_window(), Some(&MonitorIndex(2)))
+        }
+
+        #[test]
+        fn test_all_fields() {
+            assert_eq!(
+                render("{id} {hex_id} {x} {y} {w} {h} {class} {title} {monitor}"),
+                "83886086 0x05000006 1920 24 800 600 code.Code main.rs - Visual Studio Code 2"
+            );
+        }
+
+        #[test]
+        fn test_literals_around_fields() {
+            assert_eq!(
+                render("{class}\t{w}x{h}+{x}+{y}"),
+                "code.Code\t800x600+1920+24"
+            );
+            assert_eq!(render("monitor={monitor};"), "monitor=2;");
+            assert_eq!(render("no placeholders"), "no placeholders");
+            assert_eq!(render(""), "");
+        }
+
+        #[test]
+        fn test_escaped_braces() {
+            assert_eq!(render("{{{class}}}"), "{code.Code}");
+            assert_eq!(render("{{title}}"), "{title}");
+        }
+
+        #[test]
+        fn test_unknown_placeholder() {
+            let error = WindowTemplate::parse("{id} {pid}").unwrap_err();
+
+            assert!(error.to_string().starts_with(
+                "Unknown placeholder {pid} in format \"{id} {pid}\"; valid placeholders are id, hex_id"
+            ));
+            assert!(WindowTemplate::parse("{Title}").is_err());
+            assert_eq!(
+                WindowTemplate::parse("{title").unwrap_err().to_string(),
+                "Unclosed placeholder {title in format \"{title\""
+            );
+        }
+
+        #[test]
+        fn test_unknown_monitor() {
+            let template = WindowTemplate::parse("{monitor}").unwrap();
+            assert_eq!(template.render(&create_window(), None), "-");
+        }
+
+        #[test]
+        fn test_format_windows_with_template() {
+            let template = WindowTemplate::parse("{monitor}: {title}").unwrap();
+            let windows = [
+                (create_window(), Some(MonitorIndex(0))),
+                (create_window(), Some(MonitorIndex(1))),
+            ];
+
+            assert_eq!(
+                format_windows_with_template(&windows, &template),
+                "0: main.rs - Visual Studio Code\n1: main.rs - Visual Studio Code"
+            );
+            assert_eq!(format_windows_with_template(&[], &template), "");
+        }
+    }
+
+    mod format_status {
+        use super::*;
+
+        fn create_statuses() -> Vec<MonitorStatus> {
+            vec![
+                MonitorStatus {
+                    index: 0,
+                    name: Some("DisplayPort-2".to_owned()),
+                    width: 1920,
+                    height: 1080,
+                    x: 0,
+                    y: 0,
+                    primary: false,
+                    active: false,
+                    window_count: 1,
+                },
+                MonitorStatus {
+                    index: 1,
+                    name: Some("HDMI-A-0".to_owned()),
+                    width: 1920,
+                    height: 1080,
+                    x: 0,
+                    y: 1080,
+                    primary: true,
+                    active: true,
+                    window_count: 3,
+                },
+                MonitorStatus {
+                    index: 2,
+                    name: None,
+                    width: 3440,
+                    height: 1440,
+                    x: 1920,
+                    y: 0,
+                    primary: false,
+                    active: false,
+                    window_count: 0,
+                },
+            ]
+        }
+
+        #[test]
+        fn test_status() {
+            assert_eq!(
+                format_status(&create_statuses()),
+                [
+                    "0: DisplayPort-2 1920x1080+0+0 1 window",
+                    "1: HDMI-A-0 1920x1080+0+1080 (primary, active) 3 windows",
+                    "2: unknown 3440x1440+1920+0 0 windows",
+                ]
+                .join("\n")
+            );
+        }
+
+        #[test]
+        fn test_status_json() {
+            let json: serde_json::Value =
+                serde_json::from_str(&format_status_json(&create_statuses())).unwrap();
+
+            assert_eq!(
+                json[1],
+                serde_json::json!({
+                    "index": 1,
+                    "name": "HDMI-A-0",
+                    "width": 1920,
+                    "height": 1080,
+                    "x": 0,
+                    "y": 1080,
+                    "primary": true,
+                    "active": true,
+                    "window_count": 3
+                })
+            );
+            assert_eq!(json[2]["name"], serde_json::Value::Null);
+        }
+
+        #[test]
+        fn test_status_compact() {
+            assert_eq!(
+                format_status_compact(&create_statuses(), " "),
+                "[M0:1] [M1*:3] [M2:0]"
+            );
+        }
+
+        #[test]
+        fn test_status_compact_separator() {
+            assert_eq!(
+                format_status_compact(&create_statuses(), " | "),
+                "[M0:1] | [M1*:3] | [M2:0]"
+            );
+        }
+
+        #[test]
+        fn test_status_compact_no_active_monitor() {
+            let statuses = create_statuses()
+                .into_iter()
+                .map(|status| MonitorStatus {
+                    active: false,
+                    ..status
+                })
+                .collect::<Vec<MonitorStatus>>();
+
+            assert_eq!(format_status_compact(&statuses, ""), "[M0:1][M1:3][M2:0]");
+        }
+
+        #[test]
+        fn test_no_monitors() {
+            assert_eq!(format_status(&[]), "");
+            assert_eq!(format_status_compact(&[], " "), "");
+            assert_eq!(format_status_json(&[]), "[]");
+        }
+    }
+
+    mod format_classes {
+        use super::*;
+
+        fn create_classes() -> Vec<(String, usize)> {
+            vec![("code.Code".to_owned(), 2), ("slack.Slack".to_owned(), 1)]
+        }
+
+        #[test]
+        fn test_classes() {
+            assert_eq!(
+                format_classes(&create_classes(), false),
+                "code.Code\nslack.Slack"
+            );
+        }
+
+        #[test]
+        fn test_classes_with_counts() {
+            assert_eq!(
+                format_classes(&create_classes(), true),
+                "code.Code 2\nslack.Slack 1"
+            );
+        }
+    }
+
+    mod format_counts {
+        use super::*;
+
+        #[test]
+        fn test_counts() {
+            assert_eq!(format_counts(&[3, 0, 1]), "0: 3\n1: 0\n2: 1");
+        }
+
+        #[test]
+        fn test_counts_json() {
+            let json = format_counts_json(&[3, 0, 1]);
+
+            assert_eq!(json, r#"{"0": 3, "1": 0, "2": 1}"#);
+            assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+        }
+
+        #[test]
+        fn test_no_monitors_json() {
+            assert_eq!(format_counts_json(&[]), "{}");
+        }
+    }
+
+    mod format_geometry {
+        use super::*;
+
+        #[test]
+        fn test_geometry() {
+            assert_eq!(
+                format_geometry((1920, 0, 3440, 1440)),
+                "X=1920 Y=0 W=3440 H=1440"
+            );
+        }
+
+        #[test]
+        fn test_geometry_json() {
+            let json = format_geometry_json((1920, 0, 3440, 1440));
+
+            assert_eq!(
+                json,
+                r#"{"x": 1920, "y": 0, "width": 3440, "height": 1440}"#
+            );
+            assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+        }
+    }
+
+    mod format_monitors {
+        use super::*;
+
+        #[test]
+        fn test_named_monitors() {
+            let grid = MonitorGrid(vec![
+                vec![
+                    Monitor {
+                        name: Some("DisplayPort-2".to_owned()),
+                        ..Monitor::new(1920, 1080)
+                    },
+                    Monitor {
+                        name: Some("HDMI-A-0".to_owned()),
+                        identity: Some("GSM-5B09-0001E2B4".to_owned()),
+                        ..Monitor::new_primary(1920, 1080).with_physical_size(527, 296)
+                    },
+                ],
+                vec![Monitor {
+                    name: Some("DisplayPort-0".to_owned()),
+                    ..Monitor::new(3440, 1440)
+                }],
+            ]);
+
+            assert_eq!(
+                format_monitors(&grid, false),
+                [
+                    "0: DisplayPort-2 1920x1080+0+0",
+                    "1: HDMI-A-0 1920x1080+0+1080 527x296mm (primary) [GSM-5B09-0001E2B4]",
+                    "2: DisplayPort-0 3440x1440+1920+0",
+                ]
+                .join("\n")
+            );
+        }
+
+        #[test]
+        fn test_unnamed_monitor() {
+            let grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(format_monitors(&grid, false), "0: unknown 1920x1080+0+0");
+        }
+
+        #[test]
+        fn test_dpi() {
+            let grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080).with_physical_size(527, 296)],
+                vec![Monitor::new(3440, 1440).with_physical_size(800, 337)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            assert_eq!(
+                format_monitors(&grid, true),
+                [
+                    "0: unknown 1920x1080+0+0 527x296mm 93dpi",
+                    "1: unknown 3440x1440+1920+0 800x337mm 109dpi",
+                    "2: unknown 1920x1080+5360+0",
+                ]
+                .join("\n")
+            );
+            assert_eq!(
+                format_monitors(&grid, false).lines().next(),
+                Some("0: unknown 1920x1080+0+0 527x296mm")
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::models::{Window, WindowId};
+use crate::services::monitor_cache;
+
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+
+/// A window's position and size as it was when the snapshot was taken, in the screen's coordinates.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SavedWindow {
+    /// The window's ID in hex, e.g. `0x05000006`.
+    pub id: String,
+    pub class: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Where the snapshot is kept when no file is given, e.g. `~/.cache/easy-window-switcher/snapshot.json`.
+pub fn get_default_path() -> PathBuf {
+    monitor_cache::get_cache_dir().join(SNAPSHOT_FILE_NAME)
+}
+
+pub fn save(path: &Path, saved_windows: &[SavedWindow]) -> Result<()> {
+    monitor_cache::write_json(path, &saved_windows)
+        .with_context(|| format!("Failed to save the snapshot to {}", path.display()))
+}
+
+pub fn load(path: &Path) -> Result<Vec<SavedWindow>> {
+    monitor_cache::read_json(path)
+        .with_context(|| format!("Failed to load the snapshot from {}", path.display()))
+}
+
+/// Pairs each saved window with the current window that it should be restored onto.
+///
+/// Windows are matched by ID first. Since IDs change when an app is restarted, the saved windows whose IDs are gone
+/// then fall back to the remaining windows of the same class, in order. Each window is only restored once, and saved
+/// windows without a match are left out.
+pub fn match_saved_windows<'a>(
+    saved_windows: &'a [SavedWindow],
+    windows: &'a [Window],
+) -> Vec<(&'a SavedWindow, &'a Window)> {
+    let find_by_id = |saved_window: &SavedWindow| {
+        let id = saved_window.id.parse::<WindowId>().ok()?;
+        windows.iter().find(|window| window.id == id)
+    };
+
+    let mut matched_ids: HashSet<&WindowId> = saved_windows
+        .iter()
+        .filter_map(find_by_id)
+        .map(|window| &window.id)
+        .collect();
+
+    let mut matches = Vec::new();
+
+    for saved_window in saved_windows {
+        if let Some(window) = find_by_id(saved_window) {
+            matches.push((saved_window, window));
+            continue;
+        }
+
+        let fallback = windows.iter().find(|window| {
+            window.window_class == saved_window.class && !matched_ids.contains(&window.id)
+        });
+
+        if let Some(window) = fallback {
+            matched_ids.insert(&window.id);
+            matches.push((saved_window, window));
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn create_saved_window(id: &str, class: &str, x: i32) -> SavedWindow {
+        SavedWindow {
+            id: id.to_owned(),
+            class: class.to_owned(),
+            x,
+            y: 24,
+            width: 800,
+            height: 600,
+        }
+    }
+
+    fn create_window(id: usize, class: &str) -> Window {
+        Window::new(
+            WindowId(id),
+            0,
+            24,
+            1920,
+            1056,
+            class.to_string(),
+            "title".to_string(),
+        )
+    }
+
+    mod match_saved_windows {
+        use super::*;
+
+        fn get_result(saved_windows: &[SavedWindow], windows: &[Window]) -> Vec<(i32, WindowId)> {
+            match_saved_windows(saved_windows, windows)
+                .into_iter()
+                .map(|(saved_window, window)| (saved_window.x, window.id.clone()))
+                .collect()
+        }
+
+        #[test]
+        fn test_exact_ids() {
+            let saved_windows = vec![
+                create_saved_window("0x00000002", "slack.Slack", 0),
+                create_saved_window("0x00000001", "code.Code", 1920),
+            ];
+            let windows = vec![
+                create_window(1, "code.Code"),
+                create_window(2, "slack.Slack"),
+            ];
+
+            assert_eq!(
+                get_result(&saved_windows, &windows),
+                vec![(0, WindowId(2)), (1920, WindowId(1))]
+            );
+        }
+
+        #[test]
+        fn test_class_fallback() {
+            // The app was restarted, so its window has a new ID.
+            let saved_windows = vec![
+                create_saved_window("0x00000001", "code.Code", 0),
+                create_saved_window("0x00000002", "slack.Slack", 1920),
+            ];
+            let windows = vec![
+                create_window(3, "code.Code"),
+                create_window(2, "slack.Slack"),
+            ];
+
+            assert_eq!(
+                get_result(&saved_windows, &windows),
+                vec![(0, WindowId(3)), (1920, WindowId(2))]
+            );
+        }
+
+        #[test]
+        fn test_fallback_skips_windows_matched_by_id() {
+            // Window 2 still exists, so only the new window 3 is left for the saved window whose ID is gone, even
+            // though it comes first.
+            let saved_windows = vec![
+                create_saved_window("0x00000001", "code.Code", 0),
+                create_saved_window("0x00000002", "code.Code", 1920),
+            ];
+            let windows = vec![create_window(2, "code.Code"), create_window(3, "code.Code")];
+
+            assert_eq!(
+                get_result(&saved_windows, &windows),
+                vec![(0, WindowId(3)), (1920, WindowId(2))]
+            );
+        }
+
+        #[test]
+        fn test_each_window_restored_once() {
+            let saved_windows = vec![
+                create_saved_window("0x00000001", "code.Code", 0),
+                create_saved_window("0x00000002", "code.Code", 1920),
+            ];
+            let windows = vec![create_window(3, "code.Code")];
+
+            assert_eq!(get_result(&saved_windows, &windows), vec![(0, WindowId(3))]);
+        }
+
+        #[test]
+        fn test_no_match() {
+            let saved_windows = vec![create_saved_window("0x00000001", "code.Code", 0)];
+            let windows = vec![create_window(2, "slack.Slack")];
+
+            assert!(get_result(&saved_windows, &windows).is_empty());
+            assert!(get_result(&[], &windows).is_empty());
+        }
+    }
+
+    mod load {
+        use super::*;
+
+        fn create_path(name: &str) -> PathBuf {
+            env::temp_dir()
+                .join(format!("ews-snapshot-test-{}-{name}", std::process::id()))
+                .join(SNAPSHOT_FILE_NAME)
+        }
+
+        #[test]
+        fn test_missing_snapshot() {
+            let path = create_path("missing");
+
+            assert_eq!(
+                load(&path).unwrap_err().to_string(),
+                format!("Failed to load the snapshot from {}", path.display())
+            );
+        }
+
+        #[test]
+        fn test_corrupt_snapshot() {
+            let path = create_path("corrupt");
+
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "not json").unwrap();
+            assert_eq!(
+                load(&path).unwrap_err().to_string(),
+                format!("Failed to load the snapshot from {}", path.display())
+            );
+
+            fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+    }
+}
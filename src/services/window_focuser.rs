@@ -1,591 +1,5774 @@
 use anyhow::{Ok, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
 
-use crate::external_tools::{wmctrl, xdotool, xrandr};
-use crate::models::{FocusDirection, MonitorGrid, MonitorIndex, Window, WindowId, Workspace};
+use crate::config::{Backend, Config, EmptyMonitorPreference, FocusStrategy};
+use crate::external_tools::wmctrl::{StateAction, WindowState};
+use crate::external_tools::{chooser, hook, i3, launcher, notify, wmctrl, xdotool, xprop, xrandr};
+use crate::models::{
+    FocusDirection, MonitorGrid, MonitorIndex, MonitorSelector, Region, SortOrder, Window,
+    WindowId, Workspace,
+};
+use crate::services::report::MonitorStatus;
+use crate::services::snapshot::{self, SavedWindow};
+use crate::services::{monitor_cache, peek_cache};
 
-pub fn focus_by_direction(direction: FocusDirection) -> Result<()> {
-    let workspace = xrandr::parse_workspace()?;
-    let windows = get_current_workspace_windows(&workspace);
-    let current_window_id = xdotool::get_current_focused_window_id();
+/// How long a window stays focused when it's flashed.
+const FLASH_DURATION: Duration = Duration::from_millis(500);
+
+/// How long the window manager is given to act on a focus request before it's verified.
+const VERIFY_FOCUS_DELAY: Duration = Duration::from_millis(50);
+
+pub fn focus_by_direction(direction: FocusDirection, config: &Config) -> Result<()> {
+    focus_by_direction_in(&load_workspace(config)?, direction, config)
+}
+
+/// Same as `focus_by_direction`, but with an already parsed workspace (e.g. one cached by the daemon).
+pub fn focus_by_direction_in(
+    workspace: &Workspace,
+    direction: FocusDirection,
+    config: &Config,
+) -> Result<()> {
+    let direction = resolve_direction(direction, config);
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    let wrap = if direction.is_horizontal() {
+        config.wrap_horizontal
+    } else {
+        config.wrap_vertical
+    };
+
+    // A fullscreen window hides everything else on its monitor, so there's nothing to move to there.
+    let leave_current_monitor = config.skip_fullscreen
+        && xprop::is_fullscreen(&current_window_id).unwrap_or_else(|error| {
+            log::debug!("Failed to check whether the focused window is fullscreen: {error:#}");
+            false
+        });
+
+    let options = NavigationOptions {
+        wrap,
+        current_monitor_only: config.current_monitor_only,
+        leave_current_monitor,
+        empty_monitor_preference: config.empty_monitor_preference,
+    };
 
     if let Some(window_to_focus) = find_closest_window(
         &current_window_id,
         &workspace.monitor_grid,
         &windows,
         &direction,
+        &options,
     )? {
-        wmctrl::focus_window_by_id(&window_to_focus.id);
+        let window_to_focus = if config.pointer_tiebreak {
+            pick_nearest_to_pointer(workspace, &windows, &current_window_id, window_to_focus)
+        } else {
+            window_to_focus
+        };
+
+        focus_window(&window_to_focus, workspace, config)?;
     }
 
     Ok(())
 }
 
-pub fn focus_by_monitor_index(index: MonitorIndex) -> Result<()> {
-    let workspace = xrandr::parse_workspace()?;
-    let windows = get_current_workspace_windows(&workspace);
+/// Focuses onto the window on the monitor with the given index or xrandr output name.
+pub fn focus_by_monitor(selector: &MonitorSelector, config: &Config) -> Result<()> {
+    focus_by_monitor_in(&load_workspace(config)?, selector, config)
+}
+
+pub fn focus_by_monitor_in(
+    workspace: &Workspace,
+    selector: &MonitorSelector,
+    config: &Config,
+) -> Result<()> {
+    let index = match selector {
+        // Output names can change across reboots and dock reconnects, so fall back to the monitors' stable
+        // identities, which are only read when needed since it's slower than the rest of the monitor detection.
+        MonitorSelector::Name(name) if workspace.monitor_grid.index_of_name(name).is_none() => {
+            workspace
+                .monitor_grid
+                .with_identities(&xrandr::get_monitor_identities()?)
+                .resolve(selector)?
+        }
+        _ => workspace.monitor_grid.resolve(selector)?,
+    };
+
+    focus_by_monitor_index_in(workspace, index, config)
+}
+
+pub fn focus_by_monitor_index(index: MonitorIndex, config: &Config) -> Result<()> {
+    focus_by_monitor_index_in(&load_workspace(config)?, index, config)
+}
+
+pub fn focus_by_monitor_index_in(
+    workspace: &Workspace,
+    index: MonitorIndex,
+    config: &Config,
+) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
     let windows_by_monitor_index = index_windows_by_monitor(&workspace.monitor_grid, &windows)?;
 
-    if windows_by_monitor_index.contains_key(&index) {
-        wmctrl::focus_window_by_id(&windows_by_monitor_index[&index][0].id);
+    // Monitors without any windows have nothing to focus.
+    if let Some(window) = find_monitor_window(
+        &workspace.monitor_grid,
+        &windows_by_monitor_index,
+        &index,
+        config.pick_last_window,
+        config.nth_window,
+    )? {
+        focus_window(window, workspace, config)?;
     }
 
     Ok(())
 }
 
-fn get_current_workspace_windows(workspace: &Workspace) -> Vec<Window> {
-    let mut current_workspace_windows = wmctrl::get_windows_config()
-        .into_iter()
-        .filter(|window| workspace.is_window_in_current_workspace(window))
-        .collect::<Vec<Window>>();
+/// Finds the leftmost (or with `pick_last`, the rightmost) window on the monitor with the given index, erroring if
+/// there's no such monitor so that it can be told apart from a monitor that just doesn't have any windows.
+///
+/// With `nth`, the window that many places from the left (or with `pick_last`, from the right) is picked instead,
+/// erroring if the monitor doesn't have that many windows.
+fn find_monitor_window<'a>(
+    monitor_grid: &MonitorGrid,
+    windows_by_monitor_index: &HashMap<MonitorIndex, Vec<&'a Window>>,
+    index: &MonitorIndex,
+    pick_last: bool,
+    nth: Option<usize>,
+) -> Result<Option<&'a Window>> {
+    let monitor_count = monitor_grid.calculate_monitor_count() as usize;
+
+    if index.0 >= monitor_count {
+        return Err(anyhow::anyhow!(
+            "Monitor index {index} out of range; {monitor_count} monitors detected"
+        ));
+    }
+
+    let windows = windows_by_monitor_index
+        .get(index)
+        .map_or(&[][..], |windows| windows.as_slice());
+
+    let Some(nth) = nth else {
+        let window = if pick_last {
+            windows.last()
+        } else {
+            windows.first()
+        };
+
+        return Ok(window.copied());
+    };
+
+    if nth >= windows.len() {
+        return Err(anyhow::anyhow!(
+            "Window {nth} out of range; monitor {index} has {} windows",
+            windows.len()
+        ));
+    }
 
-    // Sort by the x-offset to make sure the Windows are in order from left to right.
-    current_workspace_windows.sort_by(|a, b| a.x_offset.cmp(&b.x_offset));
+    Ok(Some(if pick_last {
+        windows[windows.len() - 1 - nth]
+    } else {
+        windows[nth]
+    }))
+}
 
-    current_workspace_windows
+/// Focuses onto the window on the primary monitor, falling back to the first monitor if none is marked as primary.
+pub fn focus_primary(config: &Config) -> Result<()> {
+    focus_primary_in(&load_workspace(config)?, config)
 }
 
-fn index_windows_by_monitor<'a>(
-    monitor_grid: &MonitorGrid,
-    windows: &'a Vec<Window>,
-) -> Result<HashMap<MonitorIndex, Vec<&'a Window>>> {
-    let mut windows_by_monitor_index: HashMap<MonitorIndex, Vec<&Window>> = HashMap::new();
+pub fn focus_primary_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let index = workspace
+        .monitor_grid
+        .get_primary_monitor()
+        .unwrap_or(MonitorIndex(0));
 
-    for window in windows {
-        let monitor_index = monitor_grid.determine_which_monitor_window_is_on(window)?;
+    focus_by_monitor_index_in(workspace, index, config)
+}
 
-        windows_by_monitor_index
-            .entry(monitor_index)
-            .or_default()
-            .push(window);
+/// Focuses onto the leftmost window whose center is within the region (a horizontal slice) of the workspace.
+pub fn focus_region(region: &Region, config: &Config) -> Result<()> {
+    focus_region_in(&load_workspace(config)?, region, config)
+}
+
+pub fn focus_region_in(workspace: &Workspace, region: &Region, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let pixel_range = region.to_pixel_range(workspace.width());
+
+    match find_window_in_pixel_range(&windows, pixel_range) {
+        Some(window) => focus_window(window, workspace, config)?,
+        None => log::info!("No windows found in region {region}"),
     }
 
-    Ok(windows_by_monitor_index)
+    Ok(())
 }
 
-fn index_monitors_by_window(
-    monitor_grid: &MonitorGrid,
-    windows: &Vec<Window>,
-) -> Result<HashMap<WindowId, MonitorIndex>> {
-    let mut monitors_by_window: HashMap<WindowId, MonitorIndex> = HashMap::new();
+/// Focuses onto the window that has gone the longest without being used, going by its `_NET_WM_USER_TIME`.
+pub fn focus_oldest(config: &Config) -> Result<()> {
+    focus_oldest_in(&load_workspace(config)?, config)
+}
 
-    for window in windows {
-        monitors_by_window.insert(
-            window.id.clone(),
-            monitor_grid.determine_which_monitor_window_is_on(window)?,
-        );
+pub fn focus_oldest_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    // Without xprop (or for windows that don't set it), there's no user time, so wmctrl's listing order is used instead.
+    let user_times = windows
+        .iter()
+        .map(|window| xprop::get_user_time(&window.id).ok().flatten())
+        .collect::<Vec<Option<u64>>>();
+
+    if let Some(window_to_focus) = find_oldest_window(&windows, &user_times) {
+        focus_window(window_to_focus, workspace, config)?;
     }
 
-    Ok(monitors_by_window)
+    Ok(())
 }
 
-fn get_current_monitor(
-    current_window_id: &WindowId,
-    monitors_by_window: &HashMap<WindowId, MonitorIndex>,
-) -> MonitorIndex {
-    monitors_by_window[current_window_id].clone()
+/// Focuses onto the window on the monitor "opposite" the current one: the other monitor on a dual-monitor setup,
+/// or the horizontally mirrored one (e.g. the first and last monitors swap) when there are more.
+pub fn flip(config: &Config) -> Result<()> {
+    flip_in(&load_workspace(config)?, config)
 }
 
-/// Finds the closest window to the current window based on the specified focus direction.
-///
-/// # Parameters
-/// - `current_window_id`: The ID of the currently focused window.
-/// - `monitor_grid`: A reference to the monitor grid containing all monitors and their respective windows.
-/// - `windows`: A vector of references to all windows.
-/// - `focus_direction`: The direction in which to search for the closest window.
-///
-/// # Returns
-/// - If a valid window is found, it returns an `Option<&Window>`.
-/// - If no valid window is found (e.g., if there are no windows or the current window does not exist), it returns `None`.
-fn find_closest_window(
+pub fn flip_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+    let monitors_by_window = index_monitors_by_window(&workspace.monitor_grid, &windows)?;
+
+    let Some(current_monitor) = get_current_monitor(&current_window_id, &monitors_by_window) else {
+        log::info!("The focused window isn't on any monitor");
+        return Ok(());
+    };
+
+    let monitor_count = workspace.monitor_grid.calculate_monitor_count() as usize;
+
+    focus_by_monitor_index_in(
+        workspace,
+        get_opposite_monitor(&current_monitor, monitor_count),
+        config,
+    )
+}
+
+/// Focuses onto the first window on the monitor next to the current one in the given direction, wrapping around like
+/// `direction` does. Unlike `direction`, this always moves a whole monitor at a time, no matter where the windows are.
+pub fn focus_relative_monitor(direction: FocusDirection, config: &Config) -> Result<()> {
+    focus_relative_monitor_in(&load_workspace(config)?, direction, config)
+}
+
+pub fn focus_relative_monitor_in(
+    workspace: &Workspace,
+    direction: FocusDirection,
+    config: &Config,
+) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    let wrap = if direction.is_horizontal() {
+        config.wrap_horizontal
+    } else {
+        config.wrap_vertical
+    };
+
+    match find_relative_monitor(
+        &current_window_id,
+        &workspace.monitor_grid,
+        &windows,
+        &direction,
+        wrap,
+    )? {
+        Some(next_monitor) => focus_by_monitor_index_in(workspace, next_monitor, config),
+        None => {
+            log::info!("There's no monitor in that direction from the focused window");
+            Ok(())
+        }
+    }
+}
+
+/// Finds the monitor next to the one that the current window is on in the given direction. Returns `None` if the
+/// current window isn't on any monitor, or if there's no monitor in that direction without wrapping.
+fn find_relative_monitor(
     current_window_id: &WindowId,
     monitor_grid: &MonitorGrid,
     windows: &Vec<Window>,
     direction: &FocusDirection,
-) -> Result<Option<Window>> {
-    if windows.is_empty() {
+    wrap: bool,
+) -> Result<Option<MonitorIndex>> {
+    let monitors_by_window = index_monitors_by_window(monitor_grid, windows)?;
+
+    Ok(
+        get_current_monitor(current_window_id, &monitors_by_window).and_then(|current_monitor| {
+            monitor_grid.get_next_monitor_with_wrap(&current_monitor, direction, wrap)
+        }),
+    )
+}
+
+/// Focuses onto the window with the given ID, which has to be one of the windows on the current workspace.
+pub fn focus_by_id(window_id: &WindowId, config: &Config) -> Result<()> {
+    focus_by_id_in(&load_workspace(config)?, window_id, config)
+}
+
+pub fn focus_by_id_in(workspace: &Workspace, window_id: &WindowId, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    focus_window(find_window_by_id(&windows, window_id)?, workspace, config)
+}
+
+pub fn focus_largest(config: &Config) -> Result<()> {
+    focus_largest_in(&load_workspace(config)?, config)
+}
+
+pub fn focus_largest_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    if let Some(window_to_focus) = find_largest_window(&windows) {
+        focus_window(window_to_focus, workspace, config)?;
+    }
+
+    Ok(())
+}
+
+/// Focuses onto the window that covers the most of the current monitor.
+pub fn focus_dominant(config: &Config) -> Result<()> {
+    focus_dominant_in(&load_workspace(config)?, config)
+}
+
+pub fn focus_dominant_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+    let monitors_by_window = index_monitors_by_window(&workspace.monitor_grid, &windows)?;
+
+    let Some(current_monitor) = get_current_monitor(&current_window_id, &monitors_by_window) else {
+        log::info!("The focused window isn't on any monitor");
+        return Ok(());
+    };
+
+    let Some(monitor_bounds) = workspace.monitor_grid.get_monitor_bounds(&current_monitor) else {
+        return Ok(());
+    };
+
+    if let Some(window_to_focus) = find_dominant_window(&windows, monitor_bounds) {
+        focus_window(window_to_focus, workspace, config)?;
+    }
+
+    Ok(())
+}
+
+pub fn raise_group(class: &str, config: &Config) -> Result<()> {
+    raise_group_in(&load_workspace(config)?, class, config)
+}
+
+pub fn raise_group_in(workspace: &Workspace, class: &str, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    if raise_windows_of_class(&windows, class, wmctrl::raise_window_by_id)? == 0 {
+        log::info!("No windows found with class {class}");
+    }
+
+    Ok(())
+}
+
+/// Focuses the next (or previous) window with the same class as the currently focused one, wrapping around at the ends.
+/// Right/down moves to the next window from left to right, while left/up moves to the previous one.
+pub fn next_same_class(direction: FocusDirection, config: &Config) -> Result<()> {
+    next_same_class_in(&load_workspace(config)?, direction, config)
+}
+
+pub fn next_same_class_in(
+    workspace: &Workspace,
+    direction: FocusDirection,
+    config: &Config,
+) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    match find_next_window_of_same_class(&windows, &current_window_id, &direction) {
+        Some(window) => focus_window(window, workspace, config)?,
+        None => log::info!("No other windows found with the same class as the focused window"),
+    }
+
+    Ok(())
+}
+
+/// Focuses the next (or previous) window on the focused window's monitor, from left to right, wrapping around at the
+/// ends without ever leaving the monitor. Right/down moves to the next window, while left/up moves to the previous one.
+pub fn cycle_monitor(direction: FocusDirection, config: &Config) -> Result<()> {
+    cycle_monitor_in(&load_workspace(config)?, direction, config)
+}
+
+pub fn cycle_monitor_in(
+    workspace: &Workspace,
+    direction: FocusDirection,
+    config: &Config,
+) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    match find_next_window_on_monitor(
+        &workspace.monitor_grid,
+        &windows,
+        &current_window_id,
+        &direction,
+    )? {
+        Some(window) => focus_window(window, workspace, config)?,
+        None => log::info!("No other windows found on the current monitor"),
+    }
+
+    Ok(())
+}
+
+/// Shows the windows on the current workspace in the configured chooser (e.g. `rofi -dmenu`) and focuses the chosen one.
+pub fn select(config: &Config) -> Result<()> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+
+    let Some(selection) = chooser::choose(&config.chooser, &build_menu_lines(&windows))? else {
+        log::info!("No window was chosen");
+        return Ok(());
+    };
+
+    let window_id = parse_menu_selection(&selection)?;
+
+    match windows.iter().find(|window| window.id == window_id) {
+        Some(window) => focus_window(window, &workspace, config),
+        None => Err(anyhow::anyhow!("Window {window_id} no longer exists")),
+    }
+}
+
+/// Briefly focuses the leftmost window of the given class before restoring focus to the original window.
+pub fn flash(class: &str, config: &Config) -> Result<()> {
+    flash_in(&load_workspace(config)?, class, config)
+}
+
+pub fn flash_in(workspace: &Workspace, class: &str, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    match windows.iter().find(|window| window.matches_class(class)) {
+        Some(window) => with_focus_restored(&window.id, || thread::sleep(FLASH_DURATION)),
+        None => {
+            log::info!("No windows found with class {class}");
+            Ok(())
+        }
+    }
+}
+
+/// Focuses the leftmost window of the given class or, if there isn't one, launches the given command (e.g. to start the
+/// app).
+pub fn activate_or_launch(class: &str, command: &str, config: &Config) -> Result<()> {
+    activate_or_launch_in(&load_workspace(config)?, class, command, config)
+}
+
+pub fn activate_or_launch_in(
+    workspace: &Workspace,
+    class: &str,
+    command: &str,
+    config: &Config,
+) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    activate_or_launch_with(
+        &windows,
+        class,
+        |window| focus_window(window, workspace, config),
+        || launcher::launch(command),
+    )
+}
+
+/// Peeks at the leftmost window of the given class: the first time, it's kept above the other windows and focused; the
+/// next time, that same window is let back down. Which window of each class is peeked at is kept in the cache between
+/// runs.
+pub fn peek_toggle(class: &str, config: &Config) -> Result<()> {
+    peek_toggle_in(&load_workspace(config)?, class, config)
+}
+
+pub fn peek_toggle_in(workspace: &Workspace, class: &str, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+
+    let peeked_window_id = peek_cache::get_peeked_window(class);
+
+    let Some((window, action)) = find_peek_target(&windows, class, peeked_window_id.as_ref())
+    else {
+        log::info!("No windows found with class {class}");
+        return Ok(());
+    };
+
+    wmctrl::set_window_state(&window.id, action, WindowState::Above)?;
+
+    if action == StateAction::Add {
+        focus_window(window, workspace, config)?;
+        peek_cache::set_peeked_window(class, Some(&window.id))
+    } else {
+        peek_cache::set_peeked_window(class, None)
+    }
+}
+
+/// Set by `SIGINT` and `SIGTERM` while pinning, so that `pin` can stop between checks.
+static PIN_STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Keeps the leftmost window of the given class focused, refocusing it whenever focus moves to another window. Checks
+/// every `EWS_PIN_INTERVAL_MS` until the window is closed or it's interrupted (e.g. with Ctrl-C) or terminated.
+///
+/// Errors if there's no window of the class to begin with. Failures along the way (e.g. a tool timing out) are only
+/// logged, so that it keeps going.
+pub fn pin(class: &str, config: &Config) -> Result<()> {
+    stop_pin_on_signals();
+
+    run_pin_loop(
+        class,
+        || PIN_STOP_REQUESTED.load(Ordering::Relaxed),
+        || xdotool::get_current_focused_window_id().ok(),
+        |pinned_window_id, current_window_id| {
+            refocus_pinned_window(class, pinned_window_id, current_window_id, config)
+        },
+        || thread::sleep(config.pin_interval),
+    )
+}
+
+extern "C" fn request_pin_stop(_signal: libc::c_int) {
+    PIN_STOP_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+fn stop_pin_on_signals() {
+    for signal in [libc::SIGINT, libc::SIGTERM] {
+        // SAFETY: The handler only stores to an atomic, which is async-signal-safe.
+        unsafe {
+            libc::signal(
+                signal,
+                request_pin_stop as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+        }
+    }
+}
+
+/// Checks on the pinned window until `should_stop` says so or the window disappears, refocusing it whenever another
+/// window is focused.
+fn run_pin_loop(
+    class: &str,
+    should_stop: impl Fn() -> bool,
+    get_current_window_id: impl Fn() -> Option<WindowId>,
+    mut refocus: impl FnMut(Option<&WindowId>, Option<&WindowId>) -> Result<Option<WindowId>>,
+    wait: impl Fn(),
+) -> Result<()> {
+    let mut pinned_window_id: Option<WindowId> = None;
+
+    while !should_stop() {
+        let current_window_id = get_current_window_id();
+
+        let is_pinned_window_focused = pinned_window_id
+            .as_ref()
+            .is_some_and(|pinned| !should_refocus(current_window_id.as_ref(), pinned));
+
+        if !is_pinned_window_focused {
+            let refocused = refocus(pinned_window_id.as_ref(), current_window_id.as_ref())
+                .inspect_err(|error| log::warn!("Failed to refocus the pinned window: {error:#}"))
+                .ok();
+
+            match refocused {
+                Some(Some(window_id)) => pinned_window_id = Some(window_id),
+                Some(None) if pinned_window_id.is_none() => {
+                    return Err(anyhow::anyhow!("No windows found with class {class}"));
+                }
+                Some(None) => {
+                    log::info!("The pinned window was closed");
+                    return Ok(());
+                }
+                // Try again at the next check.
+                None => {}
+            }
+        }
+
+        wait();
+    }
+
+    log::info!("Stopped pinning");
+    Ok(())
+}
+
+/// Finds the window to pin (the pinned one if there already is one, otherwise the leftmost window of the class) and
+/// focuses it if it isn't already, returning its ID. Returns `None` if there's no such window (anymore).
+fn refocus_pinned_window(
+    class: &str,
+    pinned_window_id: Option<&WindowId>,
+    current_window_id: Option<&WindowId>,
+    config: &Config,
+) -> Result<Option<WindowId>> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+
+    let window = match pinned_window_id {
+        Some(pinned_window_id) => windows.iter().find(|window| &window.id == pinned_window_id),
+        None => windows.iter().find(|window| window.matches_class(class)),
+    };
+
+    let Some(window) = window else {
         return Ok(None);
+    };
+
+    if should_refocus(current_window_id, &window.id) {
+        log::info!("Refocusing the pinned window {}", window.id.to_hex_string());
+        focus_window(window, &workspace, config)?;
+    }
+
+    Ok(Some(window.id.clone()))
+}
+
+/// Lets the peeked window back down if it's still around; otherwise (e.g. it's been closed since) nothing counts as
+/// peeked at, so the leftmost window of the class is raised above the others.
+fn find_peek_target<'a>(
+    windows: &'a [Window],
+    class: &str,
+    peeked_window_id: Option<&WindowId>,
+) -> Option<(&'a Window, StateAction)> {
+    let peeked_window = peeked_window_id
+        .and_then(|peeked_window_id| windows.iter().find(|window| &window.id == peeked_window_id));
+
+    if let Some(peeked_window) = peeked_window {
+        return Some((peeked_window, StateAction::Remove));
+    }
+
+    windows
+        .iter()
+        .find(|window| window.matches_class(class))
+        .map(|window| (window, StateAction::Add))
+}
+
+/// Whether focus has moved away from the pinned window, including to no window at all.
+fn should_refocus(current_window_id: Option<&WindowId>, pinned_window_id: &WindowId) -> bool {
+    current_window_id != Some(pinned_window_id)
+}
+
+/// Minimizes every other window on the monitor of the currently focused window.
+pub fn solo_monitor(config: &Config) -> Result<()> {
+    solo_monitor_in(&load_workspace(config)?, config)
+}
+
+pub fn solo_monitor_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    for window_id in get_other_windows_on_current_monitor(workspace, config)? {
+        wmctrl::minimize_window_by_id(&window_id)?;
+    }
+
+    Ok(())
+}
+
+/// Restores every other window on the monitor of the currently focused window, undoing `solo_monitor`.
+pub fn unsolo_monitor(config: &Config) -> Result<()> {
+    unsolo_monitor_in(&load_workspace(config)?, config)
+}
+
+pub fn unsolo_monitor_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    for window_id in get_other_windows_on_current_monitor(workspace, config)? {
+        wmctrl::unminimize_window_by_id(&window_id)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the focused window onto the adjacent monitor in the given direction, wrapping around like focusing does.
+pub fn move_by_direction(direction: FocusDirection, config: &Config) -> Result<()> {
+    move_by_direction_in(&load_workspace(config)?, direction, config)
+}
+
+pub fn move_by_direction_in(
+    workspace: &Workspace,
+    direction: FocusDirection,
+    config: &Config,
+) -> Result<()> {
+    let direction = resolve_direction(direction, config);
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+    let monitors_by_window = index_monitors_by_window(&workspace.monitor_grid, &windows)?;
+
+    let (Some(window), Some(current_monitor)) = (
+        windows.iter().find(|window| window.id == current_window_id),
+        get_current_monitor(&current_window_id, &monitors_by_window),
+    ) else {
+        log::info!("The focused window isn't on any monitor");
+        return Ok(());
+    };
+
+    let wrap = if direction.is_horizontal() {
+        config.wrap_horizontal
+    } else {
+        config.wrap_vertical
+    };
+
+    let Some(next_monitor) =
+        workspace
+            .monitor_grid
+            .get_next_monitor_with_wrap(&current_monitor, &direction, wrap)
+    else {
+        log::info!("There's no monitor in that direction");
+        return Ok(());
+    };
+
+    if next_monitor == current_monitor {
+        return Ok(());
+    }
+
+    if let Some((x, y)) = calculate_moved_position(
+        &workspace.monitor_grid,
+        window,
+        &current_monitor,
+        &next_monitor,
+        config.move_relative,
+    ) {
+        let (x, y) = workspace.to_screen_position(x, y);
+        wmctrl::move_window_by_id(&window.id, x, y)?;
+    }
+
+    Ok(())
+}
+
+/// Centers the focused window on the monitor that it's on, keeping its size.
+pub fn center(config: &Config) -> Result<()> {
+    center_in(&load_workspace(config)?, config)
+}
+
+pub fn center_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    let Some(window) = windows.iter().find(|window| window.id == current_window_id) else {
+        log::info!("The focused window isn't on the current workspace");
+        return Ok(());
+    };
+
+    let monitor_index = workspace
+        .monitor_grid
+        .determine_which_monitor_window_is_on(window)?;
+
+    let Some(bounds) = workspace.monitor_grid.get_monitor_bounds(&monitor_index) else {
+        return Ok(());
+    };
+
+    let (x, y) = calculate_centered_position(bounds, window.width, window.height);
+    let (x, y) = workspace.to_screen_position(x, y);
+
+    wmctrl::move_window_by_id(&window.id, x, y)
+}
+
+/// Saves the position and size of every window on the current workspace to the file, for `restore` to put back later.
+pub fn save_snapshot(path: &Path, config: &Config) -> Result<()> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+
+    let saved_windows: Vec<SavedWindow> = windows
+        .iter()
+        .filter(|window| window.has_geometry())
+        .map(|window| {
+            let (x, y) = workspace.to_screen_position(window.x_offset, window.y_offset);
+
+            SavedWindow {
+                id: window.id.to_hex_string(),
+                class: window.window_class.clone(),
+                x,
+                y,
+                width: window.width,
+                height: window.height,
+            }
+        })
+        .collect();
+
+    snapshot::save(path, &saved_windows)?;
+    log::info!(
+        "Saved {} windows to {}",
+        saved_windows.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Moves and resizes the windows back to how they were in the snapshot saved to the file (see `save_snapshot`).
+pub fn restore_snapshot(path: &Path, config: &Config) -> Result<()> {
+    let saved_windows = snapshot::load(path)?;
+    let windows = get_current_workspace_windows(&load_workspace(config)?, config)?;
+    let matches = snapshot::match_saved_windows(&saved_windows, &windows);
+
+    for (saved_window, window) in &matches {
+        // Maximized windows can't be resized.
+        wmctrl::set_window_state(&window.id, StateAction::Remove, WindowState::MaximizedVert)?;
+        wmctrl::set_window_state(&window.id, StateAction::Remove, WindowState::MaximizedHorz)?;
+
+        wmctrl::move_resize_window_by_id(
+            &window.id,
+            saved_window.x,
+            saved_window.y,
+            saved_window.width,
+            saved_window.height,
+        )?;
+    }
+
+    if matches.len() < saved_windows.len() {
+        log::info!(
+            "{} saved windows no longer exist",
+            saved_windows.len() - matches.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Tiles the windows on the monitor of the currently focused window into a grid.
+pub fn tile_monitor(config: &Config) -> Result<()> {
+    tile_monitor_in(&load_workspace(config)?, config)
+}
+
+pub fn tile_monitor_in(workspace: &Workspace, config: &Config) -> Result<()> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+    let windows_by_monitor_index = index_windows_by_monitor(&workspace.monitor_grid, &windows)?;
+
+    let Some((monitor_index, monitor_windows)) = windows_by_monitor_index
+        .iter()
+        .find(|(_, windows)| windows.iter().any(|window| window.id == current_window_id))
+    else {
+        log::info!("The focused window isn't on any monitor");
+        return Ok(());
+    };
+
+    let Some(bounds) = workspace.monitor_grid.get_monitor_bounds(monitor_index) else {
+        return Ok(());
+    };
+
+    for (window, (x, y, width, height)) in monitor_windows
+        .iter()
+        .zip(calculate_tile_geometries(bounds, monitor_windows.len()))
+    {
+        // Maximized windows can't be resized.
+        wmctrl::set_window_state(&window.id, StateAction::Remove, WindowState::MaximizedVert)?;
+        wmctrl::set_window_state(&window.id, StateAction::Remove, WindowState::MaximizedHorz)?;
+
+        let (x, y) = workspace.to_screen_position(x, y);
+        wmctrl::move_resize_window_by_id(&window.id, x, y, width, height)?;
+    }
+
+    Ok(())
+}
+
+/// Calculates where a window of the given size goes to be centered on the monitor with the given bounds
+/// (`(x, y, width, height)`). A window that's bigger than the monitor is kept at its top/left edge instead, so that its
+/// title bar stays on the monitor.
+fn calculate_centered_position(
+    bounds: (i32, i32, i32, i32),
+    width: i32,
+    height: i32,
+) -> (i32, i32) {
+    let (monitor_x, monitor_y, monitor_width, monitor_height) = bounds;
+
+    (
+        monitor_x + ((monitor_width - width) / 2).max(0),
+        monitor_y + ((monitor_height - height) / 2).max(0),
+    )
+}
+
+/// Determines where the window should be moved to so that it's on the next monitor: the same position within the
+/// monitor when both monitors are the same size, or the next monitor's origin otherwise.
+///
+/// With `relative`, a window moving onto a differently sized monitor instead keeps its position as a fraction of the
+/// monitor's size (e.g. halfway across it), shifted back as needed so that it fits on the monitor.
+fn calculate_moved_position(
+    monitor_grid: &MonitorGrid,
+    window: &Window,
+    current_monitor: &MonitorIndex,
+    next_monitor: &MonitorIndex,
+    relative: bool,
+) -> Option<(i32, i32)> {
+    let (current_x, current_y, current_width, current_height) =
+        monitor_grid.get_monitor_bounds(current_monitor)?;
+    let (next_x, next_y, next_width, next_height) =
+        monitor_grid.get_monitor_bounds(next_monitor)?;
+
+    if (current_width, current_height) == (next_width, next_height) {
+        Some((
+            next_x + window.x_offset - current_x,
+            next_y + window.y_offset - current_y,
+        ))
+    } else if relative {
+        let scale_offset = |offset: i32, current_size: i32, next_size: i32, window_size: i32| {
+            let fraction = offset as f64 / current_size.max(1) as f64;
+            let scaled_offset = (fraction * next_size as f64).round() as i32;
+
+            // Windows larger than the monitor stick to its origin.
+            scaled_offset.clamp(0, (next_size - window_size).max(0))
+        };
+
+        Some((
+            next_x
+                + scale_offset(
+                    window.x_offset - current_x,
+                    current_width,
+                    next_width,
+                    window.width,
+                ),
+            next_y
+                + scale_offset(
+                    window.y_offset - current_y,
+                    current_height,
+                    next_height,
+                    window.height,
+                ),
+        ))
+    } else {
+        Some((next_x, next_y))
+    }
+}
+
+/// Splits the monitor bounds (`(x, y, width, height)`) into cells for the given number of windows, in reading order.
+///
+/// A single window fills the monitor; otherwise, the windows are laid out in two columns with as many rows as needed.
+/// With an odd number of windows, the last one spans the full width of its row.
+fn calculate_tile_geometries(
+    bounds: (i32, i32, i32, i32),
+    count: usize,
+) -> Vec<(i32, i32, i32, i32)> {
+    let (x, y, width, height) = bounds;
+
+    if count <= 1 {
+        return vec![bounds; count];
+    }
+
+    let rows = count.div_ceil(2) as i32;
+    let left_width = width / 2;
+    let row_height = height / rows;
+
+    (0..count)
+        .map(|index| {
+            let row = (index / 2) as i32;
+            let column = index % 2;
+
+            let cell_y = y + row * row_height;
+
+            // The last row takes up any leftover pixels.
+            let cell_height = if row == rows - 1 {
+                height - row * row_height
+            } else {
+                row_height
+            };
+
+            if index == count - 1 && column == 0 {
+                (x, cell_y, width, cell_height)
+            } else if column == 0 {
+                (x, cell_y, left_width, cell_height)
+            } else {
+                (x + left_width, cell_y, width - left_width, cell_height)
+            }
+        })
+        .collect()
+}
+
+/// Focuses the window after the currently focused one in the given order, wrapping around at the end.
+pub fn cycle(sort_order: &SortOrder, config: &Config) -> Result<()> {
+    cycle_in(&load_workspace(config)?, sort_order, config)
+}
+
+pub fn cycle_in(workspace: &Workspace, sort_order: &SortOrder, config: &Config) -> Result<()> {
+    let mut windows = get_current_workspace_windows(workspace, config)?;
+    sort_current_windows_by(&mut windows, sort_order);
+
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    if let Some(window) = find_next_window_in_cycle(&windows, &current_window_id) {
+        focus_window(window, workspace, config)?;
+    }
+
+    Ok(())
+}
+
+/// Lists the windows on the current workspace in the given order.
+pub fn list_windows(sort_order: &SortOrder, config: &Config) -> Result<Vec<Window>> {
+    let mut windows = current_windows(config)?;
+    sort_current_windows_by(&mut windows, sort_order);
+
+    Ok(windows)
+}
+
+/// Same as `list_windows`, along with the monitor that each window is on (if it's on one).
+///
+/// With `monitor`, only the windows on that monitor are listed, erroring if there's no such monitor.
+pub fn list_windows_with_monitors(
+    sort_order: &SortOrder,
+    monitor: Option<&MonitorIndex>,
+    config: &Config,
+) -> Result<Vec<(Window, Option<MonitorIndex>)>> {
+    let workspace = load_workspace(config)?;
+    let mut windows = get_current_workspace_windows(&workspace, config)?;
+    sort_current_windows_by(&mut windows, sort_order);
+
+    let windows = windows
+        .into_iter()
+        .map(|window| {
+            let monitor = workspace
+                .monitor_grid
+                .determine_which_monitor_window_is_on(&window)
+                .ok();
+
+            (window, monitor)
+        })
+        .collect();
+
+    match monitor {
+        Some(monitor) => filter_windows_on_monitor(
+            windows,
+            monitor,
+            workspace.monitor_grid.calculate_monitor_count() as usize,
+        ),
+        None => Ok(windows),
+    }
+}
+
+/// Keeps only the windows on the given monitor, erroring if the index is out of range.
+fn filter_windows_on_monitor(
+    windows: Vec<(Window, Option<MonitorIndex>)>,
+    monitor: &MonitorIndex,
+    monitor_count: usize,
+) -> Result<Vec<(Window, Option<MonitorIndex>)>> {
+    if monitor.0 >= monitor_count {
+        return Err(anyhow::anyhow!(
+            "Monitor index {monitor} out of range; {monitor_count} monitors detected"
+        ));
+    }
+
+    Ok(windows
+        .into_iter()
+        .filter(|(_, window_monitor)| window_monitor.as_ref() == Some(monitor))
+        .collect())
+}
+
+/// Counts the windows on each monitor, indexed by monitor. Monitors without any windows have a count of 0.
+pub fn count_windows_per_monitor(config: &Config) -> Result<Vec<usize>> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+
+    count_windows_by_monitor(&workspace.monitor_grid, &windows)
+}
+
+/// Lists the distinct window classes (sorted alphabetically) along with how many windows have each.
+///
+/// Excluded classes (`EWS_EXCLUDED_CLASSES` or `--exclude-class`) are still listed, so that it's easy to find the ones
+/// to exclude in the first place.
+pub fn list_window_classes(config: &Config) -> Result<Vec<(String, usize)>> {
+    let windows = match config.backend {
+        Backend::X11 => wmctrl::get_windows_config(&Config {
+            excluded_classes: Vec::new(),
+            ..config.clone()
+        })?,
+        Backend::I3 => i3::get_windows()?,
+    };
+
+    Ok(count_window_classes(&windows))
+}
+
+/// Counts the windows of each distinct class, sorted alphabetically regardless of case (e.g. `Navigator.firefox` before
+/// `slack.Slack`), with classes that only differ in case kept apart.
+fn count_window_classes(windows: &[Window]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+
+    for window in windows {
+        *counts.entry(window.window_class.as_str()).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(class, count)| (class.to_owned(), count))
+        .collect();
+
+    counts.sort_by_cached_key(|(class, _)| (class.to_lowercase(), class.clone()));
+    counts
+}
+
+/// Describes every monitor along with how many windows it has and whether the focused window is on it.
+pub fn get_monitor_statuses(config: &Config) -> Result<Vec<MonitorStatus>> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    build_monitor_statuses(&workspace.monitor_grid, &windows, &current_window_id)
+}
+
+/// Calculates the bounds (`(x, y, width, height)`) of the monitor that the focused window is on.
+pub fn get_active_monitor_bounds(config: &Config) -> Result<(i32, i32, i32, i32)> {
+    let workspace = load_workspace(config)?;
+    let windows = get_current_workspace_windows(&workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+
+    let (x, y, width, height) =
+        find_window_monitor_bounds(&workspace.monitor_grid, &windows, &current_window_id)?;
+    let (x, y) = workspace.to_screen_position(x, y);
+
+    Ok((x, y, width, height))
+}
+
+/// Looks up the focused window's class and title straight from its properties, so that it works for any window (e.g. a
+/// dialog or a dock) rather than only the ones that can be focused.
+pub fn describe_focused_window() -> Result<(WindowId, Option<String>, Option<String>)> {
+    let id = xdotool::get_current_focused_window_id()?;
+    let class = xprop::get_window_class(&id)?;
+    let title = xprop::get_window_title(&id)?;
+
+    Ok((id, class, title))
+}
+
+/// Lists the windows on the current workspace, sorted from left to right.
+pub fn current_windows(config: &Config) -> Result<Vec<Window>> {
+    get_current_workspace_windows(&load_workspace(config)?, config)
+}
+
+/// Focuses the target window, runs the given function, and then restores focus to whichever window was originally focused.
+pub fn with_focus_restored(target: &WindowId, f: impl FnOnce()) -> Result<()> {
+    run_with_focus_restored(
+        target,
+        f,
+        xdotool::get_current_focused_window_id,
+        wmctrl::focus_window_by_id,
+    )
+}
+
+/// Swaps Left and Right when `EWS_INVERT_DIRECTION` is set, for the commands that move by direction.
+fn resolve_direction(direction: FocusDirection, config: &Config) -> FocusDirection {
+    if config.invert_direction {
+        direction.mirrored_horizontally()
+    } else {
+        direction
+    }
+}
+
+/// Detects the workspace with the configured backend (and the monitors cache, if it's enabled), then normalizes it.
+///
+/// This is how every command (including the daemon's) gets the monitor layout, so that they all agree on it.
+pub(crate) fn load_workspace(config: &Config) -> Result<Workspace> {
+    let workspace = if config.backend == Backend::I3 {
+        i3::parse_workspace()?
+    } else if config.cache_monitors {
+        monitor_cache::load_workspace()?
+    } else {
+        xrandr::parse_workspace()?
+    };
+
+    normalize_workspace(workspace, config)
+}
+
+/// Applies any configured changes to the detected monitor layout before it's used for navigation.
+///
+/// Errors if the configured monitor order names a monitor that isn't detected.
+pub fn normalize_workspace(workspace: Workspace, config: &Config) -> Result<Workspace> {
+    let mut workspace = workspace;
+
+    // The order has to be applied before merging, which combines the names of the merged monitors.
+    if !config.monitor_order.is_empty() {
+        workspace = Workspace::new(
+            workspace
+                .monitor_grid
+                .reorder_columns(&config.monitor_order)?,
+        )
+        .with_origin(workspace.origin());
+    }
+
+    // Scales have to be applied first, since merging bakes them into the merged monitors' sizes.
+    if !config.monitor_scales.is_empty() {
+        workspace = Workspace::new(workspace.monitor_grid.with_scales(&config.monitor_scales))
+            .with_origin(workspace.origin());
+    }
+
+    if config.merge_stacked_monitors {
+        workspace = Workspace::new(workspace.monitor_grid.merge_stacked_monitors())
+            .with_origin(workspace.origin());
+    }
+
+    Ok(workspace)
+}
+
+/// Focuses the window, first moving it onto the workspace if it's been placed off-screen
+/// and switching to its desktop if it's on another one.
+fn focus_window(window: &Window, workspace: &Workspace, config: &Config) -> Result<()> {
+    if config.include_offscreen {
+        if let Some((x, y)) = get_onscreen_position(window, workspace) {
+            let (x, y) = workspace.to_screen_position(x, y);
+            wmctrl::move_window_by_id(&window.id, x, y)?;
+        }
+    }
+
+    let focus = get_focuser(config);
+
+    if config.workspace_all {
+        focus_window_on_desktop(
+            window,
+            wmctrl::get_current_desktop()?,
+            wmctrl::switch_to_desktop,
+            focus,
+        )?;
+    } else {
+        focus(&window.id)?;
+    }
+
+    if config.verify_focus {
+        verify_focus(&window.id, focus, || {
+            thread::sleep(VERIFY_FOCUS_DELAY);
+            xdotool::get_current_focused_window_id()
+        })?;
+    }
+
+    if config.maximize {
+        maximize_window(window, workspace)?;
+    }
+
+    if let Some(command) = &config.post_focus_command {
+        // The window is already focused by now, so a broken hook shouldn't turn that into a failure.
+        if let Err(error) = hook::run_post_focus_hook(command, window) {
+            log::warn!("Post-focus hook failed: {error:#}");
+        }
+    }
+
+    if config.notify {
+        if let Err(error) = notify::send(&window.window_class, &window.title) {
+            log::warn!("Failed to show a notification (is notify-send installed?): {error:#}");
+        }
+    }
+
+    Ok(())
+}
+
+fn get_focuser(config: &Config) -> fn(&WindowId) -> Result<()> {
+    if config.backend == Backend::I3 {
+        return i3::focus_window_by_id;
+    }
+
+    match config.focus_strategy {
+        FocusStrategy::WmctrlActivate => wmctrl::focus_window_by_id,
+        FocusStrategy::XdotoolActivate => xdotool::activate_window_by_id,
+        FocusStrategy::XdotoolFocus => xdotool::focus_window_by_id,
+    }
+}
+
+/// Maximizes the window on the monitor that it's on.
+///
+/// Plain maximizing can target the wrong monitor (e.g. the primary one), so the window is first resized to fill its
+/// monitor before being marked as maximized.
+fn maximize_window(window: &Window, workspace: &Workspace) -> Result<()> {
+    if let Some((x, y, width, height)) = get_maximized_bounds(window, workspace) {
+        let (x, y) = workspace.to_screen_position(x, y);
+        wmctrl::move_resize_window_by_id(&window.id, x, y, width, height)?;
+    }
+
+    wmctrl::set_window_state(&window.id, StateAction::Add, WindowState::MaximizedVert)?;
+    wmctrl::set_window_state(&window.id, StateAction::Add, WindowState::MaximizedHorz)
+}
+
+/// Determines the bounds of the monitor that the window is on, as `(x, y, width, height)`.
+///
+/// An off-screen window would have been moved onto the first monitor, so it's maximized there.
+fn get_maximized_bounds(window: &Window, workspace: &Workspace) -> Option<(i32, i32, i32, i32)> {
+    let monitor_index = if workspace.is_window_offscreen(window) {
+        MonitorIndex(0)
+    } else {
+        workspace
+            .monitor_grid
+            .determine_which_monitor_window_is_on(window)
+            .ok()?
+    };
+
+    workspace.monitor_grid.get_monitor_bounds(&monitor_index)
+}
+
+fn focus_window_on_desktop(
+    window: &Window,
+    current_desktop: Option<usize>,
+    switch_to_desktop: impl FnOnce(usize) -> Result<()>,
+    focus: impl FnOnce(&WindowId) -> Result<()>,
+) -> Result<()> {
+    if let Some(desktop) = get_desktop_to_switch_to(window, current_desktop) {
+        switch_to_desktop(desktop)?;
+    }
+
+    focus(&window.id)
+}
+
+/// Checks that the window is the focused one, focusing it a second time if it isn't yet.
+///
+/// Errors if the window still isn't focused after the second attempt.
+fn verify_focus(
+    window_id: &WindowId,
+    focus: impl Fn(&WindowId) -> Result<()>,
+    get_focused_window_id: impl Fn() -> Result<WindowId>,
+) -> Result<()> {
+    if get_focused_window_id()? == *window_id {
+        return Ok(());
+    }
+
+    log::debug!("Window {window_id:?} wasn't focused; trying again");
+    focus(window_id)?;
+
+    let focused_window_id = get_focused_window_id()?;
+
+    if focused_window_id != *window_id {
+        anyhow::bail!(
+            "Failed to focus window {window_id:?}; window {focused_window_id:?} is still focused"
+        );
+    }
+
+    Ok(())
+}
+
+/// Determines which desktop needs to be switched to before the window can be focused, if any.
+///
+/// Windows on all desktops (i.e. a desktop of -1) or with an unknown desktop never need a switch.
+fn get_desktop_to_switch_to(window: &Window, current_desktop: Option<usize>) -> Option<usize> {
+    let desktop = usize::try_from(window.desktop?).ok()?;
+
+    (Some(desktop) != current_desktop).then_some(desktop)
+}
+
+/// Determines where an off-screen window should be moved to so that it's back on the workspace (i.e. the origin of the
+/// first monitor). Returns `None` if the window is already on-screen.
+fn get_onscreen_position(window: &Window, workspace: &Workspace) -> Option<(i32, i32)> {
+    if workspace.is_window_offscreen(window) {
+        workspace.monitor_grid.get_monitor_origin(&MonitorIndex(0))
+    } else {
+        None
+    }
+}
+
+fn get_current_workspace_windows(workspace: &Workspace, config: &Config) -> Result<Vec<Window>> {
+    let windows = match config.backend {
+        Backend::X11 => wmctrl::get_windows_config(config)?,
+        Backend::I3 => i3::get_windows()?
+            .into_iter()
+            .filter(|window| !wmctrl::is_excluded_class(window, &config.excluded_classes))
+            .collect(),
+    };
+
+    let mut current_workspace_windows = windows
+        .into_iter()
+        .map(|window| workspace.normalize_window(window))
+        .filter(|window| is_window_on_workspace(window, workspace, config))
+        .filter(|window| is_window_of_only_class(window, config.only_class.as_deref()))
+        .map(|window| {
+            if config.lenient_bounds {
+                clamp_to_nearest_monitor(window, &workspace.monitor_grid)
+            } else {
+                window
+            }
+        })
+        .collect::<Vec<Window>>();
+
+    if let Some(since) = config.since {
+        let user_times = current_workspace_windows
+            .iter()
+            .map(|window| xprop::get_user_time(&window.id).ok().flatten())
+            .collect::<Vec<Option<u64>>>();
+
+        current_workspace_windows = filter_recently_used(
+            current_workspace_windows,
+            &user_times,
+            since,
+            config.include_untimed,
+        );
+    }
+
+    sort_windows(&mut current_workspace_windows);
+
+    Ok(current_workspace_windows)
+}
+
+/// Moves a window that isn't within any monitor (as far as `determine_which_monitor_window_is_on` is concerned) to the
+/// closest position on the nearest monitor, so that it's treated as being on that monitor. Other windows are kept as-is.
+///
+/// Only the window's geometry here changes; the actual window isn't moved.
+fn clamp_to_nearest_monitor(window: Window, monitor_grid: &MonitorGrid) -> Window {
+    if !window.has_geometry()
+        || monitor_grid
+            .determine_which_monitor_window_is_on(&window)
+            .is_ok()
+    {
+        return window;
+    }
+
+    let Some((x, y, width, height)) = monitor_grid
+        .find_nearest_monitor(&window)
+        .and_then(|monitor| monitor_grid.get_monitor_bounds(&monitor))
+    else {
+        return window;
+    };
+
+    log::debug!(
+        "Window {} isn't on any monitor; treating it as being on the one at {x},{y}",
+        window.id
+    );
+
+    // A window counts as being on a monitor when its offsets (minus its decoration) are within the monitor.
+    let max_x = (x + width - 1).max(x);
+    let max_y = (y + height - window.decoration_height() - 1).max(y);
+
+    Window {
+        x_offset: window.x_offset.clamp(x, max_x),
+        y_offset: window.y_offset.clamp(y, max_y),
+        ..window
+    }
+}
+
+/// Sorts the windows from left to right, then top to bottom for windows at the same x-offset, so that the first/last
+/// window of a monitor is always the same one no matter what order `wmctrl` listed them in.
+///
+/// Windows at the exact same position fall back to their stacking order (so that cycling through overlapping windows
+/// is predictable), and then to their IDs.
+fn sort_windows(windows: &mut [Window]) {
+    windows.sort_by_key(|window| {
+        (
+            window.x_offset,
+            window.y_offset,
+            window.stacking_index,
+            window.id.0,
+        )
+    });
+}
+
+/// Same as `sort_windows_by`, but first reads what the order needs from the windows' properties: the window manager's
+/// own stacking order for `Stacking`, and each window's user time for `Mru`.
+fn sort_current_windows_by(windows: &mut [Window], sort_order: &SortOrder) {
+    if *sort_order == SortOrder::Mru {
+        let user_times: Vec<Option<u64>> = windows
+            .iter()
+            .map(|window| xprop::get_user_time(&window.id).ok().flatten())
+            .collect();
+
+        sort_windows_by_user_time(windows, &user_times);
+        return;
+    }
+
+    if *sort_order == SortOrder::Stacking {
+        let result = xprop::get_client_list_stacking()
+            .map(|stacking| apply_stacking_order(windows, &stacking));
+
+        if let Err(error) = result {
+            log::warn!(
+                "Failed to read the stacking order (is xprop installed?); falling back to wmctrl's: {error:#}"
+            );
+        }
+    }
+
+    sort_windows_by(windows, sort_order);
+}
+
+/// Sorts the windows by their user time (`_NET_WM_USER_TIME`), most recent first, where `user_times` holds each
+/// window's user time (if it has one). The windows without one come last, from left to right.
+fn sort_windows_by_user_time(windows: &mut [Window], user_times: &[Option<u64>]) {
+    let mut timed_windows: Vec<(Window, Option<u64>)> = windows
+        .iter()
+        .cloned()
+        .zip(user_times.iter().copied())
+        .collect();
+
+    timed_windows.sort_by_key(|(window, user_time)| {
+        (
+            user_time.is_none(),
+            std::cmp::Reverse(*user_time),
+            window.x_offset,
+            window.y_offset,
+            window.id.0,
+        )
+    });
+
+    for (slot, (window, _)) in windows.iter_mut().zip(timed_windows) {
+        *slot = window;
+    }
+}
+
+/// Replaces the windows' stacking indices with their positions in the given stacking order (bottom-most first).
+/// Windows that aren't in it go below every window that is, keeping their relative order.
+fn apply_stacking_order(windows: &mut [Window], stacking: &[WindowId]) {
+    for window in windows.iter_mut() {
+        window.stacking_index = match stacking.iter().position(|id| *id == window.id) {
+            Some(position) => position + 1,
+            None => 0,
+        };
+    }
+}
+
+fn sort_windows_by(windows: &mut [Window], sort_order: &SortOrder) {
+    match sort_order {
+        SortOrder::Position => sort_windows(windows),
+        SortOrder::Class => windows.sort_by_cached_key(|window| {
+            (
+                window.window_class.to_lowercase(),
+                window.x_offset,
+                window.stacking_index,
+            )
+        }),
+        SortOrder::Title => windows.sort_by_cached_key(|window| {
+            (
+                window.title.to_lowercase(),
+                window.x_offset,
+                window.stacking_index,
+            )
+        }),
+        // Without any user times to go by (see `sort_current_windows_by`), there's nothing more recent than anything
+        // else, so the windows stay in their left to right order.
+        SortOrder::Mru => sort_windows(windows),
+        // The top-most window comes last in the stacking order.
+        SortOrder::Stacking => {
+            windows.sort_by_key(|window| std::cmp::Reverse(window.stacking_index))
+        }
+    }
+}
+
+/// Finds the window after the current one, wrapping around to the first window.
+/// If the current window isn't in the list (e.g. nothing is focused), the first window is used.
+fn find_next_window_in_cycle<'a>(
+    windows: &'a [Window],
+    current_window_id: &WindowId,
+) -> Option<&'a Window> {
+    let next_index = windows
+        .iter()
+        .position(|window| window.id == *current_window_id)
+        .map_or(0, |index| (index + 1) % windows.len());
+
+    windows.get(next_index)
+}
+
+/// Windows whose geometry isn't known are kept, so that the commands that don't need positions can still find them;
+/// the ones that do error out once they try to place them on a monitor.
+fn is_window_on_workspace(window: &Window, workspace: &Workspace, config: &Config) -> bool {
+    !window.has_geometry()
+        || config.workspace_all
+        || workspace.is_window_in_current_workspace(window)
+        || (config.include_offscreen && workspace.is_window_offscreen(window))
+}
+
+/// Whether the window can be focused given the `--only-class` filter (if any).
+fn is_window_of_only_class(window: &Window, only_class: Option<&str>) -> bool {
+    only_class.is_none_or(|only_class| window.class_contains(only_class))
+}
+
+fn get_other_windows_on_current_monitor(
+    workspace: &Workspace,
+    config: &Config,
+) -> Result<Vec<WindowId>> {
+    let windows = get_current_workspace_windows(workspace, config)?;
+    let current_window_id = xdotool::get_current_focused_window_id()?;
+    let windows_by_monitor_index = index_windows_by_monitor(&workspace.monitor_grid, &windows)?;
+
+    let other_windows = get_other_windows_on_monitor(&current_window_id, &windows_by_monitor_index);
+
+    if other_windows.is_empty() {
+        log::info!("No other windows found on the current monitor");
+    }
+
+    Ok(other_windows)
+}
+
+/// Finds the ids of the other windows on the same monitor as the given window.
+///
+/// Returns nothing if the window isn't on any monitor (e.g. the desktop is focused).
+fn get_other_windows_on_monitor(
+    window_id: &WindowId,
+    windows_by_monitor_index: &HashMap<MonitorIndex, Vec<&Window>>,
+) -> Vec<WindowId> {
+    windows_by_monitor_index
+        .values()
+        .find(|windows| windows.iter().any(|window| &window.id == window_id))
+        .map(|windows| {
+            windows
+                .iter()
+                .filter(|window| &window.id != window_id)
+                .map(|window| window.id.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn count_windows_by_monitor(
+    monitor_grid: &MonitorGrid,
+    windows: &Vec<Window>,
+) -> Result<Vec<usize>> {
+    let windows_by_monitor_index = index_windows_by_monitor(monitor_grid, windows)?;
+
+    Ok((0..monitor_grid.calculate_monitor_count() as usize)
+        .map(|index| windows_by_monitor_index[&MonitorIndex(index)].len())
+        .collect())
+}
+
+/// Joins the monitors' metadata with the windows on them.
+fn build_monitor_statuses(
+    monitor_grid: &MonitorGrid,
+    windows: &Vec<Window>,
+    current_window_id: &WindowId,
+) -> Result<Vec<MonitorStatus>> {
+    let windows_by_monitor_index = index_windows_by_monitor(monitor_grid, windows)?;
+    let monitors_by_window = index_monitors_by_window(monitor_grid, windows)?;
+    let current_monitor = get_current_monitor(current_window_id, &monitors_by_window);
+
+    Ok(monitor_grid
+        .0
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let monitor_index = MonitorIndex(index);
+            let (x, y, width, height) = monitor_grid
+                .get_monitor_bounds(&monitor_index)
+                .unwrap_or_default();
+
+            MonitorStatus {
+                index,
+                name: monitor.name.clone(),
+                width,
+                height,
+                x,
+                y,
+                primary: monitor.primary,
+                active: current_monitor.as_ref() == Some(&monitor_index),
+                window_count: windows_by_monitor_index
+                    .get(&monitor_index)
+                    .map_or(0, |windows| windows.len()),
+            }
+        })
+        .collect())
+}
+
+/// Groups the windows by the monitor that they're on. Every monitor has an entry, even if it doesn't have any windows.
+fn index_windows_by_monitor<'a>(
+    monitor_grid: &MonitorGrid,
+    windows: &'a Vec<Window>,
+) -> Result<HashMap<MonitorIndex, Vec<&'a Window>>> {
+    let mut windows_by_monitor_index: HashMap<MonitorIndex, Vec<&Window>> =
+        (0..monitor_grid.calculate_monitor_count() as usize)
+            .map(|index| (MonitorIndex(index), Vec::new()))
+            .collect();
+
+    for window in windows {
+        let monitor_index = monitor_grid.determine_which_monitor_window_is_on(window)?;
+
+        windows_by_monitor_index
+            .entry(monitor_index)
+            .or_default()
+            .push(window);
+    }
+
+    Ok(windows_by_monitor_index)
+}
+
+fn index_monitors_by_window(
+    monitor_grid: &MonitorGrid,
+    windows: &Vec<Window>,
+) -> Result<HashMap<WindowId, MonitorIndex>> {
+    let mut monitors_by_window: HashMap<WindowId, MonitorIndex> = HashMap::new();
+
+    for window in windows {
+        monitors_by_window.insert(
+            window.id.clone(),
+            monitor_grid.determine_which_monitor_window_is_on(window)?,
+        );
+    }
+
+    Ok(monitors_by_window)
+}
+
+fn find_window_monitor_bounds(
+    monitor_grid: &MonitorGrid,
+    windows: &[Window],
+    window_id: &WindowId,
+) -> Result<(i32, i32, i32, i32)> {
+    let window = windows
+        .iter()
+        .find(|window| window.id == *window_id)
+        .ok_or_else(|| anyhow::anyhow!("Window {window_id} is not on the current workspace"))?;
+
+    let monitor_index = monitor_grid.determine_which_monitor_window_is_on(window)?;
+
+    monitor_grid
+        .get_monitor_bounds(&monitor_index)
+        .ok_or_else(|| anyhow::anyhow!("Monitor {} does not exist", monitor_index.0))
+}
+
+/// Finds the monitor of the current window, if it's one of the tracked windows.
+fn get_current_monitor(
+    current_window_id: &WindowId,
+    monitors_by_window: &HashMap<WindowId, MonitorIndex>,
+) -> Option<MonitorIndex> {
+    monitors_by_window.get(current_window_id).cloned()
+}
+
+/// Mirrors the monitor's index, i.e. 0 <-> last, 1 <-> second-last, and so on.
+///
+/// With an odd number of monitors, the middle one is its own opposite.
+fn get_opposite_monitor(current_monitor: &MonitorIndex, monitor_count: usize) -> MonitorIndex {
+    MonitorIndex(
+        monitor_count
+            .saturating_sub(1)
+            .saturating_sub(current_monitor.0),
+    )
+}
+
+fn run_with_focus_restored(
+    target: &WindowId,
+    f: impl FnOnce(),
+    get_focused_window_id: impl FnOnce() -> Result<WindowId>,
+    mut focus: impl FnMut(&WindowId) -> Result<()>,
+) -> Result<()> {
+    let original_window_id = get_focused_window_id()?;
+
+    focus(target)?;
+    f();
+    focus(&original_window_id)
+}
+
+/// Focuses the first window of the class, or runs `launch` if there isn't one.
+fn activate_or_launch_with(
+    windows: &[Window],
+    class: &str,
+    focus: impl FnOnce(&Window) -> Result<()>,
+    launch: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    match windows.iter().find(|window| window.matches_class(class)) {
+        Some(window) => focus(window),
+        None => {
+            log::info!("No windows found with class {class}, launching it");
+            launch()
+        }
+    }
+}
+
+/// Raises every window of the given class in left-to-right order, except for the leftmost window which is raised last
+/// so that it's the one that ends up focused. Returns how many windows were raised.
+///
+/// Assumes that the windows are already sorted from left to right.
+fn raise_windows_of_class(
+    windows: &[Window],
+    class: &str,
+    mut raise: impl FnMut(&WindowId) -> Result<()>,
+) -> Result<usize> {
+    let matching_windows: Vec<&Window> = windows
+        .iter()
+        .filter(|window| window.matches_class(class))
+        .collect();
+
+    if let Some((leftmost_window, other_windows)) = matching_windows.split_first() {
+        for window in other_windows {
+            raise(&window.id)?;
+        }
+
+        raise(&leftmost_window.id)?;
+    }
+
+    Ok(matching_windows.len())
+}
+
+/// Finds the neighbour of the current window among the windows that share its class, wrapping around at the ends.
+/// Returns `None` if the current window isn't in the list or is the only window of its class.
+///
+/// Assumes that the windows are already sorted from left to right.
+fn find_next_window_of_same_class<'a>(
+    windows: &'a [Window],
+    current_window_id: &WindowId,
+    direction: &FocusDirection,
+) -> Option<&'a Window> {
+    let current_window = windows
+        .iter()
+        .find(|window| window.id == *current_window_id)?;
+
+    let same_class_windows: Vec<&Window> = windows
+        .iter()
+        .filter(|window| window.window_class == current_window.window_class)
+        .collect();
+
+    if same_class_windows.len() < 2 {
+        return None;
+    }
+
+    let current_index = same_class_windows
+        .iter()
+        .position(|window| window.id == *current_window_id)? as i32;
+
+    let next_index =
+        (current_index + direction.to_int()).rem_euclid(same_class_windows.len() as i32);
+
+    same_class_windows.get(next_index as usize).copied()
+}
+
+/// Finds the window after (or, for left/up, before) the current one among the windows on its monitor, in the order
+/// they're given in (i.e. from left to right), wrapping around at the ends.
+///
+/// Returns nothing if the current window isn't one of the windows or is the only one on its monitor.
+fn find_next_window_on_monitor<'a>(
+    monitor_grid: &MonitorGrid,
+    windows: &'a Vec<Window>,
+    current_window_id: &WindowId,
+    direction: &FocusDirection,
+) -> Result<Option<&'a Window>> {
+    let monitors_by_window = index_monitors_by_window(monitor_grid, windows)?;
+
+    let Some(current_monitor) = get_current_monitor(current_window_id, &monitors_by_window) else {
+        return Ok(None);
+    };
+
+    let monitor_windows: Vec<&Window> = windows
+        .iter()
+        .filter(|window| monitors_by_window.get(&window.id) == Some(&current_monitor))
+        .collect();
+
+    if monitor_windows.len() < 2 {
+        return Ok(None);
+    }
+
+    let Some(current_index) = monitor_windows
+        .iter()
+        .position(|window| window.id == *current_window_id)
+    else {
+        return Ok(None);
+    };
+
+    let next_index =
+        (current_index as i32 + direction.to_int()).rem_euclid(monitor_windows.len() as i32);
+
+    Ok(monitor_windows.get(next_index as usize).copied())
+}
+
+/// Builds a chooser line for each window, e.g. `main.rs - Visual Studio Code (code.Code) [0x05000006]`.
+///
+/// The window's ID comes last so that the chosen line can be mapped back to its window, even when titles are duplicated.
+fn build_menu_lines(windows: &[Window]) -> Vec<String> {
+    windows
+        .iter()
+        .map(|window| {
+            format!(
+                "{} ({}) [{}]",
+                window.title,
+                window.window_class,
+                window.id.to_hex_string()
+            )
+        })
+        .collect()
+}
+
+/// Maps the line chosen from `build_menu_lines` back to its window's ID.
+fn parse_menu_selection(selection: &str) -> Result<WindowId> {
+    let invalid_selection = || anyhow::anyhow!("Invalid selection: {selection}");
+
+    let (_, id) = selection
+        .trim_end()
+        .strip_suffix(']')
+        .and_then(|selection| selection.rsplit_once(" ["))
+        .ok_or_else(invalid_selection)?;
+
+    if !id.starts_with("0x") {
+        return Err(invalid_selection());
+    }
+
+    WindowId::from_hex_str(id).map_err(|_| invalid_selection())
+}
+
+/// Finds the leftmost window whose center x is within the range (start inclusive, end exclusive).
+fn find_window_in_pixel_range(windows: &[Window], (start, end): (i32, i32)) -> Option<&Window> {
+    windows
+        .iter()
+        .filter(|window| (start..end).contains(&window.center().0))
+        .min_by_key(|window| (window.x_offset, window.y_offset, window.stacking_index))
+}
+
+/// Keeps the windows whose user time is within `since` of the most recent user time, where `user_times` holds each
+/// window's user time (if it has one) in the same order as the windows.
+///
+/// User times are X server timestamps (in milliseconds) rather than wall-clock times, so the most recently used window
+/// stands in for "now". Windows without a user time are kept only when `include_untimed` is set.
+fn filter_recently_used(
+    windows: Vec<Window>,
+    user_times: &[Option<u64>],
+    since: Duration,
+    include_untimed: bool,
+) -> Vec<Window> {
+    let Some(now) = user_times.iter().flatten().max().copied() else {
+        return if include_untimed { windows } else { Vec::new() };
+    };
+
+    let threshold = now.saturating_sub(since.as_millis() as u64);
+
+    windows
+        .into_iter()
+        .zip(user_times)
+        .filter(|(_, user_time)| {
+            user_time.map_or(include_untimed, |user_time| user_time >= threshold)
+        })
+        .map(|(window, _)| window)
+        .collect()
+}
+
+/// Finds the window with the smallest user time (i.e. the one that was used the longest ago), where `user_times`
+/// holds each window's user time (if it has one) in the same order as the windows.
+///
+/// Windows without a user time are only picked when none of the windows have one, in which case the first window in
+/// wmctrl's output (i.e. the window manager's client list) is picked. Ties are also broken by that order.
+fn find_oldest_window<'a>(windows: &'a [Window], user_times: &[Option<u64>]) -> Option<&'a Window> {
+    windows
+        .iter()
+        .zip(user_times)
+        .min_by_key(|(window, user_time)| (user_time.is_none(), **user_time, window.stacking_index))
+        .map(|(window, _)| window)
+}
+
+/// Finds the window that covers the most of the monitor with the given bounds (`(x, y, width, height)`). Ties are
+/// broken by taking the smaller window (i.e. the one with more of itself on the monitor), then the one listed last by
+/// wmctrl.
+fn find_dominant_window(
+    windows: &[Window],
+    monitor_bounds: (i32, i32, i32, i32),
+) -> Option<&Window> {
+    windows
+        .iter()
+        .map(|window| (window, window.overlap_area(monitor_bounds)))
+        .filter(|(_, overlap_area)| *overlap_area > 0)
+        .max_by(|(a, a_overlap_area), (b, b_overlap_area)| {
+            a_overlap_area
+                .cmp(b_overlap_area)
+                .then_with(|| b.area().cmp(&a.area()))
+                .then_with(|| a.stacking_index.cmp(&b.stacking_index))
+        })
+        .map(|(window, _)| window)
+}
+
+/// Finds the window with the given ID, erroring if it isn't one of the windows.
+fn find_window_by_id<'a>(windows: &'a [Window], window_id: &WindowId) -> Result<&'a Window> {
+    windows
+        .iter()
+        .find(|window| window.id == *window_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Window {} isn't one of the windows on the current workspace",
+                window_id.to_hex_string()
+            )
+        })
+}
+
+/// Finds the window with the greatest area. Ties are broken by taking the leftmost window (i.e. smallest x-offset).
+fn find_largest_window(windows: &[Window]) -> Option<&Window> {
+    windows.iter().max_by(|a, b| {
+        a.area()
+            .cmp(&b.area())
+            .then_with(|| b.x_offset.cmp(&a.x_offset))
+    })
+}
+
+/// How `find_closest_window` moves between windows and monitors.
+#[derive(Clone, Copy, Debug)]
+struct NavigationOptions {
+    /// Whether to wrap around at the edges of the grid (along the direction's axis).
+    wrap: bool,
+    /// Whether to only move between the windows on the current monitor.
+    current_monitor_only: bool,
+    /// Whether to move straight onto the next monitor instead of to the next window on the current one.
+    leave_current_monitor: bool,
+    /// Where to go when the next monitor over is empty, instead of carrying on in the same direction.
+    empty_monitor_preference: Option<EmptyMonitorPreference>,
+}
+
+impl Default for NavigationOptions {
+    fn default() -> Self {
+        NavigationOptions {
+            wrap: true,
+            current_monitor_only: false,
+            leave_current_monitor: false,
+            empty_monitor_preference: None,
+        }
+    }
+}
+
+/// Finds the closest window to the current window based on the specified focus direction.
+///
+/// # Parameters
+/// - `current_window_id`: The ID of the currently focused window.
+/// - `monitor_grid`: A reference to the monitor grid containing all monitors and their respective windows.
+/// - `windows`: A vector of references to all windows.
+/// - `focus_direction`: The direction in which to search for the closest window.
+/// - `options`: How to move between windows and monitors (wrapping, staying on the current monitor, etc.).
+///
+/// # Returns
+/// - If a valid window is found, it returns an `Option<&Window>`.
+/// - If no valid window is found (e.g., if there are no windows or the current window does not exist), it returns `None`.
+fn find_closest_window(
+    current_window_id: &WindowId,
+    monitor_grid: &MonitorGrid,
+    windows: &Vec<Window>,
+    direction: &FocusDirection,
+    options: &NavigationOptions,
+) -> Result<Option<Window>> {
+    let NavigationOptions {
+        wrap,
+        current_monitor_only,
+        leave_current_monitor,
+        empty_monitor_preference,
+    } = *options;
+
+    if windows.is_empty() {
+        return Ok(None);
+    }
+
+    let windows_by_monitor = index_windows_by_monitor(monitor_grid, windows)?;
+    let monitors_by_window = index_monitors_by_window(monitor_grid, windows)?;
+
+    let Some(current_monitor) = get_current_monitor(current_window_id, &monitors_by_window) else {
+        // Nothing that's tracked is focused (e.g. the desktop/root window after clicking on it),
+        // so start from the first window of the first monitor (that has any windows) instead.
+        let first_window = (0..monitor_grid.calculate_monitor_count() as usize)
+            .find_map(|index| windows_by_monitor.get(&MonitorIndex(index))?.first());
+
+        return Ok(first_window.map(|window| (*window).clone()));
+    };
+
+    let current_monitor_windows = &windows_by_monitor[&current_monitor];
+
+    if let Some(current_window_position) = current_monitor_windows
+        .iter()
+        .position(|w| w.id == *current_window_id)
+    {
+        if current_monitor_only {
+            return Ok(find_window_on_same_monitor(
+                direction,
+                current_monitor_windows,
+                current_window_position,
+                wrap,
+            )
+            .cloned());
+        }
+
+        if leave_current_monitor
+            || is_closest_window_not_on_current_monitor(
+                direction,
+                current_monitor_windows,
+                current_window_position,
+            )
+        {
+            // Without wrapping, running off the edge of the grid means there's nothing to focus.
+            let get_next_monitor = |monitor: &MonitorIndex| {
+                monitor_grid.get_next_monitor_with_wrap(monitor, direction, wrap)
+            };
+
+            let Some(mut next_monitor) = get_next_monitor(&current_monitor) else {
+                return Ok(None);
+            };
+
+            let is_populated = |monitor: &MonitorIndex| {
+                windows_by_monitor
+                    .get(monitor)
+                    .is_some_and(|windows| !windows.is_empty())
+            };
+
+            // When the monitor next over is empty, the preferred neighbor of it (if any has windows) wins over
+            // carrying on in the same direction.
+            if let Some(preference) = empty_monitor_preference {
+                if preference.applies_to(direction) && !is_populated(&next_monitor) {
+                    if let Some(monitor) =
+                        monitor_grid.find_nearest_across(&next_monitor, direction, |monitor| {
+                            *monitor != current_monitor && is_populated(monitor)
+                        })
+                    {
+                        next_monitor = monitor;
+                    }
+                }
+            }
+
+            // Since moving between columns can change rows, the walk isn't guaranteed to come back around
+            // to the current monitor; track the visited monitors so that we always terminate.
+            let mut visited_monitors = HashSet::from([current_monitor.clone()]);
+
+            loop {
+                if let Some(window) =
+                    find_next_monitor_window(&windows_by_monitor, &next_monitor, direction)
+                {
+                    return Ok(Some(window.clone()));
+                }
+
+                if !visited_monitors.insert(next_monitor.clone()) {
+                    return Ok(None);
+                }
+
+                let Some(monitor) = get_next_monitor(&next_monitor) else {
+                    return Ok(None);
+                };
+
+                next_monitor = monitor;
+            }
+        } else {
+            let position = (current_window_position as i32 + direction.to_int()) as usize;
+            Ok(Some(current_monitor_windows[position].clone()))
+        }
+    } else {
+        Err(anyhow::anyhow!(
+            "Invariant violated: current focused window not found on current monitor"
+        ))
+    }
+}
+
+/// Re-picks among the windows tied with the chosen one by how near they are to the mouse pointer (see
+/// `break_tie_by_pointer`), keeping the chosen one if the pointer's location can't be read.
+fn pick_nearest_to_pointer(
+    workspace: &Workspace,
+    windows: &[Window],
+    current_window_id: &WindowId,
+    chosen_window: Window,
+) -> Window {
+    let Some((x, y)) = xdotool::get_mouse_location()
+        .inspect_err(|error| log::debug!("Failed to read the mouse location: {error:#}"))
+        .ok()
+    else {
+        return chosen_window;
+    };
+
+    let monitor_grid = &workspace.monitor_grid;
+    let chosen_monitor = monitor_grid
+        .determine_which_monitor_window_is_on(&chosen_window)
+        .ok();
+
+    let current_window = windows
+        .iter()
+        .find(|window| window.id == *current_window_id);
+    let candidates: Vec<&Window> = windows
+        .iter()
+        .filter(|window| window.id != *current_window_id)
+        .filter(|window| {
+            monitor_grid
+                .determine_which_monitor_window_is_on(window)
+                .ok()
+                == chosen_monitor
+        })
+        .collect();
+
+    break_tie_by_pointer(
+        &chosen_window,
+        current_window,
+        &candidates,
+        workspace.from_screen_position(x, y),
+    )
+    .clone()
+}
+
+/// Among the candidates (the other windows on the chosen window's monitor), picks the one nearest the pointer (by its
+/// center) out of those at the same x offset as the chosen window, i.e. the ones that are just as far in the direction.
+///
+/// When the chosen window is at the same x offset as the current one (i.e. when stepping through a stack of windows),
+/// there's no tie to break; picking by the pointer there could keep bouncing between the same windows.
+fn break_tie_by_pointer<'a>(
+    chosen_window: &'a Window,
+    current_window: Option<&Window>,
+    candidates: &[&'a Window],
+    pointer: (i32, i32),
+) -> &'a Window {
+    if current_window.is_some_and(|window| window.x_offset == chosen_window.x_offset) {
+        return chosen_window;
+    }
+
+    let distance_to_pointer = |window: &Window| {
+        let (x, y) = window.center();
+        let (dx, dy) = ((x - pointer.0) as i64, (y - pointer.1) as i64);
+
+        dx * dx + dy * dy
+    };
+
+    std::iter::once(chosen_window)
+        .chain(
+            candidates
+                .iter()
+                .copied()
+                .filter(|window| window.x_offset == chosen_window.x_offset),
+        )
+        .min_by_key(|window| distance_to_pointer(window))
+        .unwrap_or(chosen_window)
+}
+
+/// Given the windows of the current monitor, and the direction we want to focus to,
+/// determines if we need to look at another monitor to find the correct window to focus to.
+///
+/// That is, if we're already at the leftmost/rightmost window, we need to look at the next
+/// monitor to find the window to focus on. Up/Down always move between monitors.
+fn is_closest_window_not_on_current_monitor(
+    direction: &FocusDirection,
+    current_monitor_windows: &[&Window],
+    current_window_position: usize,
+) -> bool {
+    if current_monitor_windows.len() == 1 {
+        true
+    } else {
+        match direction {
+            FocusDirection::Left => current_window_position == 0,
+            FocusDirection::Right => current_window_position == current_monitor_windows.len() - 1,
+            FocusDirection::Up | FocusDirection::Down => true,
+        }
+    }
+}
+
+/// Finds the next window to the left/right of the current one without leaving the current monitor, wrapping around
+/// to the other end of the monitor (if wrapping is enabled) instead of moving onto the next monitor.
+///
+/// Up/Down always move between monitors, so they never find anything.
+fn find_window_on_same_monitor<'a>(
+    direction: &FocusDirection,
+    current_monitor_windows: &[&'a Window],
+    current_window_position: usize,
+    wrap: bool,
+) -> Option<&'a Window> {
+    let last_position = current_monitor_windows.len() - 1;
+
+    let position = match direction {
+        FocusDirection::Left if current_window_position == 0 => wrap.then_some(last_position)?,
+        FocusDirection::Left => current_window_position - 1,
+        FocusDirection::Right if current_window_position == last_position => wrap.then_some(0)?,
+        FocusDirection::Right => current_window_position + 1,
+        FocusDirection::Up | FocusDirection::Down => return None,
+    };
+
+    Some(current_monitor_windows[position])
+}
+
+/// Used to "find the next monitor's window", using the focus direction as a signal for which side
+/// of a monitor's windows to focus to.
+///
+/// That is, if switching to the left monitor, take the farthest right (i.e. last) window on the monitor.
+/// If switching to the right monitor, take the farthest left (i.e. first) window on the monitor.
+/// If switching to the monitor above/below, also take the farthest left (i.e. first) window on the monitor.
+fn find_next_monitor_window<'a>(
+    windows_by_monitor: &'a HashMap<MonitorIndex, Vec<&'a Window>>,
+    monitor: &MonitorIndex,
+    direction: &FocusDirection,
+) -> Option<&'a Window> {
+    if let Some(windows) = windows_by_monitor.get(monitor) {
+        match direction {
+            FocusDirection::Left => windows.last().map(|v| &**v),
+            FocusDirection::Right | FocusDirection::Up | FocusDirection::Down => {
+                windows.first().map(|v| &**v)
+            }
+        }
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    mod find_closest_window {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_mock_windows() -> Vec<Window> {
+            // Note: Windows are deliberately not in order by ID so that they can be tested for proper ordering.
+            // The proper order is the order of the IDs.
+            vec![
+                Window {
+                    id: WindowId(5),
+                    x_offset: 5360,
+                    y_offset: 0,
+                    width: 30,
+                    height: 40,
+                    window_class: "class1".to_string(),
+                    title: "title1".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(3),
+                    x_offset: 1920,
+                    y_offset: 0,
+                    width: 30,
+                    height: 40,
+                    window_class: "class1".to_string(),
+                    title: "title1".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(1),
+                    x_offset: 0,
+                    y_offset: 0,
+                    width: 10,
+                    height: 10,
+                    window_class: "class1".to_string(),
+                    title: "title1".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(2),
+                    x_offset: 0,
+                    y_offset: 1080,
+                    width: 70,
+                    height: 80,
+                    window_class: "class2".to_string(),
+                    title: "title2".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(4),
+                    x_offset: 3000,
+                    y_offset: 0,
+                    width: 70,
+                    height: 80,
+                    window_class: "class2".to_string(),
+                    title: "title2".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(6),
+                    x_offset: 5360,
+                    y_offset: 1200,
+                    width: 70,
+                    height: 80,
+                    window_class: "class2".to_string(),
+                    title: "title2".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+            ]
+        }
+
+        fn create_mock_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        fn get_result(window_id: usize, direction: FocusDirection) -> WindowId {
+            let windows = create_mock_windows();
+            let monitor_grid = create_mock_monitor_grid();
+            let current_window_id = WindowId(window_id);
+
+            find_closest_window(
+                &current_window_id,
+                &monitor_grid,
+                &windows,
+                &direction,
+                &NavigationOptions::default(),
+            )
+            .unwrap()
+            .unwrap()
+            .id
+        }
+
+        fn get_only_class_result(
+            window_id: usize,
+            only_class: &str,
+            direction: FocusDirection,
+        ) -> Option<WindowId> {
+            let windows = create_mock_windows()
+                .into_iter()
+                .filter(|window| is_window_of_only_class(window, Some(only_class)))
+                .collect();
+
+            find_closest_window(
+                &WindowId(window_id),
+                &create_mock_monitor_grid(),
+                &windows,
+                &direction,
+                &NavigationOptions::default(),
+            )
+            .unwrap()
+            .map(|window| window.id)
+        }
+
+        #[test]
+        fn test_only_class_skips_other_classes() {
+            // Window 3 (class1) is the first window on the middle monitor, so it gets skipped over.
+            assert_eq!(
+                get_only_class_result(2, "class2", FocusDirection::Right),
+                Some(WindowId(4))
+            );
+            assert_eq!(
+                get_only_class_result(4, "CLASS2", FocusDirection::Right),
+                Some(WindowId(6))
+            );
+            assert_eq!(
+                get_only_class_result(3, "class1", FocusDirection::Left),
+                Some(WindowId(1))
+            );
+        }
+
+        #[test]
+        fn test_only_class_single_match_stays() {
+            let mut windows = create_mock_windows();
+            windows.retain(|window| window.id == WindowId(4));
+
+            for direction in [FocusDirection::Left, FocusDirection::Right] {
+                let result = find_closest_window(
+                    &WindowId(4),
+                    &create_mock_monitor_grid(),
+                    &windows,
+                    &direction,
+                    &NavigationOptions::default(),
+                )
+                .unwrap()
+                .map(|window| window.id);
+
+                assert_eq!(result, Some(WindowId(4)));
+            }
+        }
+
+        #[test]
+        fn test_only_class_no_matches() {
+            assert_eq!(
+                get_only_class_result(4, "slack", FocusDirection::Left),
+                None
+            );
+        }
+
+        fn get_wrap_result(
+            window_id: usize,
+            direction: FocusDirection,
+            wrap_horizontal: bool,
+            wrap_vertical: bool,
+        ) -> Option<WindowId> {
+            let wrap = if direction.is_horizontal() {
+                wrap_horizontal
+            } else {
+                wrap_vertical
+            };
+
+            find_closest_window(
+                &WindowId(window_id),
+                &create_mock_monitor_grid(),
+                &create_mock_windows(),
+                &direction,
+                &NavigationOptions {
+                    wrap,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .map(|window| window.id)
+        }
+
+        /// Checks the moves off each edge of the grid, which only go anywhere when their axis wraps,
+        /// along with moves inside the grid, which always do.
+        fn assert_wrap_results(wrap_horizontal: bool, wrap_vertical: bool) {
+            let get_result = |window_id, direction| {
+                get_wrap_result(window_id, direction, wrap_horizontal, wrap_vertical)
+            };
+
+            let if_wrapping = |wrap: bool, window_id| wrap.then_some(WindowId(window_id));
+
+            assert_eq!(
+                get_result(1, FocusDirection::Left),
+                if_wrapping(wrap_horizontal, 6)
+            );
+            assert_eq!(
+                get_result(6, FocusDirection::Right),
+                if_wrapping(wrap_horizontal, 1)
+            );
+            assert_eq!(
+                get_result(1, FocusDirection::Up),
+                if_wrapping(wrap_vertical, 2)
+            );
+            assert_eq!(
+                get_result(2, FocusDirection::Down),
+                if_wrapping(wrap_vertical, 1)
+            );
+
+            assert_eq!(get_result(4, FocusDirection::Right), Some(WindowId(5)));
+            assert_eq!(get_result(3, FocusDirection::Left), Some(WindowId(1)));
+            assert_eq!(get_result(1, FocusDirection::Down), Some(WindowId(2)));
+            assert_eq!(get_result(2, FocusDirection::Up), Some(WindowId(1)));
+        }
+
+        #[test]
+        fn test_wrap_both_axes() {
+            assert_wrap_results(true, true);
+        }
+
+        #[test]
+        fn test_wrap_horizontal_only() {
+            assert_wrap_results(true, false);
+        }
+
+        #[test]
+        fn test_wrap_vertical_only() {
+            assert_wrap_results(false, true);
+        }
+
+        #[test]
+        fn test_wrap_neither_axis() {
+            assert_wrap_results(false, false);
+        }
+
+        fn get_current_monitor_only_result(
+            window_id: usize,
+            direction: FocusDirection,
+            wrap: bool,
+        ) -> Option<WindowId> {
+            find_closest_window(
+                &WindowId(window_id),
+                &create_mock_monitor_grid(),
+                &create_mock_windows(),
+                &direction,
+                &NavigationOptions {
+                    wrap,
+                    current_monitor_only: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .map(|window| window.id)
+        }
+
+        fn get_leave_current_monitor_result(
+            window_id: usize,
+            direction: FocusDirection,
+        ) -> WindowId {
+            find_closest_window(
+                &WindowId(window_id),
+                &create_mock_monitor_grid(),
+                &create_mock_windows(),
+                &direction,
+                &NavigationOptions {
+                    leave_current_monitor: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap()
+            .id
+        }
+
+        #[test]
+        fn test_fullscreen_skips_to_next_monitor() {
+            // Windows 3 and 4 share the middle monitor, but a fullscreen window hides the other one.
+            assert_eq!(
+                get_leave_current_monitor_result(3, FocusDirection::Right),
+                WindowId(5)
+            );
+            assert_eq!(
+                get_leave_current_monitor_result(4, FocusDirection::Left),
+                WindowId(1)
+            );
+        }
+
+        #[test]
+        fn test_fullscreen_at_edge_of_monitor() {
+            assert_eq!(
+                get_leave_current_monitor_result(4, FocusDirection::Right),
+                WindowId(5)
+            );
+            assert_eq!(
+                get_leave_current_monitor_result(2, FocusDirection::Up),
+                WindowId(1)
+            );
+        }
+
+        #[test]
+        fn test_current_monitor_only_wraps_within_monitor() {
+            // Window 4 is the rightmost window on the middle monitor; window 5 is on the monitor to its right.
+            assert_eq!(
+                get_current_monitor_only_result(4, FocusDirection::Right, true),
+                Some(WindowId(3))
+            );
+            assert_eq!(
+                get_current_monitor_only_result(3, FocusDirection::Left, true),
+                Some(WindowId(4))
+            );
+        }
+
+        #[test]
+        fn test_current_monitor_only_moves_within_monitor() {
+            assert_eq!(
+                get_current_monitor_only_result(3, FocusDirection::Right, true),
+                Some(WindowId(4))
+            );
+            assert_eq!(
+                get_current_monitor_only_result(4, FocusDirection::Left, true),
+                Some(WindowId(3))
+            );
+        }
+
+        #[test]
+        fn test_current_monitor_only_without_wrap() {
+            assert_eq!(
+                get_current_monitor_only_result(4, FocusDirection::Right, false),
+                None
+            );
+            assert_eq!(
+                get_current_monitor_only_result(3, FocusDirection::Left, false),
+                None
+            );
+        }
+
+        #[test]
+        fn test_current_monitor_only_single_window() {
+            assert_eq!(
+                get_current_monitor_only_result(1, FocusDirection::Right, true),
+                Some(WindowId(1))
+            );
+        }
+
+        #[test]
+        fn test_current_monitor_only_up_down() {
+            assert_eq!(
+                get_current_monitor_only_result(1, FocusDirection::Down, true),
+                None
+            );
+            assert_eq!(
+                get_current_monitor_only_result(4, FocusDirection::Up, true),
+                None
+            );
+        }
+
+        #[test]
+        fn test_left_same_monitor() {
+            assert_eq!(get_result(4, FocusDirection::Left), WindowId(3));
+        }
+
+        #[test]
+        fn test_untracked_focused_window() {
+            // E.g. the root window, which is what's focused after clicking on the desktop.
+            for direction in [
+                FocusDirection::Left,
+                FocusDirection::Right,
+                FocusDirection::Up,
+                FocusDirection::Down,
+            ] {
+                assert_eq!(get_result(0x1e6, direction), WindowId(1));
+            }
+        }
+
+        #[test]
+        fn test_untracked_focused_window_with_empty_first_monitor() {
+            let windows = create_mock_windows()
+                .into_iter()
+                .filter(|window| window.id != WindowId(1))
+                .collect::<Vec<Window>>();
+
+            let result = find_closest_window(
+                &WindowId(0x1e6),
+                &create_mock_monitor_grid(),
+                &windows,
+                &FocusDirection::Right,
+                &NavigationOptions::default(),
+            );
+
+            assert_eq!(result.unwrap().unwrap().id, WindowId(2));
+        }
+
+        #[test]
+        fn test_right_same_monitor() {
+            assert_eq!(get_result(3, FocusDirection::Right), WindowId(4));
+        }
+
+        #[test]
+        fn test_left_window() {
+            assert_eq!(get_result(3, FocusDirection::Left), WindowId(1));
+        }
+
+        #[test]
+        fn test_right_window_from_bottom_row() {
+            assert_eq!(get_result(2, FocusDirection::Right), WindowId(3));
+        }
+
+        #[test]
+        fn test_up_window() {
+            assert_eq!(get_result(2, FocusDirection::Up), WindowId(1));
+        }
+
+        #[test]
+        fn test_down_window() {
+            assert_eq!(get_result(1, FocusDirection::Down), WindowId(2));
+        }
+
+        #[test]
+        fn test_down_wraps_within_column() {
+            assert_eq!(get_result(2, FocusDirection::Down), WindowId(1));
+        }
+
+        #[test]
+        fn test_up_single_monitor_column() {
+            // The center monitor is alone in its column, so Up lands back on its first window.
+            assert_eq!(get_result(4, FocusDirection::Up), WindowId(3));
+        }
+
+        #[test]
+        fn test_right_window() {
+            assert_eq!(get_result(4, FocusDirection::Right), WindowId(5));
+        }
+
+        #[test]
+        fn test_wrap_left() {
+            assert_eq!(get_result(1, FocusDirection::Left), WindowId(6));
+        }
+
+        #[test]
+        fn test_wrap_right() {
+            assert_eq!(get_result(6, FocusDirection::Right), WindowId(1));
+        }
+
+        #[test]
+        fn test_above_same_monitor() {
+            assert_eq!(get_result(6, FocusDirection::Left), WindowId(5));
+        }
+
+        #[test]
+        fn test_below_same_monitor() {
+            assert_eq!(get_result(5, FocusDirection::Right), WindowId(6));
+        }
+    }
+
+    mod resolve_direction {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn get_result(window_id: usize, direction: FocusDirection, invert: bool) -> Option<usize> {
+            let config = Config {
+                invert_direction: invert,
+                ..Config::default()
+            };
+
+            // One window per monitor, with each window's ID being its monitor's index.
+            let monitor_grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+            let windows = (0..3)
+                .map(|id| {
+                    Window::new(
+                        WindowId(id),
+                        id as i32 * 1920,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect::<Vec<Window>>();
+
+            find_closest_window(
+                &WindowId(window_id),
+                &monitor_grid,
+                &windows,
+                &resolve_direction(direction, &config),
+                &NavigationOptions::default(),
+            )
+            .unwrap()
+            .map(|window| window.id.0)
+        }
+
+        #[test]
+        fn test_not_inverted() {
+            assert_eq!(get_result(1, FocusDirection::Left, false), Some(0));
+            assert_eq!(get_result(1, FocusDirection::Right, false), Some(2));
+        }
+
+        #[test]
+        fn test_inverted_swaps_neighbors() {
+            assert_eq!(get_result(1, FocusDirection::Left, true), Some(2));
+            assert_eq!(get_result(1, FocusDirection::Right, true), Some(0));
+        }
+
+        #[test]
+        fn test_inverted_keeps_vertical_directions() {
+            assert_eq!(
+                resolve_direction(
+                    FocusDirection::Up,
+                    &Config {
+                        invert_direction: true,
+                        ..Config::default()
+                    }
+                ),
+                FocusDirection::Up
+            );
+        }
+    }
+
+    mod empty_monitor_preference {
+        use super::*;
+        use crate::models::Monitor;
+
+        // [0] [2] [4]
+        // [1] [3]
+        //
+        // Only monitors 0, 3, and 4 (an L shape) have windows, with each window's ID being its monitor's index.
+        fn create_mock_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ])
+        }
+
+        fn create_mock_windows() -> Vec<Window> {
+            [(0, 0, 24), (3, 1920, 1104), (4, 3840, 24)]
+                .into_iter()
+                .map(|(id, x_offset, y_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        y_offset,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        fn get_result(
+            window_id: usize,
+            direction: FocusDirection,
+            empty_monitor_preference: Option<EmptyMonitorPreference>,
+        ) -> Option<usize> {
+            find_closest_window(
+                &WindowId(window_id),
+                &create_mock_monitor_grid(),
+                &create_mock_windows(),
+                &direction,
+                &NavigationOptions {
+                    empty_monitor_preference,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .map(|window| window.id.0)
+        }
+
+        #[test]
+        fn test_no_preference_carries_on() {
+            assert_eq!(get_result(0, FocusDirection::Right, None), Some(4));
+            assert_eq!(get_result(4, FocusDirection::Left, None), Some(0));
+            assert_eq!(get_result(3, FocusDirection::Up, None), Some(3));
+        }
+
+        #[test]
+        fn test_prefer_column() {
+            let preference = Some(EmptyMonitorPreference::Column);
+
+            // Monitor 2 is empty, so its column's populated monitor beats carrying on to monitor 4.
+            assert_eq!(get_result(0, FocusDirection::Right, preference), Some(3));
+            assert_eq!(get_result(4, FocusDirection::Left, preference), Some(3));
+
+            // Up/Down already stay within the column.
+            assert_eq!(get_result(3, FocusDirection::Up, preference), Some(3));
+        }
+
+        #[test]
+        fn test_prefer_row() {
+            let preference = Some(EmptyMonitorPreference::Row);
+
+            // Monitor 2 is empty, so the closest populated monitor in its row is focused (ties go left).
+            assert_eq!(get_result(3, FocusDirection::Up, preference), Some(0));
+            assert_eq!(get_result(3, FocusDirection::Down, preference), Some(0));
+
+            // Left/Right already stay within the row.
+            assert_eq!(get_result(0, FocusDirection::Right, preference), Some(4));
+        }
+
+        #[test]
+        fn test_populated_neighbor_is_unaffected() {
+            assert_eq!(
+                get_result(
+                    4,
+                    FocusDirection::Right,
+                    Some(EmptyMonitorPreference::Column)
+                ),
+                Some(0)
+            );
+        }
+    }
+
+    mod get_onscreen_position {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_test_workspace() -> Workspace {
+            Workspace::new(MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ]))
+        }
+
+        fn create_test_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "app".to_string(),
+                "App".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_offscreen_left() {
+            let window = create_test_window(-800, 100);
+            assert_eq!(
+                get_onscreen_position(&window, &create_test_workspace()),
+                Some((0, 0))
+            );
+        }
+
+        #[test]
+        fn test_offscreen_above() {
+            let window = create_test_window(2000, -600);
+            assert_eq!(
+                get_onscreen_position(&window, &create_test_workspace()),
+                Some((0, 0))
+            );
+        }
+
+        #[test]
+        fn test_onscreen() {
+            let window = create_test_window(2000, 100);
+            assert_eq!(
+                get_onscreen_position(&window, &create_test_workspace()),
+                None
+            );
+        }
+    }
+
+    mod focus_window_on_desktop {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Call {
+            SwitchToDesktop(usize),
+            Focus(WindowId),
+        }
+
+        fn create_test_window(desktop: Option<i32>) -> Window {
+            let mut window = Window::new(
+                WindowId(1),
+                100,
+                100,
+                800,
+                600,
+                "app".to_string(),
+                "App".to_string(),
+            );
+
+            window.desktop = desktop;
+            window
+        }
+
+        fn get_calls(window: &Window, current_desktop: Option<usize>) -> Vec<Call> {
+            let calls = std::cell::RefCell::new(Vec::new());
+
+            focus_window_on_desktop(
+                window,
+                current_desktop,
+                |desktop| {
+                    calls.borrow_mut().push(Call::SwitchToDesktop(desktop));
+                    Ok(())
+                },
+                |id| {
+                    calls.borrow_mut().push(Call::Focus(id.clone()));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            calls.into_inner()
+        }
+
+        #[test]
+        fn test_switches_then_focuses_on_other_desktop() {
+            assert_eq!(
+                get_calls(&create_test_window(Some(2)), Some(0)),
+                vec![Call::SwitchToDesktop(2), Call::Focus(WindowId(1))]
+            );
+        }
+
+        #[test]
+        fn test_only_focuses_on_current_desktop() {
+            assert_eq!(
+                get_calls(&create_test_window(Some(0)), Some(0)),
+                vec![Call::Focus(WindowId(1))]
+            );
+        }
+
+        #[test]
+        fn test_only_focuses_when_on_all_desktops() {
+            assert_eq!(
+                get_calls(&create_test_window(Some(-1)), Some(0)),
+                vec![Call::Focus(WindowId(1))]
+            );
+        }
+
+        #[test]
+        fn test_only_focuses_when_desktop_unknown() {
+            assert_eq!(
+                get_calls(&create_test_window(None), Some(0)),
+                vec![Call::Focus(WindowId(1))]
+            );
+        }
+    }
+
+    mod verify_focus {
+        use super::*;
+        use std::cell::RefCell;
+
+        /// Mocks a window manager that only acts on the focus requests after the first `ignored_requests`.
+        fn verify_with(ignored_requests: usize) -> (Result<()>, usize) {
+            let focused = RefCell::new(WindowId(1));
+            let requests = RefCell::new(vec![WindowId(2)]);
+
+            let result = verify_focus(
+                &WindowId(2),
+                |id| {
+                    requests.borrow_mut().push(id.clone());
+                    Ok(())
+                },
+                || {
+                    let requests = requests.borrow();
+
+                    if requests.len() > ignored_requests {
+                        *focused.borrow_mut() = requests[requests.len() - 1].clone();
+                    }
+
+                    Ok(focused.borrow().clone())
+                },
+            );
+
+            let request_count = requests.borrow().len();
+            (result, request_count)
+        }
+
+        #[test]
+        fn test_focused_first_time() {
+            let (result, request_count) = verify_with(0);
+
+            assert!(result.is_ok());
+            assert_eq!(request_count, 1);
+        }
+
+        #[test]
+        fn test_focused_after_retry() {
+            let (result, request_count) = verify_with(1);
+
+            assert!(result.is_ok());
+            assert_eq!(request_count, 2);
+        }
+
+        #[test]
+        fn test_never_focused() {
+            let (result, request_count) = verify_with(2);
+
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("window WindowId(1) is still focused"));
+            assert_eq!(request_count, 2);
+        }
+
+        #[test]
+        fn test_refocus_error() {
+            let result = verify_focus(
+                &WindowId(2),
+                |_| anyhow::bail!("wmctrl failed"),
+                || Ok(WindowId(1)),
+            );
+
+            assert_eq!(result.unwrap_err().to_string(), "wmctrl failed");
+        }
+    }
+
+    mod run_with_focus_restored {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Call {
+            GetFocused,
+            Focus(WindowId),
+            Run,
+        }
+
+        #[test]
+        fn test_focus_is_restored() {
+            let calls = std::cell::RefCell::new(Vec::new());
+
+            run_with_focus_restored(
+                &WindowId(2),
+                || calls.borrow_mut().push(Call::Run),
+                || {
+                    calls.borrow_mut().push(Call::GetFocused);
+                    Ok(WindowId(1))
+                },
+                |id| {
+                    calls.borrow_mut().push(Call::Focus(id.clone()));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                calls.into_inner(),
+                vec![
+                    Call::GetFocused,
+                    Call::Focus(WindowId(2)),
+                    Call::Run,
+                    Call::Focus(WindowId(1))
+                ]
+            );
+        }
+
+        #[test]
+        fn test_nothing_focused_when_focus_unknown() {
+            let mut focused = Vec::new();
+
+            let result = run_with_focus_restored(
+                &WindowId(2),
+                || panic!("Should not run"),
+                || Err(anyhow::anyhow!("xdotool failed")),
+                |id| {
+                    focused.push(id.clone());
+                    Ok(())
+                },
+            );
+
+            assert!(result.is_err());
+            assert!(focused.is_empty());
+        }
+    }
+
+    mod count_window_classes {
+        use super::*;
+
+        fn create_mock_window(id: usize, window_class: &str) -> Window {
+            Window::new(
+                WindowId(id),
+                0,
+                100,
+                800,
+                600,
+                window_class.to_string(),
+                "title".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_repeated_classes() {
+            let windows = vec![
+                create_mock_window(1, "slack.Slack"),
+                create_mock_window(2, "code.Code"),
+                create_mock_window(3, "gnome-terminal-server.Gnome-terminal"),
+                create_mock_window(4, "code.Code"),
+                create_mock_window(5, "code.Code"),
+                create_mock_window(6, "gnome-terminal-server.Gnome-terminal"),
+            ];
+
+            assert_eq!(
+                count_window_classes(&windows),
+                vec![
+                    ("code.Code".to_owned(), 3),
+                    ("gnome-terminal-server.Gnome-terminal".to_owned(), 2),
+                    ("slack.Slack".to_owned(), 1),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_no_windows() {
+            assert!(count_window_classes(&[]).is_empty());
+        }
+
+        #[test]
+        fn test_sorted_regardless_of_case() {
+            let windows = vec![
+                create_mock_window(1, "slack.Slack"),
+                create_mock_window(2, "Navigator.firefox"),
+                create_mock_window(3, "code.Code"),
+                create_mock_window(4, "navigator.Firefox"),
+            ];
+
+            assert_eq!(
+                count_window_classes(&windows),
+                vec![
+                    ("code.Code".to_owned(), 1),
+                    ("Navigator.firefox".to_owned(), 1),
+                    ("navigator.Firefox".to_owned(), 1),
+                    ("slack.Slack".to_owned(), 1),
+                ]
+            );
+        }
+    }
+
+    mod find_peek_target {
+        use super::*;
+
+        fn create_mock_window(id: usize, x_offset: i32, class: &str) -> Window {
+            Window::new(
+                WindowId(id),
+                x_offset,
+                0,
+                800,
+                500,
+                class.to_string(),
+                "title".to_string(),
+            )
+        }
+
+        fn create_mock_windows() -> Vec<Window> {
+            vec![
+                create_mock_window(1, 0, "slack"),
+                create_mock_window(2, 1000, "code"),
+                create_mock_window(3, 2000, "code"),
+            ]
+        }
+
+        fn get_target(peeked_window_id: Option<usize>) -> Option<(usize, StateAction)> {
+            find_peek_target(
+                &create_mock_windows(),
+                "code",
+                peeked_window_id.map(WindowId).as_ref(),
+            )
+            .map(|(window, action)| (window.id.0, action))
+        }
+
+        #[test]
+        fn test_first_press_raises_leftmost() {
+            assert_eq!(get_target(None), Some((2, StateAction::Add)));
+        }
+
+        #[test]
+        fn test_second_press_lowers_peeked_window() {
+            assert_eq!(get_target(Some(3)), Some((3, StateAction::Remove)));
+        }
+
+        #[test]
+        fn test_closed_peeked_window_raises_again() {
+            assert_eq!(get_target(Some(4)), Some((2, StateAction::Add)));
+        }
+
+        #[test]
+        fn test_no_windows_of_class() {
+            assert_eq!(
+                find_peek_target(&create_mock_windows(), "firefox", None)
+                    .map(|(window, _)| &window.id),
+                None
+            );
+        }
+
+        #[test]
+        fn test_state_args() {
+            assert_eq!(
+                format!(
+                    "{},{}",
+                    StateAction::Add.as_str(),
+                    WindowState::Above.as_str()
+                ),
+                "add,above"
+            );
+            assert_eq!(
+                format!(
+                    "{},{}",
+                    StateAction::Remove.as_str(),
+                    WindowState::Above.as_str()
+                ),
+                "remove,above"
+            );
+        }
+    }
+
+    mod break_tie_by_pointer {
+        use super::*;
+
+        fn create_mock_window(id: usize, x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(id),
+                x_offset,
+                y_offset,
+                800,
+                500,
+                "class".to_string(),
+                "title".to_string(),
+            )
+        }
+
+        fn create_mock_windows() -> Vec<Window> {
+            vec![
+                create_mock_window(1, 0, 100),
+                // Two windows stacked at the same x offset, so they're tied when moving right from the first one.
+                create_mock_window(2, 1000, 0),
+                create_mock_window(3, 1000, 540),
+                create_mock_window(4, 1500, 100),
+            ]
+        }
+
+        fn get_result(pointer: (i32, i32)) -> WindowId {
+            let windows = create_mock_windows();
+            let candidates: Vec<&Window> = windows[1..].iter().collect();
+
+            break_tie_by_pointer(&windows[1], Some(&windows[0]), &candidates, pointer)
+                .id
+                .clone()
+        }
+
+        #[test]
+        fn test_pointer_near_chosen_window() {
+            assert_eq!(get_result((1400, 200)), WindowId(2));
+        }
+
+        #[test]
+        fn test_pointer_near_tied_window() {
+            assert_eq!(get_result((1400, 900)), WindowId(3));
+        }
+
+        #[test]
+        fn test_ignores_windows_that_arent_tied() {
+            // The window at 1500 has the nearest center, but it's further right than the chosen one.
+            assert_eq!(get_result((1900, 350)), WindowId(2));
+        }
+
+        #[test]
+        fn test_equidistant_keeps_chosen_window() {
+            // Exactly halfway between the centers of the two stacked windows.
+            assert_eq!(get_result((1400, 520)), WindowId(2));
+        }
+
+        #[test]
+        fn test_stepping_through_stack() {
+            let windows = create_mock_windows();
+            let candidates: Vec<&Window> = vec![&windows[0], &windows[3]];
+
+            // Moving from one stacked window to the other isn't a tie, no matter where the pointer is.
+            let result =
+                break_tie_by_pointer(&windows[2], Some(&windows[1]), &candidates, (1400, 900));
+            assert_eq!(result.id, WindowId(3));
+        }
+
+        #[test]
+        fn test_no_current_window() {
+            let windows = create_mock_windows();
+            let candidates: Vec<&Window> = windows[1..].iter().collect();
+
+            let result = break_tie_by_pointer(&windows[1], None, &candidates, (1400, 900));
+            assert_eq!(result.id, WindowId(3));
+        }
+    }
+
+    mod should_refocus {
+        use super::*;
+
+        #[test]
+        fn test_pinned_window_focused() {
+            assert!(!should_refocus(Some(&WindowId(1)), &WindowId(1)));
+        }
+
+        #[test]
+        fn test_other_window_focused() {
+            assert!(should_refocus(Some(&WindowId(2)), &WindowId(1)));
+        }
+
+        #[test]
+        fn test_no_window_focused() {
+            assert!(should_refocus(None, &WindowId(1)));
+        }
+    }
+
+    mod run_pin_loop {
+        use super::*;
+        use std::cell::Cell;
+        use std::collections::VecDeque;
+
+        /// Runs the loop with the given refocus results (in order) and focused window, stopping after `max_checks`.
+        /// Returns the loop's result along with the pinned window IDs that each refocus was called with.
+        fn run(
+            results: Vec<Result<Option<WindowId>>>,
+            current_window_id: Option<WindowId>,
+            max_checks: usize,
+        ) -> (Result<()>, Vec<Option<WindowId>>) {
+            let mut results = VecDeque::from(results);
+            let mut pinned_window_ids = Vec::new();
+            let checks = Cell::new(0);
+
+            let result = run_pin_loop(
+                "firefox",
+                || checks.get() >= max_checks,
+                || current_window_id.clone(),
+                |pinned_window_id, _| {
+                    pinned_window_ids.push(pinned_window_id.cloned());
+                    results.pop_front().expect("refocused too many times")
+                },
+                || checks.set(checks.get() + 1),
+            );
+
+            (result, pinned_window_ids)
+        }
+
+        #[test]
+        fn test_stops_when_requested() {
+            let (result, pinned_window_ids) = run(
+                vec![Ok(Some(WindowId(1))), Ok(Some(WindowId(1)))],
+                Some(WindowId(2)),
+                2,
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(pinned_window_ids, vec![None, Some(WindowId(1))]);
+        }
+
+        #[test]
+        fn test_stops_when_window_closed() {
+            let (result, pinned_window_ids) = run(
+                vec![Ok(Some(WindowId(1))), Ok(None)],
+                Some(WindowId(2)),
+                usize::MAX,
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(pinned_window_ids, vec![None, Some(WindowId(1))]);
+        }
+
+        #[test]
+        fn test_no_window_to_pin() {
+            let (result, _) = run(vec![Ok(None)], None, usize::MAX);
+
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "No windows found with class firefox"
+            );
+        }
+
+        #[test]
+        fn test_keeps_going_after_error() {
+            let (result, pinned_window_ids) = run(
+                vec![
+                    Err(anyhow::anyhow!("wmctrl timed out")),
+                    Ok(Some(WindowId(1))),
+                    Ok(None),
+                ],
+                Some(WindowId(2)),
+                usize::MAX,
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(pinned_window_ids, vec![None, None, Some(WindowId(1))]);
+        }
+
+        #[test]
+        fn test_leaves_focused_window_alone() {
+            let (result, pinned_window_ids) =
+                run(vec![Ok(Some(WindowId(1)))], Some(WindowId(1)), 5);
+
+            assert!(result.is_ok());
+            assert_eq!(pinned_window_ids, vec![None]);
+        }
+    }
+
+    mod activate_or_launch_with {
+        use super::*;
+
+        fn create_mock_windows() -> Vec<Window> {
+            vec![
+                Window::new(
+                    WindowId(1),
+                    0,
+                    100,
+                    800,
+                    600,
+                    "gnome-terminal-server.Gnome-terminal".to_string(),
+                    "Terminal".to_string(),
+                ),
+                Window::new(
+                    WindowId(2),
+                    1920,
+                    100,
+                    800,
+                    600,
+                    "firefox.Firefox".to_string(),
+                    "Mozilla Firefox".to_string(),
+                ),
+                Window::new(
+                    WindowId(3),
+                    3000,
+                    100,
+                    800,
+                    600,
+                    "firefox.Firefox".to_string(),
+                    "Mozilla Firefox".to_string(),
+                ),
+            ]
+        }
+
+        /// Returns the window that was focused (if any) and whether the command was launched.
+        fn run(windows: &[Window], class: &str) -> (Option<WindowId>, bool) {
+            let mut focused = None;
+            let mut launched = false;
+
+            activate_or_launch_with(
+                windows,
+                class,
+                |window| {
+                    focused = Some(window.id.clone());
+                    Ok(())
+                },
+                || {
+                    launched = true;
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            (focused, launched)
+        }
+
+        #[test]
+        fn test_focuses_existing_window() {
+            assert_eq!(
+                run(&create_mock_windows(), "firefox"),
+                (Some(WindowId(2)), false)
+            );
+        }
+
+        #[test]
+        fn test_launches_missing_class() {
+            assert_eq!(run(&create_mock_windows(), "slack"), (None, true));
+        }
+
+        #[test]
+        fn test_launches_without_windows() {
+            assert_eq!(run(&[], "firefox"), (None, true));
+        }
+
+        #[test]
+        fn test_launch_error() {
+            let result = activate_or_launch_with(
+                &[],
+                "firefox",
+                |_| Ok(()),
+                || Err(anyhow::anyhow!("Failed to launch: firefox")),
+            );
+
+            assert_eq!(result.unwrap_err().to_string(), "Failed to launch: firefox");
+        }
+    }
+
+    mod raise_windows_of_class {
+        use super::*;
+
+        fn create_mock_window(id: usize, x_offset: i32, window_class: &str) -> Window {
+            Window {
+                id: WindowId(id),
+                x_offset,
+                y_offset: 100,
+                width: 800,
+                height: 600,
+                window_class: window_class.to_string(),
+                title: "title".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            }
+        }
+
+        fn create_mock_windows() -> Vec<Window> {
+            vec![
+                create_mock_window(1, 0, "code.Code"),
+                create_mock_window(2, 500, "gnome-terminal-server.Gnome-terminal"),
+                create_mock_window(3, 1920, "code.Code"),
+                create_mock_window(4, 3000, "code.Code"),
+            ]
+        }
+
+        fn get_raised(windows: &[Window], class: &str) -> (usize, Vec<WindowId>) {
+            let mut raised = Vec::new();
+            let count = raise_windows_of_class(windows, class, |id| {
+                raised.push(id.clone());
+                Ok(())
+            })
+            .unwrap();
+
+            (count, raised)
+        }
+
+        #[test]
+        fn test_leftmost_raised_last() {
+            let (count, raised) = get_raised(&create_mock_windows(), "code");
+
+            assert_eq!(count, 3);
+            assert_eq!(raised, vec![WindowId(3), WindowId(4), WindowId(1)]);
+        }
+
+        #[test]
+        fn test_single_match() {
+            let (count, raised) = get_raised(&create_mock_windows(), "Gnome-terminal");
+
+            assert_eq!(count, 1);
+            assert_eq!(raised, vec![WindowId(2)]);
+        }
+
+        #[test]
+        fn test_no_matches() {
+            let (count, raised) = get_raised(&create_mock_windows(), "firefox");
+
+            assert_eq!(count, 0);
+            assert!(raised.is_empty());
+        }
+    }
+
+    mod find_next_window_on_monitor {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ])
+        }
+
+        /// Three windows on the left monitor and two on the right one, sorted from left to right.
+        fn create_windows() -> Vec<Window> {
+            [(1, 0), (2, 600), (3, 1200), (4, 1920), (5, 2600)]
+                .into_iter()
+                .map(|(id, x_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        100,
+                        600,
+                        600,
+                        "app.App".to_string(),
+                        "Window".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        fn find_next_id(
+            windows: &Vec<Window>,
+            current_id: usize,
+            direction: FocusDirection,
+        ) -> Option<usize> {
+            find_next_window_on_monitor(
+                &create_monitor_grid(),
+                windows,
+                &WindowId(current_id),
+                &direction,
+            )
+            .unwrap()
+            .map(|window| window.id.0)
+        }
+
+        #[test]
+        fn test_next_and_previous_window() {
+            let windows = create_windows();
+
+            assert_eq!(find_next_id(&windows, 1, FocusDirection::Right), Some(2));
+            assert_eq!(find_next_id(&windows, 2, FocusDirection::Down), Some(3));
+            assert_eq!(find_next_id(&windows, 3, FocusDirection::Left), Some(2));
+            assert_eq!(find_next_id(&windows, 2, FocusDirection::Up), Some(1));
+        }
+
+        #[test]
+        fn test_wraps_around_at_the_right_end() {
+            let windows = create_windows();
+
+            assert_eq!(find_next_id(&windows, 3, FocusDirection::Right), Some(1));
+        }
+
+        #[test]
+        fn test_wraps_around_at_the_left_end() {
+            let windows = create_windows();
+
+            assert_eq!(find_next_id(&windows, 1, FocusDirection::Left), Some(3));
+        }
+
+        #[test]
+        fn test_stays_on_the_monitor() {
+            let windows = create_windows();
+
+            assert_eq!(find_next_id(&windows, 5, FocusDirection::Right), Some(4));
+            assert_eq!(find_next_id(&windows, 4, FocusDirection::Left), Some(5));
+        }
+
+        #[test]
+        fn test_only_window_on_monitor() {
+            let windows = create_windows()
+                .into_iter()
+                .filter(|window| window.id != WindowId(5))
+                .collect();
+
+            assert_eq!(find_next_id(&windows, 4, FocusDirection::Right), None);
+        }
+
+        #[test]
+        fn test_no_windows() {
+            assert_eq!(find_next_id(&Vec::new(), 1, FocusDirection::Right), None);
+        }
+    }
+
+    mod find_next_window_of_same_class {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            [
+                (1, 0, "Alacritty.Alacritty"),
+                (2, 800, "code.Code"),
+                (3, 1920, "Alacritty.Alacritty"),
+                (4, 2500, "slack.Slack"),
+                (5, 3840, "Alacritty.Alacritty"),
+            ]
+            .into_iter()
+            .map(|(id, x_offset, window_class)| {
+                Window::new(
+                    WindowId(id),
+                    x_offset,
+                    100,
+                    800,
+                    600,
+                    window_class.to_string(),
+                    "Window".to_string(),
+                )
+            })
+            .collect()
+        }
+
+        fn find_next_id(current_id: usize, direction: FocusDirection) -> Option<usize> {
+            let windows = create_windows();
+
+            find_next_window_of_same_class(&windows, &WindowId(current_id), &direction)
+                .map(|window| window.id.0)
+        }
+
+        #[test]
+        fn test_next_window() {
+            assert_eq!(find_next_id(1, FocusDirection::Right), Some(3));
+            assert_eq!(find_next_id(3, FocusDirection::Down), Some(5));
+        }
+
+        #[test]
+        fn test_previous_window() {
+            assert_eq!(find_next_id(5, FocusDirection::Left), Some(3));
+            assert_eq!(find_next_id(3, FocusDirection::Up), Some(1));
+        }
+
+        #[test]
+        fn test_wraps_around() {
+            assert_eq!(find_next_id(5, FocusDirection::Right), Some(1));
+            assert_eq!(find_next_id(1, FocusDirection::Left), Some(5));
+        }
+
+        #[test]
+        fn test_only_window_of_class() {
+            assert_eq!(find_next_id(2, FocusDirection::Right), None);
+        }
+
+        #[test]
+        fn test_unknown_current_window() {
+            assert_eq!(find_next_id(42, FocusDirection::Right), None);
+        }
+    }
+
+    mod build_menu_lines {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            [
+                (0x05000006, "code.Code", "main.rs - Visual Studio Code"),
+                (0x03a00003, "slack.Slack", "Slack [general]"),
+            ]
+            .into_iter()
+            .map(|(id, window_class, title)| {
+                Window::new(
+                    WindowId(id),
+                    0,
+                    24,
+                    800,
+                    600,
+                    window_class.to_string(),
+                    title.to_string(),
+                )
+            })
+            .collect()
+        }
+
+        #[test]
+        fn test_menu_lines() {
+            assert_eq!(
+                build_menu_lines(&create_windows()),
+                vec![
+                    "main.rs - Visual Studio Code (code.Code) [0x05000006]",
+                    "Slack [general] (slack.Slack) [0x03a00003]",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_round_trip() {
+            for (window, line) in create_windows()
+                .iter()
+                .zip(build_menu_lines(&create_windows()))
+            {
+                assert_eq!(parse_menu_selection(&line).unwrap(), window.id);
+            }
+        }
+
+        #[test]
+        fn test_trailing_newline() {
+            assert_eq!(
+                parse_menu_selection("Slack (slack.Slack) [0x03a00003]\n").unwrap(),
+                WindowId(0x03a00003)
+            );
+        }
+
+        #[test]
+        fn test_invalid_selection() {
+            assert!(parse_menu_selection("Slack").is_err());
+            assert!(parse_menu_selection("Slack [general]").is_err());
+            assert!(parse_menu_selection("Slack [0xnope]").is_err());
+        }
+    }
+
+    mod find_window_in_pixel_range {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            // Centers at x = 960, 2400, 3600, and 6080 on a 6800 pixel wide workspace.
+            [
+                (4, 5360, 1440),
+                (2, 1920, 960),
+                (3, 3000, 1200),
+                (1, 0, 1920),
+            ]
+            .into_iter()
+            .map(|(id, x_offset, width)| {
+                Window::new(
+                    WindowId(id),
+                    x_offset,
+                    24,
+                    width,
+                    1000,
+                    "class".to_string(),
+                    "title".to_string(),
+                )
+            })
+            .collect()
+        }
+
+        fn get_result(region: &str) -> Option<WindowId> {
+            let pixel_range = Region::try_from_string(region)
+                .unwrap()
+                .to_pixel_range(6800);
+
+            find_window_in_pixel_range(&create_windows(), pixel_range)
+                .map(|window| window.id.clone())
+        }
+
+        #[test]
+        fn test_leftmost_quarter() {
+            assert_eq!(get_result("0-25"), Some(WindowId(1)));
+        }
+
+        #[test]
+        fn test_middle() {
+            // Window 1 starts further left, but its center isn't in the middle half.
+            assert_eq!(get_result("25-75"), Some(WindowId(2)));
+        }
+
+        #[test]
+        fn test_rightmost_quarter() {
+            assert_eq!(get_result("75-100"), Some(WindowId(4)));
+        }
+
+        #[test]
+        fn test_empty_region() {
+            assert_eq!(get_result("60-75"), None);
+        }
+    }
+
+    mod build_monitor_statuses {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![
+                    Monitor {
+                        name: Some("DisplayPort-2".to_owned()),
+                        ..Monitor::new(1920, 1080)
+                    },
+                    Monitor {
+                        name: Some("HDMI-A-0".to_owned()),
+                        ..Monitor::new_primary(1920, 1080)
+                    },
+                ],
+                vec![Monitor {
+                    name: Some("DisplayPort-0".to_owned()),
+                    ..Monitor::new(3440, 1440)
+                }],
+            ])
+        }
+
+        fn create_windows() -> Vec<Window> {
+            [(1, 0, 24), (2, 1920, 24), (3, 2500, 24)]
+                .into_iter()
+                .map(|(id, x_offset, y_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        y_offset,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_rows() {
+            let statuses =
+                build_monitor_statuses(&create_monitor_grid(), &create_windows(), &WindowId(3))
+                    .unwrap();
+
+            assert_eq!(
+                statuses,
+                vec![
+                    MonitorStatus {
+                        index: 0,
+                        name: Some("DisplayPort-2".to_owned()),
+                        width: 1920,
+                        height: 1080,
+                        x: 0,
+                        y: 0,
+                        primary: false,
+                        active: false,
+                        window_count: 1,
+                    },
+                    MonitorStatus {
+                        index: 1,
+                        name: Some("HDMI-A-0".to_owned()),
+                        width: 1920,
+                        height: 1080,
+                        x: 0,
+                        y: 1080,
+                        primary: true,
+                        active: false,
+                        window_count: 0,
+                    },
+                    MonitorStatus {
+                        index: 2,
+                        name: Some("DisplayPort-0".to_owned()),
+                        width: 3440,
+                        height: 1440,
+                        x: 1920,
+                        y: 0,
+                        primary: false,
+                        active: true,
+                        window_count: 2,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_untracked_focused_window() {
+            let statuses =
+                build_monitor_statuses(&create_monitor_grid(), &create_windows(), &WindowId(0x1e6))
+                    .unwrap();
+
+            assert!(statuses.iter().all(|status| !status.active));
+        }
+    }
+
+    mod find_relative_monitor {
+        use super::*;
+        use crate::models::Monitor;
+
+        // [0] [2] [3]
+        // [1]
+        fn create_mock_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        // One window on each monitor, with the window's ID being its monitor's index.
+        fn create_mock_windows() -> Vec<Window> {
+            [(0, 0, 24), (1, 0, 1104), (2, 1920, 24), (3, 5360, 24)]
+                .into_iter()
+                .map(|(id, x_offset, y_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        y_offset,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        fn get_result(monitor: usize, direction: FocusDirection, wrap: bool) -> Option<usize> {
+            find_relative_monitor(
+                &WindowId(monitor),
+                &create_mock_monitor_grid(),
+                &create_mock_windows(),
+                &direction,
+                wrap,
+            )
+            .unwrap()
+            .map(|monitor| monitor.0)
+        }
+
+        #[test]
+        fn test_right() {
+            assert_eq!(get_result(0, FocusDirection::Right, true), Some(2));
+            assert_eq!(get_result(1, FocusDirection::Right, true), Some(2));
+            assert_eq!(get_result(2, FocusDirection::Right, true), Some(3));
+            assert_eq!(get_result(3, FocusDirection::Right, true), Some(0));
+        }
+
+        #[test]
+        fn test_left() {
+            assert_eq!(get_result(0, FocusDirection::Left, true), Some(3));
+            assert_eq!(get_result(1, FocusDirection::Left, true), Some(3));
+            assert_eq!(get_result(2, FocusDirection::Left, true), Some(0));
+            assert_eq!(get_result(3, FocusDirection::Left, true), Some(2));
+        }
+
+        #[test]
+        fn test_up_and_down() {
+            assert_eq!(get_result(0, FocusDirection::Down, true), Some(1));
+            assert_eq!(get_result(1, FocusDirection::Down, true), Some(0));
+            assert_eq!(get_result(0, FocusDirection::Up, true), Some(1));
+            assert_eq!(get_result(2, FocusDirection::Up, true), Some(2));
+            assert_eq!(get_result(3, FocusDirection::Down, true), Some(3));
+        }
+
+        #[test]
+        fn test_without_wrap() {
+            assert_eq!(get_result(3, FocusDirection::Right, false), None);
+            assert_eq!(get_result(0, FocusDirection::Left, false), None);
+            assert_eq!(get_result(0, FocusDirection::Up, false), None);
+            assert_eq!(get_result(2, FocusDirection::Right, false), Some(3));
+        }
+
+        #[test]
+        fn test_window_not_found() {
+            assert_eq!(get_result(9, FocusDirection::Right, true), None);
+        }
+    }
+
+    mod filter_windows_on_monitor {
+        use super::*;
+
+        fn create_windows() -> Vec<(Window, Option<MonitorIndex>)> {
+            [
+                (1, Some(0)),
+                (2, Some(1)),
+                (3, None),
+                (4, Some(1)),
+                (5, Some(2)),
+            ]
+            .into_iter()
+            .map(|(id, monitor)| {
+                (
+                    Window::new(
+                        WindowId(id),
+                        0,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    ),
+                    monitor.map(MonitorIndex),
+                )
+            })
+            .collect()
+        }
+
+        fn get_ids(monitor: usize) -> Result<Vec<usize>> {
+            Ok(
+                filter_windows_on_monitor(create_windows(), &MonitorIndex(monitor), 4)?
+                    .into_iter()
+                    .map(|(window, _)| window.id.0)
+                    .collect(),
+            )
+        }
+
+        #[test]
+        fn test_windows_on_monitor() {
+            assert_eq!(get_ids(0).unwrap(), vec![1]);
+            assert_eq!(get_ids(1).unwrap(), vec![2, 4]);
+            assert_eq!(get_ids(2).unwrap(), vec![5]);
+        }
+
+        #[test]
+        fn test_empty_monitor() {
+            assert_eq!(get_ids(3).unwrap(), Vec::<usize>::new());
+        }
+
+        #[test]
+        fn test_out_of_range() {
+            assert_eq!(
+                get_ids(4).unwrap_err().to_string(),
+                "Monitor index 4 out of range; 4 monitors detected"
+            );
+        }
+    }
+
+    mod find_monitor_window {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        fn create_windows() -> Vec<Window> {
+            [(1, 0), (2, 1920), (3, 3000)]
+                .into_iter()
+                .map(|(id, x_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        fn get_nth_result(
+            index: usize,
+            pick_last: bool,
+            nth: Option<usize>,
+        ) -> Result<Option<WindowId>> {
+            let monitor_grid = create_monitor_grid();
+            let windows = create_windows();
+            let windows_by_monitor_index = index_windows_by_monitor(&monitor_grid, &windows)?;
+
+            find_monitor_window(
+                &monitor_grid,
+                &windows_by_monitor_index,
+                &MonitorIndex(index),
+                pick_last,
+                nth,
+            )
+            .map(|window| window.map(|window| window.id.clone()))
+        }
+
+        fn get_result_picking(index: usize, pick_last: bool) -> Result<Option<WindowId>> {
+            get_nth_result(index, pick_last, None)
+        }
+
+        fn get_result(index: usize) -> Result<Option<WindowId>> {
+            get_result_picking(index, false)
+        }
+
+        #[test]
+        fn test_leftmost_window() {
+            assert_eq!(get_result(2).unwrap(), Some(WindowId(2)));
+        }
+
+        #[test]
+        fn test_rightmost_window() {
+            assert_eq!(get_result_picking(2, true).unwrap(), Some(WindowId(3)));
+        }
+
+        #[test]
+        fn test_single_window_is_both_first_and_last() {
+            assert_eq!(get_result_picking(0, false).unwrap(), Some(WindowId(1)));
+            assert_eq!(get_result_picking(0, true).unwrap(), Some(WindowId(1)));
+        }
+
+        #[test]
+        fn test_empty_monitor() {
+            assert_eq!(get_result(1).unwrap(), None);
+            assert_eq!(get_result(3).unwrap(), None);
+        }
+
+        #[test]
+        fn test_out_of_range() {
+            assert_eq!(
+                get_result(5).unwrap_err().to_string(),
+                "Monitor index 5 out of range; 4 monitors detected"
+            );
+            assert!(get_result(4).is_err());
+        }
+
+        #[test]
+        fn test_nth_window() {
+            assert_eq!(
+                get_nth_result(2, false, Some(0)).unwrap(),
+                Some(WindowId(2))
+            );
+            assert_eq!(
+                get_nth_result(2, false, Some(1)).unwrap(),
+                Some(WindowId(3))
+            );
+        }
+
+        #[test]
+        fn test_nth_window_from_the_right() {
+            assert_eq!(get_nth_result(2, true, Some(0)).unwrap(), Some(WindowId(3)));
+            assert_eq!(get_nth_result(2, true, Some(1)).unwrap(), Some(WindowId(2)));
+        }
+
+        #[test]
+        fn test_nth_window_out_of_range() {
+            assert_eq!(
+                get_nth_result(2, false, Some(2)).unwrap_err().to_string(),
+                "Window 2 out of range; monitor 2 has 2 windows"
+            );
+            assert!(get_nth_result(2, true, Some(2)).is_err());
+        }
+
+        #[test]
+        fn test_nth_window_on_empty_monitor() {
+            assert_eq!(
+                get_nth_result(1, false, Some(0)).unwrap_err().to_string(),
+                "Window 0 out of range; monitor 1 has 0 windows"
+            );
+        }
+    }
+
+    mod filter_recently_used {
+        use super::*;
+
+        const MINUTE: u64 = 60 * 1000;
+
+        fn get_result(
+            user_times: &[Option<u64>],
+            since_minutes: u64,
+            include_untimed: bool,
+        ) -> Vec<usize> {
+            let windows = (1..=user_times.len())
+                .map(|id| {
+                    Window::new(
+                        WindowId(id),
+                        0,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect();
+
+            filter_recently_used(
+                windows,
+                user_times,
+                Duration::from_secs(since_minutes * 60),
+                include_untimed,
+            )
+            .iter()
+            .map(|window| window.id.0)
+            .collect()
+        }
+
+        #[test]
+        fn test_keeps_windows_within_threshold() {
+            let now = 100 * MINUTE;
+
+            assert_eq!(
+                get_result(
+                    &[
+                        Some(now),
+                        Some(now - 4 * MINUTE),
+                        Some(now - 6 * MINUTE),
+                        Some(now - 5 * MINUTE)
+                    ],
+                    5,
+                    false
+                ),
+                vec![1, 2, 4]
+            );
+        }
+
+        #[test]
+        fn test_untimed_windows() {
+            let user_times = [Some(10 * MINUTE), None, Some(MINUTE)];
+
+            assert_eq!(get_result(&user_times, 5, false), vec![1]);
+            assert_eq!(get_result(&user_times, 5, true), vec![1, 2]);
+        }
+
+        #[test]
+        fn test_threshold_before_server_start() {
+            assert_eq!(get_result(&[Some(MINUTE), Some(0)], 5, false), vec![1, 2]);
+        }
+
+        #[test]
+        fn test_no_user_times() {
+            assert_eq!(get_result(&[None, None], 5, false), Vec::<usize>::new());
+            assert_eq!(get_result(&[None, None], 5, true), vec![1, 2]);
+        }
+    }
+
+    mod find_oldest_window {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            (1..=3)
+                .map(|id| {
+                    let mut window = Window::new(
+                        WindowId(id),
+                        0,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    );
+
+                    // Window 1 is at the top of the stack and window 3 at the bottom.
+                    window.stacking_index = 3 - id;
+                    window
+                })
+                .collect()
+        }
+
+        fn get_result(user_times: &[Option<u64>]) -> Option<WindowId> {
+            find_oldest_window(&create_windows(), user_times).map(|window| window.id.clone())
+        }
+
+        #[test]
+        fn test_smallest_user_time() {
+            assert_eq!(
+                get_result(&[Some(300), Some(100), Some(200)]),
+                Some(WindowId(2))
+            );
+        }
+
+        #[test]
+        fn test_missing_user_times_are_skipped() {
+            assert_eq!(get_result(&[Some(300), None, Some(200)]), Some(WindowId(3)));
+            assert_eq!(get_result(&[Some(300), None, None]), Some(WindowId(1)));
+        }
+
+        #[test]
+        fn test_falls_back_to_stacking_order() {
+            assert_eq!(get_result(&[None, None, None]), Some(WindowId(3)));
+        }
+
+        #[test]
+        fn test_tie_broken_by_stacking_order() {
+            assert_eq!(
+                get_result(&[Some(100), Some(100), Some(200)]),
+                Some(WindowId(2))
+            );
+        }
+
+        #[test]
+        fn test_no_windows() {
+            assert!(find_oldest_window(&[], &[]).is_none());
+        }
+    }
+
+    mod find_dominant_window {
+        use super::*;
+
+        const MONITOR_BOUNDS: (i32, i32, i32, i32) = (1920, 0, 1920, 1080);
+
+        fn create_window(
+            id: usize,
+            x_offset: i32,
+            width: i32,
+            height: i32,
+            stacking_index: usize,
+        ) -> Window {
+            Window {
+                stacking_index,
+                ..Window::new(
+                    WindowId(id),
+                    x_offset,
+                    0,
+                    width,
+                    height,
+                    "class".to_string(),
+                    "title".to_string(),
+                )
+            }
+        }
+
+        #[test]
+        fn test_greatest_coverage() {
+            let windows = vec![
+                create_window(1, 1920, 800, 600, 2),
+                create_window(2, 2000, 1600, 1000, 0),
+                create_window(3, 2500, 1000, 900, 1),
+            ];
+
+            assert_eq!(
+                find_dominant_window(&windows, MONITOR_BOUNDS).unwrap().id,
+                WindowId(2)
+            );
+        }
+
+        #[test]
+        fn test_larger_window_mostly_off_monitor() {
+            // Window 1 is the largest overall, but most of it is on the monitor to the left.
+            let windows = vec![
+                create_window(1, 0, 2400, 1080, 0),
+                create_window(2, 2400, 1000, 1000, 0),
+            ];
+
+            assert_eq!(
+                find_dominant_window(&windows, MONITOR_BOUNDS).unwrap().id,
+                WindowId(2)
+            );
+        }
+
+        #[test]
+        fn test_tie_prefers_smaller_window() {
+            // Both cover the whole monitor, but window 2 doesn't spill over onto the monitor to the left.
+            let windows = vec![
+                create_window(1, 0, 3840, 1080, 1),
+                create_window(2, 1920, 1920, 1080, 0),
+            ];
+
+            assert_eq!(
+                find_dominant_window(&windows, MONITOR_BOUNDS).unwrap().id,
+                WindowId(2)
+            );
+        }
+
+        #[test]
+        fn test_tie_prefers_topmost_window() {
+            let windows = vec![
+                create_window(1, 1920, 1920, 1080, 1),
+                create_window(2, 1920, 1920, 1080, 3),
+                create_window(3, 1920, 1920, 1080, 2),
+            ];
+
+            assert_eq!(
+                find_dominant_window(&windows, MONITOR_BOUNDS).unwrap().id,
+                WindowId(2)
+            );
+        }
+
+        #[test]
+        fn test_no_windows_on_monitor() {
+            let windows = vec![create_window(1, 0, 800, 600, 0)];
+
+            assert!(find_dominant_window(&windows, MONITOR_BOUNDS).is_none());
+            assert!(find_dominant_window(&[], MONITOR_BOUNDS).is_none());
+        }
+    }
+
+    mod find_window_by_id {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            [0x05000006, 0x03a00003]
+                .into_iter()
+                .map(|id| {
+                    Window::new(
+                        WindowId(id),
+                        0,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_found() {
+            let windows = create_windows();
+
+            assert_eq!(
+                find_window_by_id(&windows, &WindowId(0x03a00003))
+                    .unwrap()
+                    .id,
+                WindowId(0x03a00003)
+            );
+        }
+
+        #[test]
+        fn test_not_found() {
+            assert_eq!(
+                find_window_by_id(&create_windows(), &WindowId(0x1e6))
+                    .unwrap_err()
+                    .to_string(),
+                "Window 0x000001e6 isn't one of the windows on the current workspace"
+            );
+            assert!(find_window_by_id(&[], &WindowId(0x05000006)).is_err());
+        }
+    }
+
+    mod find_largest_window {
+        use super::*;
+
+        fn create_mock_window(id: usize, x_offset: i32, width: i32, height: i32) -> Window {
+            Window {
+                id: WindowId(id),
+                x_offset,
+                y_offset: 100,
+                width,
+                height,
+                window_class: "class".to_string(),
+                title: "title".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            }
+        }
+
+        #[test]
+        fn test_largest_window() {
+            let windows = vec![
+                create_mock_window(1, 0, 800, 600),
+                create_mock_window(2, 1920, 3440, 1416),
+                create_mock_window(3, 5360, 1440, 1200),
+            ];
+
+            assert_eq!(find_largest_window(&windows).unwrap().id, WindowId(2));
+        }
+
+        #[test]
+        fn test_tie_takes_leftmost() {
+            let windows = vec![
+                create_mock_window(1, 1920, 1000, 500),
+                create_mock_window(2, 0, 500, 1000),
+                create_mock_window(3, 3000, 1000, 500),
+            ];
+
+            assert_eq!(find_largest_window(&windows).unwrap().id, WindowId(2));
+        }
+
+        #[test]
+        fn test_no_windows() {
+            assert!(find_largest_window(&[]).is_none());
+        }
+    }
+
+    mod is_closest_window_not_on_current_monitor {
+        use super::*;
+
+        fn create_mock_windows() -> Vec<Window> {
+            let window1 = Window {
+                id: WindowId(1),
+                x_offset: 10,
+                y_offset: 20,
+                width: 30,
+                height: 40,
+                window_class: "class1".to_string(),
+                title: "title1".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            };
+
+            let window2 = Window {
+                id: WindowId(2),
+                x_offset: 50,
+                y_offset: 60,
+                width: 70,
+                height: 80,
+                window_class: "class2".to_string(),
+                title: "title2".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            };
+
+            vec![window1, window2]
+        }
+
+        #[test]
+        fn test_left_true() {
+            let windows = create_mock_windows();
+            let window_refs: Vec<&Window> = windows.iter().collect();
+
+            let result =
+                is_closest_window_not_on_current_monitor(&FocusDirection::Left, &window_refs, 0);
+
+            assert!(result);
+        }
+
+        #[test]
+        fn test_left_false() {
+            let windows = create_mock_windows();
+            let window_refs: Vec<&Window> = windows.iter().collect();
+
+            let result =
+                is_closest_window_not_on_current_monitor(&FocusDirection::Left, &window_refs, 1);
+
+            assert!(!result);
+        }
+
+        #[test]
+        fn test_right_true() {
+            let windows = create_mock_windows();
+            let window_refs: Vec<&Window> = windows.iter().collect();
+
+            let result =
+                is_closest_window_not_on_current_monitor(&FocusDirection::Right, &window_refs, 1);
+
+            assert!(result);
+        }
+
+        #[test]
+        fn test_right_false() {
+            let windows = create_mock_windows();
+            let window_refs: Vec<&Window> = windows.iter().collect();
+
+            let result =
+                is_closest_window_not_on_current_monitor(&FocusDirection::Right, &window_refs, 0);
+
+            assert!(!result);
+        }
+
+        #[test]
+        fn test_one_window() {
+            let mut windows = create_mock_windows();
+            windows.truncate(1);
+
+            let window_refs: Vec<&Window> = windows.iter().collect();
+
+            assert!(is_closest_window_not_on_current_monitor(
+                &FocusDirection::Left,
+                &window_refs,
+                1
+            ));
+
+            assert!(is_closest_window_not_on_current_monitor(
+                &FocusDirection::Right,
+                &window_refs,
+                0
+            ));
+        }
+    }
+
+    mod find_next_monitor_window {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_mock_windows() -> Vec<Window> {
+            let window1 = Window {
+                id: WindowId(1),
+                x_offset: 10,
+                y_offset: 20,
+                width: 30,
+                height: 40,
+                window_class: "class1".to_string(),
+                title: "title1".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            };
+
+            let window2 = Window {
+                id: WindowId(2),
+                x_offset: 50,
+                y_offset: 60,
+                width: 70,
+                height: 80,
+                window_class: "class2".to_string(),
+                title: "title2".to_string(),
+                frame_top: None,
+                desktop: None,
+                stacking_index: 0,
+            };
+
+            vec![window1, window2]
+        }
+
+        fn create_mock_index(
+            windows: &[Window],
+        ) -> (HashMap<MonitorIndex, Vec<&Window>>, MonitorIndex) {
+            let mut windows_by_monitor: HashMap<MonitorIndex, Vec<&Window>> = HashMap::new();
+
+            let monitor_index = MonitorIndex(0);
+            windows_by_monitor.insert(monitor_index.clone(), vec![&windows[0], &windows[1]]);
+
+            (windows_by_monitor, monitor_index)
+        }
+
+        #[test]
+        fn test_left_monitor() {
+            let windows = create_mock_windows();
+            let (windows_by_monitor, monitor_index) = create_mock_index(&windows);
+
+            let result = find_next_monitor_window(
+                &windows_by_monitor,
+                &monitor_index,
+                &FocusDirection::Left,
+            )
+            .unwrap();
+
+            assert_eq!(result.id, WindowId(2));
+        }
+
+        #[test]
+        fn test_right_monitor() {
+            let windows = create_mock_windows();
+            let (windows_by_monitor, monitor_index) = create_mock_index(&windows);
+
+            let result = find_next_monitor_window(
+                &windows_by_monitor,
+                &monitor_index,
+                &FocusDirection::Right,
+            )
+            .unwrap();
+
+            assert_eq!(result.id, WindowId(1));
+        }
+
+        #[test]
+        fn test_one_window_monitor() {
+            let windows = create_mock_windows();
+            let (mut windows_by_monitor, monitor_index) = create_mock_index(&windows);
+
+            windows_by_monitor
+                .get_mut(&monitor_index)
+                .unwrap()
+                .truncate(1);
+
+            let result1 = find_next_monitor_window(
+                &windows_by_monitor,
+                &monitor_index,
+                &FocusDirection::Left,
+            )
+            .unwrap();
+
+            let result2 = find_next_monitor_window(
+                &windows_by_monitor,
+                &monitor_index,
+                &FocusDirection::Right,
+            )
+            .unwrap();
+
+            assert_eq!(result1.id, WindowId(1));
+            assert_eq!(result2.id, WindowId(1));
+        }
+
+        #[test]
+        fn test_ties_in_x_offset_are_stable() {
+            let monitor_grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+
+            // Windows 1 and 2 share the leftmost x-offset and windows 3 and 4 the rightmost one.
+            let create_windows = || {
+                [(4, 900, 400), (2, 0, 500), (3, 900, 24), (1, 0, 500)]
+                    .into_iter()
+                    .map(|(id, x_offset, y_offset)| {
+                        Window::new(
+                            WindowId(id),
+                            x_offset,
+                            y_offset,
+                            800,
+                            500,
+                            "class".to_string(),
+                            "title".to_string(),
+                        )
+                    })
+                    .collect::<Vec<Window>>()
+            };
+
+            for reversed in [false, true] {
+                let mut windows = create_windows();
+
+                if reversed {
+                    windows.reverse();
+                }
+
+                sort_windows(&mut windows);
+                let windows_by_monitor = index_windows_by_monitor(&monitor_grid, &windows).unwrap();
+
+                let get_result = |direction| {
+                    find_next_monitor_window(&windows_by_monitor, &MonitorIndex(0), &direction)
+                        .unwrap()
+                        .id
+                        .clone()
+                };
+
+                assert_eq!(get_result(FocusDirection::Right), WindowId(1));
+                assert_eq!(get_result(FocusDirection::Left), WindowId(4));
+            }
+        }
+
+        #[test]
+        fn test_no_windows() {
+            let windows_by_monitor = HashMap::new();
+            let monitor_index = MonitorIndex(0);
+
+            let result = find_next_monitor_window(
+                &windows_by_monitor,
+                &monitor_index,
+                &FocusDirection::Right,
+            );
+
+            assert!(result.is_none());
+        }
+    }
+
+    mod get_current_workspace_windows {
+        use super::*;
+        use crate::models::{Monitor, Workspace};
+
+        fn create_test_workspace() -> Workspace {
+            let monitor_grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+            Workspace::new(monitor_grid)
+        }
+
+        fn create_test_windows() -> Vec<Window> {
+            vec![
+                Window {
+                    id: WindowId(3),
+                    x_offset: 1920, // Second monitor
+                    y_offset: 100,
+                    width: 800,
+                    height: 600,
+                    window_class: "app1".to_string(),
+                    title: "App 1".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(1),
+                    x_offset: 100, // First monitor
+                    y_offset: 100,
+                    width: 800,
+                    height: 600,
+                    window_class: "app2".to_string(),
+                    title: "App 2".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+                Window {
+                    id: WindowId(2),
+                    x_offset: 500, // First monitor
+                    y_offset: 200,
+                    width: 800,
+                    height: 600,
+                    window_class: "app3".to_string(),
+                    title: "App 3".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
+                },
+            ]
+        }
+
+        #[test]
+        fn test_offscreen_windows_only_included_when_relaxed() {
+            let workspace = create_test_workspace();
+            let mut all_windows = create_test_windows();
+            all_windows[0].x_offset = -500;
+
+            let strict_config = Config::default();
+            let relaxed_config = Config {
+                include_offscreen: true,
+                ..Config::default()
+            };
+
+            assert!(!is_window_on_workspace(
+                &all_windows[0],
+                &workspace,
+                &strict_config
+            ));
+            assert!(is_window_on_workspace(
+                &all_windows[0],
+                &workspace,
+                &relaxed_config
+            ));
+            assert!(is_window_on_workspace(
+                &all_windows[1],
+                &workspace,
+                &strict_config
+            ));
+            assert!(is_window_on_workspace(
+                &all_windows[1],
+                &workspace,
+                &relaxed_config
+            ));
+        }
+
+        #[test]
+        fn test_windows_on_other_desktops_included_with_workspace_all() {
+            let workspace = create_test_workspace();
+
+            // e.g. a window on another viewport.
+            let mut window = create_test_windows().remove(0);
+            window.x_offset = 10000;
+            window.desktop = Some(1);
+
+            let config = Config {
+                workspace_all: true,
+                ..Config::default()
+            };
+
+            assert!(!is_window_on_workspace(
+                &window,
+                &workspace,
+                &Config::default()
+            ));
+            assert!(is_window_on_workspace(&window, &workspace, &config));
+        }
+
+        #[test]
+        fn test_windows_sorted_by_x_offset() {
+            let workspace = create_test_workspace();
+            let all_windows = create_test_windows();
+
+            // Mock the wmctrl::get_windows_config call by using filter directly
+            let mut current_workspace_windows: Vec<Window> = all_windows
+                .into_iter()
+                .filter(|window| workspace.is_window_in_current_workspace(window))
+                .collect();
+
+            sort_windows(&mut current_workspace_windows);
+
+            // Windows should be sorted by x_offset: 100, 500, 1920
+            assert_eq!(current_workspace_windows[0].x_offset, 100); // WindowId(1)
+            assert_eq!(current_workspace_windows[1].x_offset, 500); // WindowId(2)
+            assert_eq!(current_workspace_windows[2].x_offset, 1920); // WindowId(3)
+        }
+    }
+
+    mod get_maximized_bounds {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_test_workspace() -> Workspace {
+            Workspace::new(MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+            ]))
+        }
+
+        fn create_test_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "app".to_string(),
+                "App".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_window_on_non_origin_monitor() {
+            assert_eq!(
+                get_maximized_bounds(&create_test_window(2500, 300), &create_test_workspace()),
+                Some((1920, 0, 3440, 1440))
+            );
+        }
+
+        #[test]
+        fn test_window_on_lower_monitor() {
+            assert_eq!(
+                get_maximized_bounds(&create_test_window(100, 1300), &create_test_workspace()),
+                Some((0, 1080, 1920, 1080))
+            );
+        }
+
+        #[test]
+        fn test_offscreen_window() {
+            assert_eq!(
+                get_maximized_bounds(&create_test_window(-5000, 300), &create_test_workspace()),
+                Some((0, 0, 1920, 1080))
+            );
+        }
+    }
+
+    mod get_other_windows_on_monitor {
+        use super::*;
+
+        fn create_test_window(id: usize) -> Window {
+            Window::new(
+                WindowId(id),
+                100,
+                100,
+                800,
+                600,
+                "app".to_string(),
+                "App".to_string(),
+            )
+        }
+
+        fn get_result(window_id: usize) -> Vec<WindowId> {
+            let windows: Vec<Window> = (1..=5).map(create_test_window).collect();
+
+            let windows_by_monitor_index = HashMap::from([
+                (MonitorIndex(0), vec![&windows[0], &windows[1], &windows[2]]),
+                (MonitorIndex(1), vec![&windows[3]]),
+                (MonitorIndex(2), vec![&windows[4]]),
+            ]);
+
+            get_other_windows_on_monitor(&WindowId(window_id), &windows_by_monitor_index)
+        }
+
+        #[test]
+        fn test_other_windows_on_same_monitor() {
+            assert_eq!(get_result(2), vec![WindowId(1), WindowId(3)]);
+        }
+
+        #[test]
+        fn test_only_window_on_monitor() {
+            assert_eq!(get_result(4), vec![]);
+        }
+
+        #[test]
+        fn test_window_not_on_any_monitor() {
+            assert_eq!(get_result(99), vec![]);
+        }
+    }
+
+    mod clamp_to_nearest_monitor {
+        use super::*;
+        use crate::models::Monitor;
+
+        fn create_mock_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
+        }
+
+        fn create_window(x_offset: i32, y_offset: i32) -> Window {
+            Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            )
+        }
+
+        fn get_monitor(window: Window) -> MonitorIndex {
+            let monitor_grid = create_mock_monitor_grid();
+
+            monitor_grid
+                .determine_which_monitor_window_is_on(&clamp_to_nearest_monitor(
+                    window,
+                    &monitor_grid,
+                ))
+                .unwrap()
+        }
+
+        #[test]
+        fn test_just_past_right_edge() {
+            let monitor_grid = create_mock_monitor_grid();
+            let window = create_window(6810, 100);
+
+            assert!(monitor_grid
+                .determine_which_monitor_window_is_on(&window)
+                .is_err());
+            assert_eq!(get_monitor(window), MonitorIndex(3));
+        }
+
+        #[test]
+        fn test_below_shorter_last_monitor() {
+            let monitor_grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+            ]);
+
+            let window = clamp_to_nearest_monitor(create_window(3000, 2000), &monitor_grid);
+
+            assert_eq!(
+                monitor_grid
+                    .determine_which_monitor_window_is_on(&window)
+                    .unwrap(),
+                MonitorIndex(2)
+            );
+        }
+
+        #[test]
+        fn test_within_monitor_unchanged() {
+            let window =
+                clamp_to_nearest_monitor(create_window(2000, 100), &create_mock_monitor_grid());
+
+            assert_eq!((window.x_offset, window.y_offset), (2000, 100));
+        }
+    }
+
+    mod sort_windows {
+        use super::*;
+
+        fn create_overlapping_windows() -> Vec<Window> {
+            [(3, 500, 2), (1, 100, 5), (2, 100, 0), (4, 100, 3)]
+                .into_iter()
+                .map(|(id, x_offset, stacking_index)| {
+                    let mut window = Window::new(
+                        WindowId(id),
+                        x_offset,
+                        100,
+                        800,
+                        600,
+                        "app".to_string(),
+                        "App".to_string(),
+                    );
+
+                    window.stacking_index = stacking_index;
+                    window
+                })
+                .collect()
+        }
+
+        fn get_ids(windows: &[Window]) -> Vec<usize> {
+            windows.iter().map(|window| window.id.0).collect()
+        }
+
+        #[test]
+        fn test_overlapping_windows_in_stacking_order() {
+            let mut windows = create_overlapping_windows();
+            sort_windows(&mut windows);
+
+            assert_eq!(get_ids(&windows), vec![2, 4, 1, 3]);
+        }
+
+        #[test]
+        fn test_order_independent_of_input_order() {
+            let mut windows = create_overlapping_windows();
+            windows.reverse();
+            sort_windows(&mut windows);
+
+            assert_eq!(get_ids(&windows), vec![2, 4, 1, 3]);
+        }
+
+        #[test]
+        fn test_same_x_offset_sorted_top_to_bottom() {
+            let mut windows = create_overlapping_windows();
+            windows[0].x_offset = 100;
+            windows[0].y_offset = 50;
+            windows[1].y_offset = 700;
+
+            sort_windows(&mut windows);
+
+            // Window 3 is now the topmost, and window 1 the bottommost, of the windows at x = 100.
+            assert_eq!(get_ids(&windows), vec![3, 2, 4, 1]);
+        }
+
+        #[test]
+        fn test_same_position_and_stacking_index_sorted_by_id() {
+            let mut windows = create_overlapping_windows();
+
+            for window in &mut windows {
+                window.x_offset = 100;
+                window.stacking_index = 0;
+            }
+
+            sort_windows(&mut windows);
+            assert_eq!(get_ids(&windows), vec![1, 2, 3, 4]);
+
+            windows.reverse();
+            sort_windows(&mut windows);
+            assert_eq!(get_ids(&windows), vec![1, 2, 3, 4]);
+        }
     }
 
-    let windows_by_monitor = index_windows_by_monitor(monitor_grid, windows)?;
-    let monitors_by_window = index_monitors_by_window(monitor_grid, windows)?;
+    mod sort_windows_by {
+        use super::*;
 
-    let current_monitor = get_current_monitor(current_window_id, &monitors_by_window);
-    let current_monitor_windows = &windows_by_monitor[&current_monitor];
+        fn create_windows() -> Vec<Window> {
+            [
+                (1, 1920, 1, "code.Code", "main.rs"),
+                (2, 0, 3, "slack.Slack", "General"),
+                (3, 3840, 0, "Alacritty.Alacritty", "zsh"),
+                (4, 0, 2, "code.Code", "README.md"),
+            ]
+            .into_iter()
+            .map(|(id, x_offset, stacking_index, window_class, title)| {
+                let mut window = Window::new(
+                    WindowId(id),
+                    x_offset,
+                    100,
+                    800,
+                    600,
+                    window_class.to_string(),
+                    title.to_string(),
+                );
 
-    if let Some(current_window_position) = current_monitor_windows
-        .iter()
-        .position(|w| w.id == *current_window_id)
-    {
-        if is_closest_window_not_on_current_monitor(
-            direction,
-            current_monitor_windows,
-            current_window_position,
-        ) {
-            let mut next_monitor = monitor_grid.get_next_monitor(&current_monitor, direction);
+                window.stacking_index = stacking_index;
+                window
+            })
+            .collect()
+        }
 
-            let mut optional_window =
-                find_next_monitor_window(&windows_by_monitor, &next_monitor, direction);
+        fn get_sorted_ids(sort_order: SortOrder) -> Vec<usize> {
+            let mut windows = create_windows();
+            sort_windows_by(&mut windows, &sort_order);
 
-            loop {
-                match optional_window {
-                    Some(window) => {
-                        return Ok(Some(window.clone()));
-                    }
-                    None => {
-                        next_monitor = monitor_grid.get_next_monitor(&next_monitor, direction);
+            windows.iter().map(|window| window.id.0).collect()
+        }
 
-                        optional_window =
-                            find_next_monitor_window(&windows_by_monitor, &next_monitor, direction);
-                    }
-                }
-            }
-        } else {
-            let position = (current_window_position as i32 + direction.to_int()) as usize;
-            Ok(Some(current_monitor_windows[position].clone()))
+        #[test]
+        fn test_position() {
+            assert_eq!(get_sorted_ids(SortOrder::Position), vec![4, 2, 1, 3]);
         }
-    } else {
-        Err(anyhow::anyhow!(
-            "Invariant violated: current focused window not found on current monitor"
-        ))
-    }
-}
 
-/// Given the windows of the current monitor, and the direction we want to focus to,
-/// determines if we need to look at another monitor to find the correct window to focus to.
-///
-/// That is, if we're already at the leftmost/rightmost window, we need to look at the next
-/// monitor to find the window to focus on.
-fn is_closest_window_not_on_current_monitor(
-    direction: &FocusDirection,
-    current_monitor_windows: &[&Window],
-    current_window_position: usize,
-) -> bool {
-    if current_monitor_windows.len() == 1 {
-        true
-    } else {
-        match direction {
-            FocusDirection::Left => current_window_position == 0,
-            FocusDirection::Right => current_window_position == current_monitor_windows.len() - 1,
+        #[test]
+        fn test_class() {
+            // Windows of the same class are ordered from left to right.
+            assert_eq!(get_sorted_ids(SortOrder::Class), vec![3, 4, 1, 2]);
         }
-    }
-}
 
-/// Used to "find the next monitor's window", using the focus direction as a signal for which side
-/// of a monitor's windows to focus to.
-///
-/// That is, if switching to the left monitor, take the farthest right (i.e. last) window on the monitor.
-/// If switching to the right monitor, take the farthest left (i.e. first) window on the monitor.
-fn find_next_monitor_window<'a>(
-    windows_by_monitor: &'a HashMap<MonitorIndex, Vec<&'a Window>>,
-    monitor: &MonitorIndex,
-    direction: &FocusDirection,
-) -> Option<&'a Window> {
-    if let Some(windows) = windows_by_monitor.get(monitor) {
-        match direction {
-            FocusDirection::Left => windows.last().map(|v| &**v),
-            FocusDirection::Right => windows.first().map(|v| &**v),
+        #[test]
+        fn test_title() {
+            assert_eq!(get_sorted_ids(SortOrder::Title), vec![2, 1, 4, 3]);
         }
-    } else {
-        None
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
+        #[test]
+        fn test_mru_without_user_times() {
+            assert_eq!(get_sorted_ids(SortOrder::Mru), vec![4, 2, 1, 3]);
+        }
+    }
 
-    mod find_closest_window {
+    mod sort_windows_by_user_time {
         use super::*;
-        use crate::models::Monitor;
 
-        fn create_mock_windows() -> Vec<Window> {
-            // Note: Windows are deliberately not in order by ID so that they can be tested for proper ordering.
-            // The proper order is the order of the IDs.
-            vec![
-                Window {
-                    id: WindowId(5),
-                    x_offset: 5360,
-                    y_offset: 0,
-                    width: 30,
-                    height: 40,
-                    window_class: "class1".to_string(),
-                    title: "title1".to_string(),
-                },
-                Window {
-                    id: WindowId(3),
-                    x_offset: 1920,
-                    y_offset: 0,
-                    width: 30,
-                    height: 40,
-                    window_class: "class1".to_string(),
-                    title: "title1".to_string(),
-                },
-                Window {
-                    id: WindowId(1),
-                    x_offset: 0,
-                    y_offset: 0,
-                    width: 10,
-                    height: 10,
-                    window_class: "class1".to_string(),
-                    title: "title1".to_string(),
-                },
-                Window {
-                    id: WindowId(2),
-                    x_offset: 0,
-                    y_offset: 1080,
-                    width: 70,
-                    height: 80,
-                    window_class: "class2".to_string(),
-                    title: "title2".to_string(),
-                },
-                Window {
-                    id: WindowId(4),
-                    x_offset: 3000,
-                    y_offset: 0,
-                    width: 70,
-                    height: 80,
-                    window_class: "class2".to_string(),
-                    title: "title2".to_string(),
-                },
-                Window {
-                    id: WindowId(6),
-                    x_offset: 5360,
-                    y_offset: 1200,
-                    width: 70,
-                    height: 80,
-                    window_class: "class2".to_string(),
-                    title: "title2".to_string(),
-                },
-            ]
-        }
+        fn get_sorted_ids(user_times: &[Option<u64>]) -> Vec<usize> {
+            let mut windows: Vec<Window> = [(1, 1920), (2, 0), (3, 3840), (4, 0)]
+                .into_iter()
+                .map(|(id, x_offset)| {
+                    Window::new(
+                        WindowId(id),
+                        x_offset,
+                        100,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect();
 
-        fn create_mock_monitor_grid() -> MonitorGrid {
-            MonitorGrid(vec![
-                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
-                vec![Monitor::new(3440, 1440)],
-                vec![Monitor::new(1440, 2560)],
-            ])
+            sort_windows_by_user_time(&mut windows, user_times);
+            windows.iter().map(|window| window.id.0).collect()
         }
 
-        fn get_result(window_id: usize, direction: FocusDirection) -> WindowId {
-            let windows = create_mock_windows();
-            let monitor_grid = create_mock_monitor_grid();
-            let current_window_id = WindowId(window_id);
-
-            find_closest_window(&current_window_id, &monitor_grid, &windows, &direction)
-                .unwrap()
-                .unwrap()
-                .id
+        #[test]
+        fn test_most_recent_first() {
+            assert_eq!(
+                get_sorted_ids(&[Some(300), Some(100), Some(400), Some(200)]),
+                vec![3, 1, 4, 2]
+            );
         }
 
         #[test]
-        fn test_left_same_monitor() {
-            assert_eq!(get_result(4, FocusDirection::Left), WindowId(3));
+        fn test_untimed_windows_last() {
+            assert_eq!(
+                get_sorted_ids(&[None, Some(100), None, Some(200)]),
+                vec![4, 2, 1, 3]
+            );
         }
 
         #[test]
-        fn test_right_same_monitor() {
-            assert_eq!(get_result(3, FocusDirection::Right), WindowId(4));
+        fn test_no_user_times() {
+            assert_eq!(get_sorted_ids(&[None, None, None, None]), vec![2, 4, 1, 3]);
+        }
+    }
+
+    mod apply_stacking_order {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            [(1, 0), (2, 1), (3, 2), (4, 3)]
+                .into_iter()
+                .map(|(id, stacking_index)| Window {
+                    stacking_index,
+                    ..Window::new(
+                        WindowId(id),
+                        0,
+                        24,
+                        800,
+                        600,
+                        "class".to_string(),
+                        "title".to_string(),
+                    )
+                })
+                .collect()
         }
 
         #[test]
-        fn test_left_window() {
-            assert_eq!(get_result(3, FocusDirection::Left), WindowId(2));
+        fn test_overrides_wmctrl_order() {
+            let mut windows = create_windows();
+
+            apply_stacking_order(
+                &mut windows,
+                &[WindowId(3), WindowId(1), WindowId(4), WindowId(2)],
+            );
+            sort_windows_by(&mut windows, &SortOrder::Stacking);
+
+            assert_eq!(
+                windows
+                    .iter()
+                    .map(|window| window.id.0)
+                    .collect::<Vec<usize>>(),
+                vec![2, 4, 1, 3]
+            );
         }
 
         #[test]
-        fn test_right_window() {
-            assert_eq!(get_result(4, FocusDirection::Right), WindowId(5));
+        fn test_missing_windows_go_last() {
+            let mut windows = create_windows();
+
+            apply_stacking_order(&mut windows, &[WindowId(2), WindowId(9)]);
+            sort_windows_by(&mut windows, &SortOrder::Stacking);
+
+            assert_eq!(windows[0].id, WindowId(2));
+            assert!(windows[1..].iter().all(|window| window.stacking_index == 0));
+        }
+    }
+
+    mod find_next_window_in_cycle {
+        use super::*;
+
+        fn create_windows() -> Vec<Window> {
+            (1..=3)
+                .map(|id| {
+                    Window::new(
+                        WindowId(id),
+                        id as i32 * 100,
+                        100,
+                        800,
+                        600,
+                        "app".to_string(),
+                        "App".to_string(),
+                    )
+                })
+                .collect()
         }
 
         #[test]
-        fn test_wrap_left() {
-            assert_eq!(get_result(1, FocusDirection::Left), WindowId(6));
+        fn test_next_window() {
+            let windows = create_windows();
+            let result = find_next_window_in_cycle(&windows, &WindowId(1));
+
+            assert_eq!(result.unwrap().id, WindowId(2));
         }
 
         #[test]
-        fn test_wrap_right() {
-            assert_eq!(get_result(6, FocusDirection::Right), WindowId(1));
+        fn test_wraps_around() {
+            let windows = create_windows();
+            let result = find_next_window_in_cycle(&windows, &WindowId(3));
+
+            assert_eq!(result.unwrap().id, WindowId(1));
         }
 
         #[test]
-        fn test_above_same_monitor() {
-            assert_eq!(get_result(6, FocusDirection::Left), WindowId(5));
+        fn test_unknown_current_window() {
+            let windows = create_windows();
+            let result = find_next_window_in_cycle(&windows, &WindowId(42));
+
+            assert_eq!(result.unwrap().id, WindowId(1));
         }
 
         #[test]
-        fn test_below_same_monitor() {
-            assert_eq!(get_result(5, FocusDirection::Right), WindowId(6));
+        fn test_no_windows() {
+            assert!(find_next_window_in_cycle(&[], &WindowId(1)).is_none());
         }
     }
 
-    mod is_closest_window_not_on_current_monitor {
+    mod calculate_moved_position {
         use super::*;
+        use crate::models::Monitor;
 
-        fn create_mock_windows() -> Vec<Window> {
-            let window1 = Window {
-                id: WindowId(1),
-                x_offset: 10,
-                y_offset: 20,
-                width: 30,
-                height: 40,
-                window_class: "class1".to_string(),
-                title: "title1".to_string(),
-            };
+        fn create_mock_monitor_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+            ])
+        }
 
-            let window2 = Window {
-                id: WindowId(2),
-                x_offset: 50,
-                y_offset: 60,
-                width: 70,
-                height: 80,
-                window_class: "class2".to_string(),
-                title: "title2".to_string(),
-            };
+        fn get_result(direction: FocusDirection) -> Option<(i32, i32)> {
+            let monitor_grid = create_mock_monitor_grid();
+            let window = Window::new(
+                WindowId(1),
+                2020,
+                124,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            );
 
-            vec![window1, window2]
+            // The window is on the top monitor of the middle column.
+            let current_monitor = MonitorIndex(1);
+            let next_monitor = monitor_grid
+                .get_next_monitor(&current_monitor, &direction)
+                .unwrap();
+
+            calculate_moved_position(
+                &monitor_grid,
+                &window,
+                &current_monitor,
+                &next_monitor,
+                false,
+            )
+        }
+
+        fn get_relative_result(
+            x_offset: i32,
+            y_offset: i32,
+            width: i32,
+            height: i32,
+        ) -> (i32, i32) {
+            let window = Window::new(
+                WindowId(1),
+                x_offset,
+                y_offset,
+                width,
+                height,
+                "class".to_string(),
+                "title".to_string(),
+            );
+
+            // From the top monitor of the middle column onto the ultrawide to its right.
+            calculate_moved_position(
+                &create_mock_monitor_grid(),
+                &window,
+                &MonitorIndex(1),
+                &MonitorIndex(3),
+                true,
+            )
+            .unwrap()
         }
 
         #[test]
-        fn test_left_true() {
-            let windows = create_mock_windows();
-            let window_refs: Vec<&Window> = windows.iter().collect();
+        fn test_relative_keeps_fraction() {
+            // 50%/50% of the 1920x1080 monitor is 50%/50% of the 3440x1440 monitor.
+            assert_eq!(
+                get_relative_result(1920 + 960, 540, 400, 300),
+                (3840 + 1720, 720)
+            );
+            assert_eq!(get_relative_result(1920, 0, 400, 300), (3840, 0));
+        }
 
-            let result =
-                is_closest_window_not_on_current_monitor(&FocusDirection::Left, &window_refs, 0);
+        #[test]
+        fn test_relative_clamps_onto_monitor() {
+            // 75% of the way across would leave most of the window hanging off the right edge.
+            assert_eq!(
+                get_relative_result(1920 + 1440, 810, 1200, 600),
+                (3840 + 3440 - 1200, 1440 - 600)
+            );
+        }
 
-            assert!(result);
+        #[test]
+        fn test_relative_window_larger_than_monitor() {
+            assert_eq!(get_relative_result(1920 + 960, 540, 4000, 2000), (3840, 0));
         }
 
         #[test]
-        fn test_left_false() {
-            let windows = create_mock_windows();
-            let window_refs: Vec<&Window> = windows.iter().collect();
+        fn test_relative_same_size_monitors() {
+            let window = Window::new(
+                WindowId(1),
+                2020,
+                124,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            );
 
-            let result =
-                is_closest_window_not_on_current_monitor(&FocusDirection::Left, &window_refs, 1);
+            assert_eq!(
+                calculate_moved_position(
+                    &create_mock_monitor_grid(),
+                    &window,
+                    &MonitorIndex(1),
+                    &MonitorIndex(0),
+                    true
+                ),
+                Some((100, 124))
+            );
+        }
 
-            assert!(!result);
+        #[test]
+        fn test_left_keeps_position_within_monitor() {
+            assert_eq!(get_result(FocusDirection::Left), Some((100, 124)));
         }
 
         #[test]
-        fn test_right_true() {
-            let windows = create_mock_windows();
-            let window_refs: Vec<&Window> = windows.iter().collect();
+        fn test_right_to_differently_sized_monitor() {
+            assert_eq!(get_result(FocusDirection::Right), Some((3840, 0)));
+        }
 
-            let result =
-                is_closest_window_not_on_current_monitor(&FocusDirection::Right, &window_refs, 1);
+        #[test]
+        fn test_down_keeps_position_within_monitor() {
+            assert_eq!(get_result(FocusDirection::Down), Some((2020, 1204)));
+        }
 
-            assert!(result);
+        #[test]
+        fn test_up_wraps_within_column() {
+            assert_eq!(get_result(FocusDirection::Up), Some((2020, 1204)));
         }
 
         #[test]
-        fn test_right_false() {
-            let windows = create_mock_windows();
-            let window_refs: Vec<&Window> = windows.iter().collect();
+        fn test_unknown_monitor() {
+            let window = Window::new(
+                WindowId(1),
+                0,
+                0,
+                800,
+                600,
+                "class".to_string(),
+                "title".to_string(),
+            );
 
-            let result =
-                is_closest_window_not_on_current_monitor(&FocusDirection::Right, &window_refs, 0);
+            assert_eq!(
+                calculate_moved_position(
+                    &create_mock_monitor_grid(),
+                    &window,
+                    &MonitorIndex(0),
+                    &MonitorIndex(7),
+                    false
+                ),
+                None
+            );
+        }
+    }
 
-            assert!(!result);
+    mod calculate_centered_position {
+        use super::*;
+
+        // A monitor to the right of a 1920px wide one.
+        const BOUNDS: (i32, i32, i32, i32) = (1920, 0, 2560, 1440);
+
+        #[test]
+        fn test_smaller_window() {
+            assert_eq!(calculate_centered_position(BOUNDS, 1280, 720), (2560, 360));
         }
 
         #[test]
-        fn test_one_window() {
-            let mut windows = create_mock_windows();
-            windows.truncate(1);
+        fn test_odd_margins() {
+            assert_eq!(calculate_centered_position(BOUNDS, 801, 601), (2799, 419));
+        }
 
-            let window_refs: Vec<&Window> = windows.iter().collect();
+        #[test]
+        fn test_full_size_window() {
+            assert_eq!(calculate_centered_position(BOUNDS, 2560, 1440), (1920, 0));
+        }
 
-            assert!(is_closest_window_not_on_current_monitor(
-                &FocusDirection::Left,
-                &window_refs,
-                1
-            ));
+        #[test]
+        fn test_oversized_window() {
+            assert_eq!(calculate_centered_position(BOUNDS, 3000, 1600), (1920, 0));
+            assert_eq!(calculate_centered_position(BOUNDS, 3000, 720), (1920, 360));
+            assert_eq!(calculate_centered_position(BOUNDS, 1280, 1600), (2560, 0));
+        }
 
-            assert!(is_closest_window_not_on_current_monitor(
-                &FocusDirection::Right,
-                &window_refs,
-                0
-            ));
+        #[test]
+        fn test_stacked_monitor() {
+            assert_eq!(
+                calculate_centered_position((0, 1080, 1920, 1080), 960, 540),
+                (480, 1350)
+            );
         }
     }
 
-    mod find_next_monitor_window {
+    mod calculate_tile_geometries {
         use super::*;
 
-        fn create_mock_windows() -> Vec<Window> {
-            let window1 = Window {
-                id: WindowId(1),
-                x_offset: 10,
-                y_offset: 20,
-                width: 30,
-                height: 40,
-                window_class: "class1".to_string(),
-                title: "title1".to_string(),
-            };
+        // A monitor to the right of a 1920px wide one.
+        const BOUNDS: (i32, i32, i32, i32) = (1920, 0, 2560, 1440);
 
-            let window2 = Window {
-                id: WindowId(2),
-                x_offset: 50,
-                y_offset: 60,
-                width: 70,
-                height: 80,
-                window_class: "class2".to_string(),
-                title: "title2".to_string(),
-            };
+        #[test]
+        fn test_no_windows() {
+            assert_eq!(calculate_tile_geometries(BOUNDS, 0), vec![]);
+        }
 
-            vec![window1, window2]
+        #[test]
+        fn test_one_window() {
+            assert_eq!(calculate_tile_geometries(BOUNDS, 1), vec![BOUNDS]);
         }
 
-        fn create_mock_index(
-            windows: &[Window],
-        ) -> (HashMap<MonitorIndex, Vec<&Window>>, MonitorIndex) {
-            let mut windows_by_monitor: HashMap<MonitorIndex, Vec<&Window>> = HashMap::new();
+        #[test]
+        fn test_two_windows() {
+            assert_eq!(
+                calculate_tile_geometries(BOUNDS, 2),
+                vec![(1920, 0, 1280, 1440), (3200, 0, 1280, 1440)]
+            );
+        }
 
-            let monitor_index = MonitorIndex(0);
-            windows_by_monitor.insert(monitor_index.clone(), vec![&windows[0], &windows[1]]);
+        #[test]
+        fn test_three_windows() {
+            assert_eq!(
+                calculate_tile_geometries(BOUNDS, 3),
+                vec![
+                    (1920, 0, 1280, 720),
+                    (3200, 0, 1280, 720),
+                    (1920, 720, 2560, 720),
+                ]
+            );
+        }
 
-            (windows_by_monitor, monitor_index)
+        #[test]
+        fn test_many_windows() {
+            assert_eq!(
+                calculate_tile_geometries(BOUNDS, 6),
+                vec![
+                    (1920, 0, 1280, 480),
+                    (3200, 0, 1280, 480),
+                    (1920, 480, 1280, 480),
+                    (3200, 480, 1280, 480),
+                    (1920, 960, 1280, 480),
+                    (3200, 960, 1280, 480),
+                ]
+            );
         }
 
         #[test]
-        fn test_left_monitor() {
-            let windows = create_mock_windows();
-            let (windows_by_monitor, monitor_index) = create_mock_index(&windows);
+        fn test_uneven_dimensions() {
+            // The leftover pixels go to the right column and the bottom row.
+            assert_eq!(
+                calculate_tile_geometries((0, 1080, 1921, 1081), 4),
+                vec![
+                    (0, 1080, 960, 540),
+                    (960, 1080, 961, 540),
+                    (0, 1620, 960, 541),
+                    (960, 1620, 961, 541),
+                ]
+            );
+        }
+    }
 
-            let result = find_next_monitor_window(
-                &windows_by_monitor,
-                &monitor_index,
-                &FocusDirection::Left,
-            )
-            .unwrap();
+    mod find_window_monitor_bounds {
+        use super::*;
+        use crate::models::Monitor;
 
-            assert_eq!(result.id, WindowId(2));
+        // The quad monitor layout from the README:
+        //
+        // [0] [2] [3]
+        // [1]
+        fn create_quad_grid() -> MonitorGrid {
+            MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(3440, 1440)],
+                vec![Monitor::new(1440, 2560)],
+            ])
         }
 
-        #[test]
-        fn test_right_monitor() {
-            let windows = create_mock_windows();
-            let (windows_by_monitor, monitor_index) = create_mock_index(&windows);
+        fn create_windows() -> Vec<Window> {
+            [
+                (1, 100, 100),
+                (2, 100, 1200),
+                (3, 2000, 100),
+                (4, 5500, 100),
+            ]
+            .into_iter()
+            .map(|(id, x_offset, y_offset)| {
+                Window::new(
+                    WindowId(id),
+                    x_offset,
+                    y_offset,
+                    800,
+                    600,
+                    "app".to_string(),
+                    "App".to_string(),
+                )
+            })
+            .collect()
+        }
 
-            let result = find_next_monitor_window(
-                &windows_by_monitor,
-                &monitor_index,
-                &FocusDirection::Right,
-            )
-            .unwrap();
+        #[test]
+        fn test_window_on_bottom_left_monitor() {
+            let result =
+                find_window_monitor_bounds(&create_quad_grid(), &create_windows(), &WindowId(2));
 
-            assert_eq!(result.id, WindowId(1));
+            assert_eq!(result.unwrap(), (0, 1080, 1920, 1080));
         }
 
         #[test]
-        fn test_one_window_monitor() {
-            let windows = create_mock_windows();
-            let (mut windows_by_monitor, monitor_index) = create_mock_index(&windows);
-
-            windows_by_monitor
-                .get_mut(&monitor_index)
-                .unwrap()
-                .truncate(1);
+        fn test_window_on_center_monitor() {
+            let result =
+                find_window_monitor_bounds(&create_quad_grid(), &create_windows(), &WindowId(3));
 
-            let result1 = find_next_monitor_window(
-                &windows_by_monitor,
-                &monitor_index,
-                &FocusDirection::Left,
-            )
-            .unwrap();
+            assert_eq!(result.unwrap(), (1920, 0, 3440, 1440));
+        }
 
-            let result2 = find_next_monitor_window(
-                &windows_by_monitor,
-                &monitor_index,
-                &FocusDirection::Right,
-            )
-            .unwrap();
+        #[test]
+        fn test_window_on_right_monitor() {
+            let result =
+                find_window_monitor_bounds(&create_quad_grid(), &create_windows(), &WindowId(4));
 
-            assert_eq!(result1.id, WindowId(1));
-            assert_eq!(result2.id, WindowId(1));
+            assert_eq!(result.unwrap(), (5360, 0, 1440, 2560));
         }
 
         #[test]
-        fn test_no_windows() {
-            let windows_by_monitor = HashMap::new();
-            let monitor_index = MonitorIndex(0);
-
-            let result = find_next_monitor_window(
-                &windows_by_monitor,
-                &monitor_index,
-                &FocusDirection::Right,
-            );
+        fn test_unknown_window() {
+            let result =
+                find_window_monitor_bounds(&create_quad_grid(), &create_windows(), &WindowId(42));
 
-            assert!(result.is_none());
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("Window 42 is not on the current workspace"));
         }
     }
 
-    mod get_current_workspace_windows {
+    mod count_windows_by_monitor {
         use super::*;
-        use crate::models::{Monitor, Workspace};
+        use crate::models::Monitor;
 
-        fn create_test_workspace() -> Workspace {
+        fn create_test_window(id: usize, x_offset: i32) -> Window {
+            Window::new(
+                WindowId(id),
+                x_offset,
+                100,
+                800,
+                600,
+                "app".to_string(),
+                "App".to_string(),
+            )
+        }
+
+        #[test]
+        fn test_empty_monitor_has_count_of_zero() {
             let monitor_grid = MonitorGrid(vec![
                 vec![Monitor::new(1920, 1080)],
                 vec![Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
             ]);
-            Workspace::new(monitor_grid)
-        }
 
-        fn create_test_windows() -> Vec<Window> {
-            vec![
-                Window {
-                    id: WindowId(3),
-                    x_offset: 1920, // Second monitor
-                    y_offset: 100,
-                    width: 800,
-                    height: 600,
-                    window_class: "app1".to_string(),
-                    title: "App 1".to_string(),
-                },
-                Window {
-                    id: WindowId(1),
-                    x_offset: 100, // First monitor
-                    y_offset: 100,
-                    width: 800,
-                    height: 600,
-                    window_class: "app2".to_string(),
-                    title: "App 2".to_string(),
-                },
-                Window {
-                    id: WindowId(2),
-                    x_offset: 500, // First monitor
-                    y_offset: 200,
-                    width: 800,
-                    height: 600,
-                    window_class: "app3".to_string(),
-                    title: "App 3".to_string(),
-                },
-            ]
+            let windows = vec![
+                create_test_window(1, 100),
+                create_test_window(2, 500),
+                create_test_window(3, 1000),
+                create_test_window(4, 4000),
+            ];
+
+            assert_eq!(
+                count_windows_by_monitor(&monitor_grid, &windows).unwrap(),
+                vec![3, 0, 1]
+            );
         }
 
         #[test]
-        fn test_windows_sorted_by_x_offset() {
-            let workspace = create_test_workspace();
-            let all_windows = create_test_windows();
-
-            // Mock the wmctrl::get_windows_config call by using filter directly
-            let mut current_workspace_windows: Vec<Window> = all_windows
-                .into_iter()
-                .filter(|window| workspace.is_window_in_current_workspace(window))
-                .collect();
-
-            // Sort by x_offset like the actual function does
-            current_workspace_windows.sort_by(|a, b| a.x_offset.cmp(&b.x_offset));
-
-            // Windows should be sorted by x_offset: 100, 500, 1920
-            assert_eq!(current_workspace_windows[0].x_offset, 100); // WindowId(1)
-            assert_eq!(current_workspace_windows[1].x_offset, 500); // WindowId(2)
-            assert_eq!(current_workspace_windows[2].x_offset, 1920); // WindowId(3)
+        fn test_no_windows() {
+            let monitor_grid = MonitorGrid(vec![vec![Monitor::new(1920, 1080)]]);
+            assert_eq!(
+                count_windows_by_monitor(&monitor_grid, &vec![]).unwrap(),
+                vec![0]
+            );
         }
     }
 
@@ -608,6 +5791,9 @@ mod tests {
                     height: 600,
                     window_class: "app1".to_string(),
                     title: "App 1".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
                 },
                 Window {
                     id: WindowId(2),
@@ -617,6 +5803,9 @@ mod tests {
                     height: 600,
                     window_class: "app2".to_string(),
                     title: "App 2".to_string(),
+                    frame_top: None,
+                    desktop: None,
+                    stacking_index: 0,
                 },
             ];
 
@@ -638,6 +5827,40 @@ mod tests {
             assert_eq!(result[&MonitorIndex(0)][0].id, WindowId(1));
             assert_eq!(result[&MonitorIndex(1)][0].id, WindowId(2));
         }
+
+        #[test]
+        fn test_includes_empty_monitors() {
+            let monitor_grid = MonitorGrid(vec![
+                vec![Monitor::new(1920, 1080), Monitor::new(1920, 1080)],
+                vec![Monitor::new(1920, 1080)],
+            ]);
+
+            // Only keep the window on the first monitor.
+            let (_, windows) = create_test_setup();
+            let windows = vec![windows[0].clone()];
+
+            let result = index_windows_by_monitor(&monitor_grid, &windows).unwrap();
+
+            assert_eq!(result.len(), 3);
+
+            for index in 0..3 {
+                assert!(result.contains_key(&MonitorIndex(index)));
+            }
+
+            assert_eq!(result[&MonitorIndex(0)].len(), 1);
+            assert!(result[&MonitorIndex(1)].is_empty());
+            assert!(result[&MonitorIndex(2)].is_empty());
+        }
+
+        #[test]
+        fn test_no_windows() {
+            let (monitor_grid, _) = create_test_setup();
+            let windows = vec![];
+            let result = index_windows_by_monitor(&monitor_grid, &windows).unwrap();
+
+            assert_eq!(result.len(), 2);
+            assert!(result.values().all(|windows| windows.is_empty()));
+        }
     }
 
     mod index_monitors_by_window {
@@ -654,6 +5877,36 @@ mod tests {
         }
     }
 
+    mod get_opposite_monitor {
+        use super::*;
+
+        fn get_opposite_indices(monitor_count: usize) -> Vec<usize> {
+            (0..monitor_count)
+                .map(|index| get_opposite_monitor(&MonitorIndex(index), monitor_count).0)
+                .collect()
+        }
+
+        #[test]
+        fn test_two_monitors() {
+            assert_eq!(get_opposite_indices(2), vec![1, 0]);
+        }
+
+        #[test]
+        fn test_three_monitors() {
+            assert_eq!(get_opposite_indices(3), vec![2, 1, 0]);
+        }
+
+        #[test]
+        fn test_four_monitors() {
+            assert_eq!(get_opposite_indices(4), vec![3, 2, 1, 0]);
+        }
+
+        #[test]
+        fn test_single_monitor() {
+            assert_eq!(get_opposite_indices(1), vec![0]);
+        }
+    }
+
     mod get_current_monitor {
         use super::*;
         use std::collections::HashMap;
@@ -665,10 +5918,19 @@ mod tests {
             monitors_by_window.insert(WindowId(2), MonitorIndex(1));
 
             let current_monitor = get_current_monitor(&WindowId(1), &monitors_by_window);
-            assert_eq!(current_monitor, MonitorIndex(0));
+            assert_eq!(current_monitor, Some(MonitorIndex(0)));
 
             let current_monitor = get_current_monitor(&WindowId(2), &monitors_by_window);
-            assert_eq!(current_monitor, MonitorIndex(1));
+            assert_eq!(current_monitor, Some(MonitorIndex(1)));
+        }
+
+        #[test]
+        fn test_untracked_window() {
+            let monitors_by_window = HashMap::from([(WindowId(1), MonitorIndex(0))]);
+            assert_eq!(
+                get_current_monitor(&WindowId(42), &monitors_by_window),
+                None
+            );
         }
     }
 }